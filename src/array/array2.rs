@@ -1,19 +1,22 @@
+use crate::mat::affine2::Affine2f;
 use crate::mat::mat2::Mat2f;
 use crate::vec::vec2::Vec2;
+use alloc::vec::Vec;
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 /// Collection of 2d float vectors
-#[wasm_bindgen(js_name=Array2f, inspectable)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=Array2f, inspectable))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Array2f {
-    #[wasm_bindgen(skip)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
     pub data: Vec<Vec2>,
 }
 
-#[wasm_bindgen(js_class=Array2f)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=Array2f))]
 impl Array2f {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self { data: Vec::new() }
     }
@@ -21,6 +24,7 @@ impl Array2f {
     /// Extends this instance with a list of `{ x, y }` points.
     ///
     /// Invalid items will be dropped
+    #[cfg(feature = "wasm")]
     #[wasm_bindgen]
     pub fn extend(&mut self, list: Box<[JsValue]>) {
         self.data.extend(
@@ -30,32 +34,33 @@ impl Array2f {
         )
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn push(&mut self, v: Vec2) {
         self.data.push(v);
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn len(&self) -> u64 {
         self.data.len() as u64
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get(&self, i: usize) -> Vec2 {
         self.data[i]
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn set(&mut self, i: usize, v: Vec2) {
         *self.data.get_mut(i).expect("Invalid index") = v;
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn remove(&mut self, i: usize) -> Vec2 {
         self.data.remove(i)
     }
 
     /// Turn this instance into a list of vectors
+    #[cfg(feature = "wasm")]
     #[wasm_bindgen(js_name=toList)]
     pub fn to_list(&self) -> Box<[JsValue]> {
         self.data
@@ -65,13 +70,29 @@ impl Array2f {
             .into_boxed_slice()
     }
 
-    #[wasm_bindgen(js_name=rightProd)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=rightProd))]
     pub fn right_prod(&mut self, m: &Mat2f) {
         self.data.iter_mut().for_each(|v| *v = m.right_prod(*v));
     }
 
-    #[wasm_bindgen(js_name=leftProd)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=leftProd))]
     pub fn left_prod(&mut self, m: &Mat2f) {
         self.data.iter_mut().for_each(|v| *v = m.left_prod(*v));
     }
+
+    /// Applies `a` to every point, including its translation
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=transformPoints))]
+    pub fn transform_points(&mut self, a: &Affine2f) {
+        self.data
+            .iter_mut()
+            .for_each(|p| *p = a.transform_point(*p));
+    }
+
+    /// Applies the linear part of `a` to every vector
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=transformVectors))]
+    pub fn transform_vectors(&mut self, a: &Affine2f) {
+        self.data
+            .iter_mut()
+            .for_each(|v| *v = a.transform_vector(*v));
+    }
 }
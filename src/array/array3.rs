@@ -1,19 +1,21 @@
 use crate::mat::mat3::Mat3f;
 use crate::vec::vec3::Vec3;
+use alloc::vec::Vec;
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 /// Collection of 3d float vectors
-#[wasm_bindgen(js_name=Array3f, inspectable)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=Array3f, inspectable))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Array3f {
-    #[wasm_bindgen(skip)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
     pub data: Vec<Vec3>,
 }
 
-#[wasm_bindgen(js_class=Array3f)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=Array3f))]
 impl Array3f {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self { data: Vec::new() }
     }
@@ -21,6 +23,7 @@ impl Array3f {
     /// Extends this instance with a list of `{ x, y, z }` points.
     ///
     /// Invalid items will be dropped
+    #[cfg(feature = "wasm")]
     #[wasm_bindgen]
     pub fn extend(&mut self, list: Box<[JsValue]>) {
         self.data.extend(
@@ -30,32 +33,33 @@ impl Array3f {
         )
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn push(&mut self, v: Vec3) {
         self.data.push(v);
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn len(&self) -> u64 {
         self.data.len() as u64
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn get(&self, i: usize) -> Vec3 {
         self.data[i]
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn set(&mut self, i: usize, v: Vec3) {
         *self.data.get_mut(i).expect("Invalid index") = v;
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn remove(&mut self, i: usize) -> Vec3 {
         self.data.remove(i)
     }
 
     /// Turn this instance into a list of vectors
+    #[cfg(feature = "wasm")]
     #[wasm_bindgen(js_name=toList)]
     pub fn to_list(&self) -> Box<[JsValue]> {
         self.data
@@ -66,13 +70,13 @@ impl Array3f {
     }
 
     /// Perform `M*v` for each vector `v` in this array
-    #[wasm_bindgen(js_name=rightProd)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=rightProd))]
     pub fn right_prod(&mut self, m: &Mat3f) {
         self.data.iter_mut().for_each(|v| *v = m.right_prod(v));
     }
 
     /// Perform `v*M` for each vector `v` in this array
-    #[wasm_bindgen(js_name=leftProd)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=leftProd))]
     pub fn left_prod(&mut self, m: &Mat3f) {
         self.data.iter_mut().for_each(|v| *v = m.left_prod(v));
     }
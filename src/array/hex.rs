@@ -0,0 +1,176 @@
+//! Hex grid operations on arrays of `cube` (`Array3f`) or `axial` (`Array2f`) coordinates
+use super::{Array2f, Array3f};
+use crate::hex::{
+    axial_to_cube, cube_distance, cube_to_axial, round_to_nearest_axial, Orientation,
+};
+use crate::vec::vec2::Vec2;
+use crate::vec::vec3::Vec3;
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=Array3f))]
+impl Array3f {
+    /// Sorts the `cube` points in place by their distance from `center`.
+    ///
+    /// The sort is stable, points at equal distance keep their order.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=sortByCubeDistance))]
+    pub fn sort_by_cube_distance(&mut self, center: &Vec3) {
+        self.data.sort_by_key(|p| cube_distance(p, center));
+    }
+
+    /// Returns the `cube` points that are at most `radius` away from `center`
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=withinCubeDistance))]
+    pub fn within_cube_distance(&self, center: &Vec3, radius: u32) -> Array3f {
+        let data = within(&self.data, radius, |p| cube_distance(p, center));
+        Array3f { data }
+    }
+
+    /// Returns the `k` `cube` points closest to `center`, ordered by their distance.
+    ///
+    /// Points at equal distance keep their order.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn nearest(&self, center: &Vec3, k: usize) -> Array3f {
+        let data = nearest(&self.data, k, |p| cube_distance(p, center));
+        Array3f { data }
+    }
+
+    /// Returns the `axial` representation of the `cube` points
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=toAxial))]
+    pub fn to_axial(&self) -> Array2f {
+        let data = self.data.iter().map(cube_to_axial).collect();
+        Array2f { data }
+    }
+
+    /// Groups the `cube` points by their distance from `center`.
+    ///
+    /// Returns `radius + 1` arrays, where the `i`th array holds the points on the ring of radius
+    /// `i`. Points further than `radius` are left out.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=ringBuckets))]
+    pub fn ring_buckets(&self, center: &Vec3, radius: u32) -> Vec<Array3f> {
+        ring_buckets(&self.data, radius, |p| cube_distance(p, center))
+            .into_iter()
+            .map(|data| Array3f { data })
+            .collect()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=Array2f))]
+impl Array2f {
+    /// Sorts the `axial` points in place by their distance from `center`.
+    ///
+    /// The sort is stable, points at equal distance keep their order.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=sortByCubeDistance))]
+    pub fn sort_by_cube_distance(&mut self, center: &Vec2) {
+        let distance = axial_distance_from(center);
+        self.data.sort_by_key(distance);
+    }
+
+    /// Returns the `axial` points that are at most `radius` away from `center`
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=withinCubeDistance))]
+    pub fn within_cube_distance(&self, center: &Vec2, radius: u32) -> Array2f {
+        let data = within(&self.data, radius, axial_distance_from(center));
+        Array2f { data }
+    }
+
+    /// Returns the `k` `axial` points closest to `center`, ordered by their distance.
+    ///
+    /// Points at equal distance keep their order.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn nearest(&self, center: &Vec2, k: usize) -> Array2f {
+        let data = nearest(&self.data, k, axial_distance_from(center));
+        Array2f { data }
+    }
+
+    /// Returns the `cube` representation of the `axial` points
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=toCube))]
+    pub fn to_cube(&self) -> Array3f {
+        let data = self.data.iter().map(axial_to_cube).collect();
+        Array3f { data }
+    }
+
+    /// Rounds every fractional `axial` point in place to the hex containing it
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=roundToNearestAxial))]
+    pub fn round_to_nearest_axial(&mut self) {
+        self.data
+            .iter_mut()
+            .for_each(|p| *p = round_to_nearest_axial(p.x, p.y));
+    }
+
+    /// Converts the pixel positions in place to the `axial` coordinates of the hexes containing
+    /// them.
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=pixelToAxial))]
+    pub fn pixel_to_axial(&mut self, orientation: Orientation, size: f32) {
+        let m = orientation.pixel_to_axial();
+        self.data.iter_mut().for_each(|p| {
+            let Vec2 { x, y } = m.right_prod(*p) / size;
+            *p = round_to_nearest_axial(x, y);
+        });
+    }
+
+    /// Converts the `axial` points in place to the pixel positions of their hex centers.
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=axialToPixel))]
+    pub fn axial_to_pixel(&mut self, orientation: Orientation, size: f32) {
+        let m = orientation.axial_to_pixel();
+        self.data
+            .iter_mut()
+            .for_each(|p| *p = m.right_prod(*p) * size);
+    }
+
+    /// Groups the `axial` points by their distance from `center`.
+    ///
+    /// Returns `radius + 1` arrays, where the `i`th array holds the points on the ring of radius
+    /// `i`. Points further than `radius` are left out.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=ringBuckets))]
+    pub fn ring_buckets(&self, center: &Vec2, radius: u32) -> Vec<Array2f> {
+        ring_buckets(&self.data, radius, axial_distance_from(center))
+            .into_iter()
+            .map(|data| Array2f { data })
+            .collect()
+    }
+}
+
+fn axial_distance_from(center: &Vec2) -> impl Fn(&Vec2) -> u32 {
+    let center = axial_to_cube(center);
+    move |p| cube_distance(&axial_to_cube(p), &center)
+}
+
+fn within<T: Copy>(data: &[T], radius: u32, distance: impl Fn(&T) -> u32) -> Vec<T> {
+    data.iter()
+        .filter(|p| distance(p) <= radius)
+        .copied()
+        .collect()
+}
+
+/// Partial selection of the `k` nearest points, instead of sorting all of them
+fn nearest<T: Copy>(data: &[T], k: usize, distance: impl Fn(&T) -> u32) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+    // the index breaks ties, so the result does not depend on the selection algorithm
+    let mut keys = data
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (distance(p), i))
+        .collect::<Vec<_>>();
+    if k < keys.len() {
+        keys.select_nth_unstable(k - 1);
+        keys.truncate(k);
+    }
+    keys.sort_unstable();
+    keys.into_iter().map(|(_, i)| data[i]).collect()
+}
+
+fn ring_buckets<T: Copy>(data: &[T], radius: u32, distance: impl Fn(&T) -> u32) -> Vec<Vec<T>> {
+    let mut buckets = vec![Vec::new(); radius as usize + 1];
+    for p in data {
+        if let Some(bucket) = buckets.get_mut(distance(p) as usize) {
+            bucket.push(*p);
+        }
+    }
+    buckets
+}
@@ -2,6 +2,7 @@
 
 mod array2;
 mod array3;
+mod hex;
 
 pub use array2::*;
 pub use array3::*;
@@ -0,0 +1,146 @@
+//! Passing generic types to and from JS
+//!
+//! wasm-bindgen can not export generic structs, so the `f32` instances of the generic types are
+//! exported by a plain wrapper struct with the JS methods. `wasm_abi_via!` lets the generic type
+//! cross the boundary as its wrapper, so exported functions can take and return `Vec2<f32>` and
+//! friends directly. Values are copied in and out of the JS owned wrapper.
+use core::ops::{Deref, DerefMut};
+
+/// Copy of a value borrowed from JS
+pub struct Borrowed<T>(pub(crate) T);
+
+impl<T> Deref for Borrowed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Copy of a value mutably borrowed from JS, written back to the wrapper when dropped
+pub struct BorrowedMut<T, W, A>
+where
+    T: Clone + Into<W>,
+    A: DerefMut<Target = W>,
+{
+    pub(crate) value: T,
+    pub(crate) anchor: A,
+}
+
+impl<T, W, A> Deref for BorrowedMut<T, W, A>
+where
+    T: Clone + Into<W>,
+    A: DerefMut<Target = W>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, W, A> DerefMut for BorrowedMut<T, W, A>
+where
+    T: Clone + Into<W>,
+    A: DerefMut<Target = W>,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T, W, A> Drop for BorrowedMut<T, W, A>
+where
+    T: Clone + Into<W>,
+    A: DerefMut<Target = W>,
+{
+    fn drop(&mut self) {
+        *self.anchor = self.value.clone().into();
+    }
+}
+
+/// Implements the wasm-bindgen conversions of `$ty` by converting it to and from `$js`, an
+/// exported struct. Both types must be `Clone` and convertible into each other.
+macro_rules! wasm_abi_via {
+    ($ty:ty, $js:ty) => {
+        impl wasm_bindgen::describe::WasmDescribe for $ty {
+            fn describe() {
+                <$js as wasm_bindgen::describe::WasmDescribe>::describe()
+            }
+        }
+
+        impl wasm_bindgen::convert::IntoWasmAbi for $ty {
+            type Abi = <$js as wasm_bindgen::convert::IntoWasmAbi>::Abi;
+
+            fn into_abi(self) -> Self::Abi {
+                <$js>::from(self).into_abi()
+            }
+        }
+
+        impl wasm_bindgen::convert::FromWasmAbi for $ty {
+            type Abi = <$js as wasm_bindgen::convert::FromWasmAbi>::Abi;
+
+            unsafe fn from_abi(js: Self::Abi) -> Self {
+                <$js as wasm_bindgen::convert::FromWasmAbi>::from_abi(js).into()
+            }
+        }
+
+        impl wasm_bindgen::convert::OptionIntoWasmAbi for $ty {
+            fn none() -> Self::Abi {
+                <$js as wasm_bindgen::convert::OptionIntoWasmAbi>::none()
+            }
+        }
+
+        impl wasm_bindgen::convert::OptionFromWasmAbi for $ty {
+            fn is_none(abi: &Self::Abi) -> bool {
+                <$js as wasm_bindgen::convert::OptionFromWasmAbi>::is_none(abi)
+            }
+        }
+
+        impl wasm_bindgen::convert::RefFromWasmAbi for $ty {
+            type Abi = <$js as wasm_bindgen::convert::RefFromWasmAbi>::Abi;
+            type Anchor = crate::bindings::Borrowed<$ty>;
+
+            unsafe fn ref_from_abi(js: Self::Abi) -> Self::Anchor {
+                let anchor = <$js as wasm_bindgen::convert::RefFromWasmAbi>::ref_from_abi(js);
+                crate::bindings::Borrowed(<$js as Clone>::clone(&anchor).into())
+            }
+        }
+
+        impl wasm_bindgen::convert::RefMutFromWasmAbi for $ty {
+            type Abi = <$js as wasm_bindgen::convert::RefMutFromWasmAbi>::Abi;
+            type Anchor = crate::bindings::BorrowedMut<
+                $ty,
+                $js,
+                <$js as wasm_bindgen::convert::RefMutFromWasmAbi>::Anchor,
+            >;
+
+            unsafe fn ref_mut_from_abi(js: Self::Abi) -> Self::Anchor {
+                let anchor =
+                    <$js as wasm_bindgen::convert::RefMutFromWasmAbi>::ref_mut_from_abi(js);
+                crate::bindings::BorrowedMut {
+                    value: <$js as Clone>::clone(&anchor).into(),
+                    anchor,
+                }
+            }
+        }
+
+        impl From<$ty> for wasm_bindgen::JsValue {
+            fn from(value: $ty) -> Self {
+                <$js>::from(value).into()
+            }
+        }
+    };
+}
+
+/// Converts the error type `$err` into a JS `Error` with its message, so exported functions can
+/// throw it with `?`
+macro_rules! impl_js_error {
+    ($err:ty) => {
+        impl From<$err> for wasm_bindgen::JsValue {
+            fn from(err: $err) -> wasm_bindgen::JsValue {
+                js_sys::Error::new(&err.to_string()).into()
+            }
+        }
+    };
+}
@@ -0,0 +1,413 @@
+//! Deterministic fixed-point numbers
+//!
+//! Floating point results may differ between JS engines and native builds, these types only use
+//! integer arithmetic so every operation, including `sqrt`, the trigonometric functions and `exp`,
+//! gives bit-identical results on every target. They implement `Float`, so vectors, matrices and
+//! the hex rounding functions can be used with them.
+//!
+//! Like the integer types, overflowing operations panic in debug builds and wrap in release
+//! builds.
+use crate::scalar::{Float, Scalar};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+
+/// Number of terms of the sine series, enough for the precision of both types
+const SIN_TERMS: i32 = 8;
+/// Number of terms of the arctangent series, after reducing the argument below `tan(pi/8)`
+const ATAN_TERMS: i32 = 14;
+/// Number of terms of the exponential series, for arguments in `[0, ln 2)`
+const EXP_TERMS: i32 = 14;
+
+macro_rules! fixed_point {
+    (
+        $(#[$meta:meta])*
+        $name:ident($bits:ty, wide: $wide:ty, unsigned_wide: $uwide:ty, frac: $frac:expr),
+        pi: $pi:expr,
+        frac_pi_2: $frac_pi_2:expr,
+        tau: $tau:expr,
+        ln_2: $ln_2:expr
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+        pub struct $name($bits);
+
+        impl $name {
+            /// Number of fractional bits
+            pub const FRAC_BITS: u32 = $frac;
+            pub const MIN: Self = Self(<$bits>::MIN);
+            pub const MAX: Self = Self(<$bits>::MAX);
+            /// The smallest positive number
+            pub const DELTA: Self = Self(1);
+            pub const HALF: Self = Self(1 << ($frac - 1));
+            pub const PI: Self = Self($pi);
+            pub const FRAC_PI_2: Self = Self($frac_pi_2);
+            pub const TAU: Self = Self($tau);
+            pub const LN_2: Self = Self($ln_2);
+
+            /// Interprets `bits` as a number with `FRAC_BITS` fractional bits
+            pub const fn from_bits(bits: $bits) -> Self {
+                Self(bits)
+            }
+
+            pub const fn to_bits(self) -> $bits {
+                self.0
+            }
+
+            pub const fn from_int(n: $bits) -> Self {
+                Self(n << $frac)
+            }
+
+            /// Returns the integer part, rounded towards negative infinity
+            pub const fn to_int(self) -> $bits {
+                self.0 >> $frac
+            }
+
+            /// Converts the float to the nearest fixed-point number, saturating at `MIN` and
+            /// `MAX`
+            pub fn from_f64(x: f64) -> Self {
+                // multiplying by a power of two is exact, so the conversion is deterministic too
+                let scaled = x * (1u64 << $frac) as f64;
+                let rounded = if scaled < 0. {
+                    scaled - 0.5
+                } else {
+                    scaled + 0.5
+                };
+                Self(rounded as $bits)
+            }
+
+            pub fn from_f32(x: f32) -> Self {
+                Self::from_f64(x as f64)
+            }
+
+            pub fn to_f64(self) -> f64 {
+                self.0 as f64 / (1u64 << $frac) as f64
+            }
+
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            /// Narrows the widened result of `op` like the integer operators do: panics in debug
+            /// builds if it does not fit, wraps in release builds
+            fn narrow(wide: $wide, op: &str) -> Self {
+                debug_assert!(<$bits>::try_from(wide).is_ok(), "attempt to {} with overflow", op);
+                Self(wide as $bits)
+            }
+
+            fn div_int(self, n: $bits) -> Self {
+                Self(self.0 / n)
+            }
+
+            fn is_negative(self) -> bool {
+                self.0 < 0
+            }
+
+            /// `atan` of `z` in `[0, 1]`
+            fn atan_unit(self) -> Self {
+                // atan(z) = 2 atan(z / (1 + sqrt(1 + z^2))) brings z below tan(pi / 8)
+                let z = self / (Self::ONE + (Self::ONE + self * self).sqrt());
+                let z2 = z * z;
+                let mut acc = Self::ZERO;
+                for k in (0..ATAN_TERMS).rev() {
+                    acc = Self::ONE.div_int(2 * k as $bits + 1) - z2 * acc;
+                }
+                z * acc * Self::from_int(2)
+            }
+        }
+
+        impl Scalar for $name {
+            const ZERO: Self = Self(0);
+            const ONE: Self = Self(1 << $frac);
+        }
+
+        impl Float for $name {
+            const EPSILON: Self = Self(1);
+
+            fn abs(self) -> Self {
+                if self.is_negative() {
+                    -self
+                } else {
+                    self
+                }
+            }
+
+            /// Negative numbers have no square root, returns zero for them
+            fn sqrt(self) -> Self {
+                if self.0 <= 0 {
+                    return Self::ZERO;
+                }
+                // sqrt(x * 2^frac) * 2^frac = sqrt(x * 2^(2 frac)), computed bit by bit
+                let n = (self.0 as $uwide) << $frac;
+                let mut rem = n;
+                let mut res: $uwide = 0;
+                let mut bit: $uwide = 1 << (<$uwide>::BITS - 2);
+                while bit > n {
+                    bit >>= 2;
+                }
+                while bit != 0 {
+                    if rem >= res + bit {
+                        rem -= res + bit;
+                        res = (res >> 1) + bit;
+                    } else {
+                        res >>= 1;
+                    }
+                    bit >>= 2;
+                }
+                Self(res as $bits)
+            }
+
+            fn sin(self) -> Self {
+                // reduce to [-pi, pi], then to [-pi/2, pi/2] using sin(x) = sin(pi - x)
+                let mut x = self % Self::TAU;
+                if x > Self::PI {
+                    x -= Self::TAU;
+                } else if x < -Self::PI {
+                    x += Self::TAU;
+                }
+                if x > Self::FRAC_PI_2 {
+                    x = Self::PI - x;
+                } else if x < -Self::FRAC_PI_2 {
+                    x = -Self::PI - x;
+                }
+                // x (1 - x^2/(2*3) (1 - x^2/(4*5) (1 - ...)))
+                let x2 = x * x;
+                let mut acc = Self::ONE;
+                for k in (1..=SIN_TERMS).rev() {
+                    let k = k as $bits;
+                    acc = Self::ONE - (x2 * acc).div_int(2 * k * (2 * k + 1));
+                }
+                x * acc
+            }
+
+            fn cos(self) -> Self {
+                (self % Self::TAU + Self::FRAC_PI_2).sin()
+            }
+
+            /// Saturates at `MIN` or `MAX` where the cosine is zero
+            fn tan(self) -> Self {
+                let cos = self.cos();
+                let sin = self.sin();
+                if cos == Self::ZERO {
+                    return if sin.is_negative() {
+                        Self::MIN
+                    } else {
+                        Self::MAX
+                    };
+                }
+                sin / cos
+            }
+
+            /// The argument is clamped to `[-1, 1]`
+            fn acos(self) -> Self {
+                let x = if self > Self::ONE {
+                    Self::ONE
+                } else if self < -Self::ONE {
+                    -Self::ONE
+                } else {
+                    self
+                };
+                (Self::ONE - x * x).sqrt().atan2(x)
+            }
+
+            fn atan2(self, x: Self) -> Self {
+                let y = self;
+                if x == Self::ZERO && y == Self::ZERO {
+                    return Self::ZERO;
+                }
+                let (ax, ay) = (x.abs(), y.abs());
+                let mut angle = match ay.cmp(&ax) {
+                    Ordering::Greater => Self::FRAC_PI_2 - (ax / ay).atan_unit(),
+                    _ => (ay / ax).atan_unit(),
+                };
+                if x.is_negative() {
+                    angle = Self::PI - angle;
+                }
+                if y.is_negative() {
+                    angle = -angle;
+                }
+                angle
+            }
+
+            /// Saturates at `MAX` if the result is not representable
+            fn exp(self) -> Self {
+                // exp(x) = 2^k exp(r) where x = k ln 2 + r and r is in [0, ln 2)
+                let k = (((self.0 as $wide) << $frac).div_euclid(Self::LN_2.0 as $wide)) >> $frac;
+                let int_bits = (<$bits>::BITS - $frac) as $wide;
+                if k >= int_bits - 1 {
+                    return Self::MAX;
+                }
+                if k < -($frac as $wide) - 1 {
+                    return Self::ZERO;
+                }
+                let r = Self(self.0 - (Self::LN_2.0 as $wide * k) as $bits);
+                let mut acc = Self::ONE;
+                for n in (1..=EXP_TERMS).rev() {
+                    acc = Self::ONE + (r * acc).div_int(n as $bits);
+                }
+                if k >= 0 {
+                    let k = k as u32;
+                    if acc.0 > <$bits>::MAX >> k {
+                        return Self::MAX;
+                    }
+                    Self(acc.0 << k)
+                } else {
+                    Self(acc.0 >> (-k) as u32)
+                }
+            }
+
+            fn floor(self) -> Self {
+                Self(self.0 & !(Self::ONE.0 - 1))
+            }
+
+            fn ceil(self) -> Self {
+                let floor = self.floor();
+                if floor == self {
+                    floor
+                } else {
+                    floor + Self::ONE
+                }
+            }
+
+            /// Rounds half-way cases away from zero, like `f32::round`
+            fn round(self) -> Self {
+                if self.is_negative() {
+                    -(-self + Self::HALF).floor()
+                } else {
+                    (self + Self::HALF).floor()
+                }
+            }
+        }
+
+        impl From<$bits> for $name {
+            fn from(n: $bits) -> Self {
+                Self::from_int(n)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f64(), f)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.to_f64())
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+
+            /// Rounds to the nearest number, half-way cases up
+            fn mul(self, rhs: Self) -> Self {
+                let product = self.0 as $wide * rhs.0 as $wide;
+                Self::narrow((product + (1 << ($frac - 1))) >> $frac, "multiply")
+            }
+        }
+
+        impl Div for $name {
+            type Output = Self;
+
+            /// Rounds to the nearest number, half-way cases away from zero. Panics if `rhs` is
+            /// zero.
+            fn div(self, rhs: Self) -> Self {
+                let n = (self.0 as $wide) << $frac;
+                let d = rhs.0 as $wide;
+                let (q, r) = (n / d, n % d);
+                if 2 * r.abs() >= d.abs() {
+                    Self::narrow(q + (n.signum() * d.signum()), "divide")
+                } else {
+                    Self::narrow(q, "divide")
+                }
+            }
+        }
+
+        impl Rem for $name {
+            type Output = Self;
+
+            fn rem(self, rhs: Self) -> Self {
+                Self(self.0 % rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl RemAssign for $name {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+    };
+}
+
+fixed_point!(
+    /// Q16.16 fixed-point number: 16 integer and 16 fractional bits in an `i32`
+    Fixed32(i32, wide: i64, unsigned_wide: u64, frac: 16),
+    pi: 205_887,
+    frac_pi_2: 102_944,
+    tau: 411_775,
+    ln_2: 45_426
+);
+
+fixed_point!(
+    /// Q32.32 fixed-point number: 32 integer and 32 fractional bits in an `i64`
+    Fixed64(i64, wide: i128, unsigned_wide: u128, frac: 32),
+    pi: 13_493_037_705,
+    frac_pi_2: 6_746_518_852,
+    tau: 26_986_075_409,
+    ln_2: 2_977_044_472
+);
@@ -1,17 +1,82 @@
 //! Hex-Grid utilities
+#[cfg(feature = "alloc")]
+mod ascii;
+#[cfg(feature = "alloc")]
+mod codec;
+#[cfg(feature = "alloc")]
+mod cone;
+#[cfg(feature = "alloc")]
+mod convolution;
+#[cfg(feature = "alloc")]
+mod diff;
+mod direction;
+#[cfg(feature = "alloc")]
+mod edge;
 mod hexagon;
+#[cfg(feature = "alloc")]
+mod map;
+#[cfg(feature = "alloc")]
+mod moves;
+#[cfg(feature = "alloc")]
+mod ray;
+#[cfg(feature = "alloc")]
+mod region;
+#[cfg(feature = "alloc")]
+mod smooth;
+#[cfg(feature = "std")]
+mod spatial;
+#[cfg(feature = "alloc")]
+mod tiled;
+#[cfg(feature = "alloc")]
+pub use ascii::*;
+#[cfg(feature = "alloc")]
+pub use codec::*;
+#[cfg(feature = "alloc")]
+pub use cone::*;
+#[cfg(feature = "alloc")]
+pub use convolution::*;
+#[cfg(feature = "alloc")]
+pub use diff::*;
+pub use direction::*;
+#[cfg(feature = "alloc")]
+pub use edge::*;
 pub use hexagon::*;
+#[cfg(feature = "alloc")]
+pub use map::*;
+#[cfg(feature = "alloc")]
+pub use moves::*;
+#[cfg(feature = "alloc")]
+pub use ray::*;
+#[cfg(feature = "alloc")]
+pub use region::*;
+#[cfg(feature = "alloc")]
+pub use smooth::*;
+#[cfg(feature = "std")]
+pub use spatial::*;
+#[cfg(feature = "alloc")]
+pub use tiled::*;
 
+#[cfg(feature = "alloc")]
 use crate::array::Array3f;
 use crate::mat::mat2::Mat2f;
+use crate::scalar;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use crate::scalar::Float;
 use crate::vec::vec2::Vec2;
 use crate::vec::vec3::Vec3;
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 const SQRT3APROX: f32 = 1.732_050_807_57;
 
+/// Axial offsets of the 6 neighbours of a hex, in counter-clockwise order
+pub(crate) const AXIAL_NEIGHBOURS: [[i32; 2]; 6] =
+    [[1, 0], [1, -1], [0, -1], [-1, 0], [-1, 1], [0, 1]];
+
 /// Converts a point on the hex grid from `cube` representation to `axial` representation
-#[wasm_bindgen(js_name = cubeToAxial)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = cubeToAxial))]
 pub fn cube_to_axial(cube: &Vec3) -> Vec2 {
     let q = cube.x;
     let r = cube.z;
@@ -19,7 +84,7 @@ pub fn cube_to_axial(cube: &Vec3) -> Vec2 {
 }
 
 /// Converts a point on the hex grid from `axial` representation to `cube` representation
-#[wasm_bindgen(js_name = axialToCube)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = axialToCube))]
 pub fn axial_to_cube(axial: &Vec2) -> Vec3 {
     let x = axial.x;
     let z = axial.y;
@@ -28,7 +93,7 @@ pub fn axial_to_cube(axial: &Vec2) -> Vec3 {
 }
 
 /// 'Manhatten-esque' distance between two 'cube' points on the hex grid
-#[wasm_bindgen(js_name = cubeDistance)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = cubeDistance))]
 pub fn cube_distance(a: &Vec3, b: &Vec3) -> u32 {
     let x = (a.x - b.x).abs();
     let y = (a.y - b.y).abs();
@@ -36,9 +101,45 @@ pub fn cube_distance(a: &Vec3, b: &Vec3) -> u32 {
     x.max(y).max(z) as u32
 }
 
+/// Layout of the hexes of a grid
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = HexOrientation))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub enum Orientation {
+    /// Hexes have a vertex on top, rows are horizontal
+    Pointy = 0,
+    /// Hexes have an edge on top, columns are vertical
+    Flat = 1,
+}
+
+impl Orientation {
+    /// See `axial_to_pixel_mat_pointy` and `axial_to_pixel_mat_flat`
+    pub fn axial_to_pixel(self) -> Mat2f {
+        match self {
+            Orientation::Pointy => axial_to_pixel_mat_pointy(),
+            Orientation::Flat => axial_to_pixel_mat_flat(),
+        }
+    }
+
+    /// See `pixel_to_axial_pointy` and `pixel_to_axial_flat`
+    pub fn pixel_to_axial(self) -> Mat2f {
+        match self {
+            Orientation::Pointy => pixel_to_axial_pointy(),
+            Orientation::Flat => pixel_to_axial_flat(),
+        }
+    }
+
+    /// Pixel position of the center of the `axial` hex
+    ///
+    /// `size` is the distance between the center and the vertices of a hex
+    pub fn hex_center(self, axial: Vec2, size: f32) -> Vec2 {
+        self.axial_to_pixel().right_prod(axial) * size
+    }
+}
+
 /// Calculate the pixel position of the hex point represented by axial coordinates by using
 /// `rightProd`. Assumes "pointy top grid".
-#[wasm_bindgen(js_name = axialToPixelMatrixPointy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = axialToPixelMatrixPointy))]
 pub fn axial_to_pixel_mat_pointy() -> Mat2f {
     let mat: Mat2f = [[SQRT3APROX, 0.], [SQRT3APROX / 2.0, 3. / 2.]].into();
     mat
@@ -46,7 +147,7 @@ pub fn axial_to_pixel_mat_pointy() -> Mat2f {
 
 /// Calculate the pixel position of the hex point represented by axial coordinates by using
 /// `rightProd`. Assumes "flat top grid".
-#[wasm_bindgen(js_name = axialToPixelMatrixFlat)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = axialToPixelMatrixFlat))]
 pub fn axial_to_pixel_mat_flat() -> Mat2f {
     let mat: Mat2f = [[3. / 2., SQRT3APROX / 2.], [0., SQRT3APROX]].into();
     mat
@@ -54,7 +155,7 @@ pub fn axial_to_pixel_mat_flat() -> Mat2f {
 
 /// Calculate the axial hex grid position of the pixel by using
 /// `rightProd`. Assumes "pointy top grid".
-#[wasm_bindgen(js_name = pixelToAxialMatrixPointy)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = pixelToAxialMatrixPointy))]
 pub fn pixel_to_axial_pointy() -> Mat2f {
     let mat: Mat2f = [[SQRT3APROX / 3., 0.], [-1. / 3., 2. / 3.]].into();
     mat
@@ -62,14 +163,22 @@ pub fn pixel_to_axial_pointy() -> Mat2f {
 
 /// Calculate the axial hex grid position of the pixel by using
 /// `rightProd`. Assumes "flat top grid".
-#[wasm_bindgen(js_name = pixelToAxialMatrixFlat)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = pixelToAxialMatrixFlat))]
 pub fn pixel_to_axial_flat() -> Mat2f {
     let mat: Mat2f = [[2. / 3., -1. / 3.], [0., SQRT3APROX / 3.]].into();
     mat.into()
 }
 
-#[wasm_bindgen(js_name = roundToNearestAxial)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = roundToNearestAxial))]
 pub fn round_to_nearest_axial(q: f32, r: f32) -> Vec2 {
+    axial_round(Vec2::new(q, r))
+}
+
+/// Rounds the fractional axial point to the axial coordinates of the hex containing it.
+///
+/// Works with any `Float`, use a fixed-point type for results that are identical on every
+/// target.
+pub fn axial_round<T: scalar::Float>(Vec2 { x: q, y: r }: Vec2<T>) -> Vec2<T> {
     // convert to cube
     let [x, z] = [q, r];
     let y = -x - z;
@@ -87,7 +196,8 @@ pub fn round_to_nearest_axial(q: f32, r: f32) -> Vec2 {
 
 /// Return a list of points, each point will be inside a hex that is intersected by the segment
 /// between points `a` and `b`, skipping `a` and `b`.
-#[wasm_bindgen(js_name=cubeSegmentPoints)]
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=cubeSegmentPoints))]
 pub fn cube_segment_points(a: &Vec3, b: &Vec3) -> Array3f {
     let n = cube_distance(a, b);
     let np1 = 1. / (n as f32);
@@ -96,10 +206,23 @@ pub fn cube_segment_points(a: &Vec3, b: &Vec3) -> Array3f {
     Array3f { data }
 }
 
+#[cfg(feature = "alloc")]
 fn lerp(a: f32, b: f32, t: f32) -> f32 {
     a + (b - a) * t
 }
 
+#[cfg(feature = "alloc")]
 fn cube_lerp(a: &Vec3, b: &Vec3, t: f32) -> Vec3 {
     [lerp(a.x, b.x, t), lerp(a.y, b.y, t), lerp(a.z, b.z, t)].into()
 }
+
+#[cfg(feature = "alloc")]
+/// Rounds the axial point to integer coordinates
+pub(crate) fn to_axial(p: &Vec2) -> [i32; 2] {
+    [p.x.round() as i32, p.y.round() as i32]
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn from_axial([q, r]: &[i32; 2]) -> Vec2 {
+    Vec2::new(*q as f32, *r as f32)
+}
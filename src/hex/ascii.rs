@@ -0,0 +1,211 @@
+//! Plain text drawings of hex maps, mostly useful for test fixtures
+//!
+//! Every hex is a single non-whitespace character. With pointy hexes rows are lines, and every
+//! row is shifted right by one character relative to the row above:
+//!
+//! ```txt
+//!   . . .
+//!  . # # .
+//! . # A # .
+//!  . # # .
+//!   . . .
+//! ```
+//!
+//! With flat hexes columns are drawn two characters apart, and every column is shifted down by
+//! one line relative to the column to its left:
+//!
+//! ```txt
+//!   .
+//! .   .
+//!   A
+//! .   .
+//!   .
+//! ```
+//!
+//! Drawings have no absolute position: when parsing, the first character of the first non-blank
+//! line is placed at a given axial `origin`, so the indentation of the text does not matter.
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{HexMap, Hexagon, Orientation};
+#[cfg(feature = "wasm")]
+use {
+    super::{from_axial, to_axial},
+    crate::array::Array2f,
+    crate::vec::vec2::Vec2,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsciiError {
+    /// The character at the zero based line and column is between two hexes
+    Misaligned { line: usize, column: usize },
+    /// A tile is outside of the hexagon of the map
+    OutsideMap([i32; 2]),
+    /// There are fewer tiles than points
+    NotEnoughTiles,
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsciiError::Misaligned { line, column } => write!(
+                f,
+                "Character at line {}, column {} is not aligned to the grid",
+                line + 1,
+                column + 1
+            ),
+            AsciiError::OutsideMap([q, r]) => {
+                write!(f, "Tile ({}, {}) is outside of the map", q, r)
+            }
+            AsciiError::NotEnoughTiles => write!(f, "Not enough tiles for the points"),
+        }
+    }
+}
+
+impl core::error::Error for AsciiError {}
+
+#[cfg(feature = "wasm")]
+impl_js_error!(AsciiError);
+
+/// Position of the hex in the drawing, relative to the hex `[0, 0]`, as `[column, line]`
+fn to_text([q, r]: [i32; 2], orientation: Orientation) -> [i32; 2] {
+    match orientation {
+        Orientation::Pointy => [2 * q + r, r],
+        Orientation::Flat => [2 * q, 2 * r + q],
+    }
+}
+
+/// Inverse of `to_text`, `None` if the position is between hexes
+fn from_text([x, y]: [i32; 2], orientation: Orientation) -> Option<[i32; 2]> {
+    match orientation {
+        Orientation::Pointy if (x - y) % 2 == 0 => Some([(x - y) / 2, y]),
+        Orientation::Flat if x % 2 == 0 && (y - x / 2) % 2 == 0 => Some([x / 2, (y - x / 2) / 2]),
+        _ => None,
+    }
+}
+
+/// Draws the tiles. Later tiles overwrite earlier ones on the same hex.
+///
+/// Lines have no trailing whitespace and end with a newline.
+pub fn render_ascii(
+    tiles: impl IntoIterator<Item = ([i32; 2], char)>,
+    orientation: Orientation,
+) -> String {
+    let tiles = tiles
+        .into_iter()
+        .map(|(p, c)| (to_text(p, orientation), c))
+        .collect::<Vec<_>>();
+    let min_x = tiles.iter().map(|([x, _], _)| *x).min().unwrap_or(0);
+    let min_y = tiles.iter().map(|([_, y], _)| *y).min().unwrap_or(0);
+    let mut lines: Vec<Vec<char>> = vec![];
+    for ([x, y], c) in tiles {
+        let [x, y] = [(x - min_x) as usize, (y - min_y) as usize];
+        if lines.len() <= y {
+            lines.resize(y + 1, vec![]);
+        }
+        let line = &mut lines[y];
+        if line.len() <= x {
+            line.resize(x + 1, ' ');
+        }
+        line[x] = c;
+    }
+    let mut text = String::new();
+    for line in lines {
+        text.extend(line);
+        text.push('\n');
+    }
+    text
+}
+
+/// Reads the tiles of a drawing, in reading order. The first character of the first non-blank
+/// line is placed at `origin`.
+pub fn parse_ascii(
+    text: &str,
+    orientation: Orientation,
+    origin: [i32; 2],
+) -> Result<Vec<([i32; 2], char)>, AsciiError> {
+    let cells = text.lines().enumerate().flat_map(|(line, chars)| {
+        chars
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(move |(column, c)| ([column, line], c))
+    });
+    let mut first = None;
+    let mut tiles = vec![];
+    for ([column, line], c) in cells {
+        let [x0, y0] = *first.get_or_insert([column as i32, line as i32]);
+        // the first tile is the hex `[0, 0]` of the drawing
+        let offset = [column as i32 - x0, line as i32 - y0];
+        let [q, r] =
+            from_text(offset, orientation).ok_or(AsciiError::Misaligned { line, column })?;
+        tiles.push(([origin[0] + q, origin[1] + r], c));
+    }
+    Ok(tiles)
+}
+
+impl<T> HexMap<T> {
+    /// Draws the map, `tile` returns the character of each value
+    pub fn to_ascii(&self, orientation: Orientation, mut tile: impl FnMut(&T) -> char) -> String {
+        render_ascii(self.iter().map(|(p, v)| (p, tile(v))), orientation)
+    }
+}
+
+impl HexMap<char> {
+    /// Reads a drawing into a map of the `hexagon`, hexes not in the drawing are `empty`.
+    ///
+    /// Fails if a tile is outside of the hexagon.
+    pub fn from_ascii(
+        text: &str,
+        orientation: Orientation,
+        origin: [i32; 2],
+        hexagon: Hexagon,
+        empty: char,
+    ) -> Result<Self, AsciiError> {
+        let mut map = HexMap::filled(hexagon, empty);
+        for (p, c) in parse_ascii(text, orientation, origin)? {
+            *map.get_mut(p).ok_or(AsciiError::OutsideMap(p))? = c;
+        }
+        Ok(map)
+    }
+}
+
+/// Draws the axial points as plain text, the `n`th point as the `n`th character of `tiles`.
+///
+/// Throws if there are fewer characters than points.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=renderHexAscii)]
+pub fn render_hex_ascii(
+    points: &Array2f,
+    tiles: &str,
+    orientation: Orientation,
+) -> Result<String, JsValue> {
+    let mut chars = tiles.chars();
+    let tiles = points
+        .data
+        .iter()
+        .map(|p| Ok((to_axial(p), chars.next().ok_or(AsciiError::NotEnoughTiles)?)))
+        .collect::<Result<Vec<_>, AsciiError>>()?;
+    Ok(render_ascii(tiles, orientation))
+}
+
+/// Reads the axial points of a plain text drawing, in reading order. The first character of the
+/// first non-blank line is placed at `origin`.
+///
+/// The tiles are the non-whitespace characters of the text, in the same order. Throws if a
+/// character is not aligned to the grid.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=parseHexAscii)]
+pub fn parse_hex_ascii(
+    text: &str,
+    orientation: Orientation,
+    origin: &Vec2,
+) -> Result<Array2f, JsValue> {
+    let data = parse_ascii(text, orientation, to_axial(origin))?
+        .into_iter()
+        .map(|(p, _)| from_axial(&p))
+        .collect();
+    Ok(Array2f { data })
+}
@@ -0,0 +1,603 @@
+//! Compact, versioned binary encoding of hexagon shaped maps
+//!
+//! Layout, integers are little-endian:
+//!
+//! - 3 bytes magic `HXM`, 1 byte format version
+//! - 1 byte tile kind, see `HexTile::KIND`
+//! - 1 byte flags: bit 0 is set for run-length encoded payloads, bit 1 for delta encoded ones
+//! - the hexagon: center `q` and `r` as `i32`, radius as `u32`
+//! - the payload
+//!
+//! Tiles are written in spiral order (`Hexagon::iter_spiral`). Raw payloads pack the tiles
+//! `HexTile::BITS` bits each, so boolean layers take one bit per hex. Run-length payloads are a
+//! list of `(run length, tile)` pairs, the run length as unsigned LEB128 and the tile padded to
+//! whole bytes. Delta payloads store the tiles XOR-ed with a previous snapshot of the same
+//! hexagon, so unchanged hexes become runs of zeros.
+use alloc::{vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{HexMap, HexMapf, Hexagon};
+use crate::vec::vec2::Vec2;
+
+const MAGIC: [u8; 3] = *b"HXM";
+const HEADER_LEN: usize = 18;
+const FLAG_RUN_LENGTH: u8 = 1;
+const FLAG_DELTA: u8 = 2;
+
+/// Version written by the encoder. Decoding rejects any other version.
+pub const FORMAT_VERSION: u8 = 1;
+/// Largest radius the decoder accepts
+pub const MAX_ENCODED_RADIUS: u32 = 4096;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=HexEncoding))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Tiles bit-packed one after the other
+    Raw = 0,
+    /// Runs of equal tiles
+    RunLength = 1,
+}
+
+/// Tile types that can be stored in the binary format
+pub trait HexTile: Copy {
+    /// Identifies the tile type in the header
+    const KIND: u8;
+    /// Number of bits of one tile
+    const BITS: u32;
+
+    fn into_u32(self) -> u32;
+    fn from_u32(bits: u32) -> Self;
+}
+
+impl HexTile for bool {
+    const KIND: u8 = 0;
+    const BITS: u32 = 1;
+
+    fn into_u32(self) -> u32 {
+        self as u32
+    }
+
+    fn from_u32(bits: u32) -> Self {
+        bits != 0
+    }
+}
+
+impl HexTile for u8 {
+    const KIND: u8 = 1;
+    const BITS: u32 = 8;
+
+    fn into_u32(self) -> u32 {
+        self as u32
+    }
+
+    fn from_u32(bits: u32) -> Self {
+        bits as u8
+    }
+}
+
+impl HexTile for f32 {
+    const KIND: u8 = 2;
+    const BITS: u32 = 32;
+
+    fn into_u32(self) -> u32 {
+        self.to_bits()
+    }
+
+    fn from_u32(bits: u32) -> Self {
+        f32::from_bits(bits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    /// The input ended before the encoded data did
+    UnexpectedEnd,
+    /// The input continues after the encoded data
+    TrailingBytes,
+    /// The input does not start with the magic bytes
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    /// The radius is larger than `MAX_ENCODED_RADIUS`
+    RadiusTooLarge(u32),
+    /// The center of the hexagon is not integer, or its radius is not a non-negative integer
+    InvalidHexagon,
+    /// The tiles are of a different type than requested
+    KindMismatch {
+        expected: u8,
+        found: u8,
+    },
+    /// The number of tiles does not match the hexagon
+    LengthMismatch,
+    /// The runs cover more hexes than the hexagon, a run is empty, or a path is longer than
+    /// `MAX_PATH_STEPS`
+    InvalidRunLength,
+    /// A run of moves has a direction outside of `HexDirection`
+    InvalidDirection(u8),
+    /// A patch changes a hex outside of its hexagon
+    InvalidIndex,
+    /// The data is delta encoded but no previous snapshot was given
+    MissingSnapshot,
+    /// The previous snapshot is of a different hexagon
+    SnapshotMismatch,
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodecError::UnexpectedEnd => write!(f, "Unexpected end of input"),
+            CodecError::TrailingBytes => write!(f, "Unexpected bytes after the end of the data"),
+            CodecError::InvalidMagic => write!(f, "Input is not an encoded hex map"),
+            CodecError::UnsupportedVersion(v) => write!(f, "Unsupported format version {}", v),
+            CodecError::RadiusTooLarge(r) => write!(f, "Radius {} is too large", r),
+            CodecError::InvalidHexagon => {
+                write!(f, "Hexagon center and radius must be integers")
+            }
+            CodecError::KindMismatch { expected, found } => write!(
+                f,
+                "Expected tiles of kind {} but found kind {}",
+                expected, found
+            ),
+            CodecError::LengthMismatch => {
+                write!(f, "Number of tiles does not match the hexagon")
+            }
+            CodecError::InvalidRunLength => write!(f, "Invalid run length"),
+            CodecError::InvalidDirection(d) => write!(f, "Invalid direction {}", d),
+            CodecError::InvalidIndex => write!(f, "Patch changes a hex outside of its hexagon"),
+            CodecError::MissingSnapshot => {
+                write!(
+                    f,
+                    "Data is delta encoded but no previous snapshot was given"
+                )
+            }
+            CodecError::SnapshotMismatch => {
+                write!(f, "Previous snapshot does not match the hexagon")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CodecError {}
+
+#[cfg(feature = "wasm")]
+impl_js_error!(CodecError);
+
+/// Encodes the tiles of the hexagon, given in the order of `Hexagon::iter_points`.
+///
+/// If `previous` is given the tiles are delta encoded against it.
+pub fn encode_tiles<T: HexTile>(
+    hexagon: &Hexagon,
+    tiles: &[T],
+    encoding: Encoding,
+    previous: Option<&[T]>,
+) -> Result<Vec<u8>, CodecError> {
+    let mut flags = 0;
+    if encoding == Encoding::RunLength {
+        flags |= FLAG_RUN_LENGTH;
+    }
+    if previous.is_some() {
+        flags |= FLAG_DELTA;
+    }
+    let mut out = Vec::with_capacity(HEADER_LEN);
+    out.extend_from_slice(&MAGIC);
+    out.push(FORMAT_VERSION);
+    out.push(T::KIND);
+    out.push(flags);
+    write_hexagon(hexagon, &mut out)?;
+
+    let n = hexagon.num_points();
+    if tiles.len() != n {
+        return Err(CodecError::LengthMismatch);
+    }
+    if previous.map(|p| p.len() != n).unwrap_or(false) {
+        return Err(CodecError::SnapshotMismatch);
+    }
+    out.reserve((n * T::BITS as usize).div_ceil(8));
+
+    let words = spiral_indices(hexagon).map(|i| {
+        let word = tiles[i].into_u32();
+        match previous {
+            Some(previous) => word ^ previous[i].into_u32(),
+            None => word,
+        }
+    });
+    match encoding {
+        Encoding::Raw => write_packed(words, T::BITS, &mut out),
+        Encoding::RunLength => write_runs(words, T::BITS, &mut out),
+    }
+    Ok(out)
+}
+
+/// Decodes tiles written by `encode_tiles`, returning the hexagon and its tiles in the order of
+/// `Hexagon::iter_points`.
+///
+/// `previous` is required if the data is delta encoded.
+pub fn decode_tiles<T: HexTile>(
+    bytes: &[u8],
+    previous: Option<&[T]>,
+) -> Result<(Hexagon, Vec<T>), CodecError> {
+    let header = Header::read(bytes)?;
+    if header.kind != T::KIND {
+        return Err(CodecError::KindMismatch {
+            expected: T::KIND,
+            found: header.kind,
+        });
+    }
+    let hexagon = header.hexagon;
+    let n = hexagon.num_points();
+    if previous.map(|p| p.len() != n).unwrap_or(false) {
+        return Err(CodecError::SnapshotMismatch);
+    }
+    let previous = match previous {
+        None if header.flags & FLAG_DELTA != 0 => return Err(CodecError::MissingSnapshot),
+        _ if header.flags & FLAG_DELTA == 0 => None,
+        previous => previous,
+    };
+
+    let payload = &bytes[HEADER_LEN..];
+    let words = if header.flags & FLAG_RUN_LENGTH != 0 {
+        read_runs(payload, n, T::BITS)?
+    } else {
+        read_packed(payload, n, T::BITS)?
+    };
+
+    let mut tiles = vec![T::from_u32(0); n];
+    for (i, word) in spiral_indices(&hexagon).zip(words) {
+        let word = match previous {
+            Some(previous) => word ^ previous[i].into_u32(),
+            None => word,
+        };
+        tiles[i] = T::from_u32(word);
+    }
+    Ok((hexagon, tiles))
+}
+
+/// Reads the hexagon from the header of encoded data
+pub fn decode_hexagon(bytes: &[u8]) -> Result<Hexagon, CodecError> {
+    Header::read(bytes).map(|header| header.hexagon)
+}
+
+impl<T: HexTile> HexMap<T> {
+    /// Encodes the map, delta encoded against `previous` if given
+    pub fn encode(
+        &self,
+        encoding: Encoding,
+        previous: Option<&HexMap<T>>,
+    ) -> Result<Vec<u8>, CodecError> {
+        if previous
+            .map(|p| p.hexagon() != self.hexagon())
+            .unwrap_or(false)
+        {
+            return Err(CodecError::SnapshotMismatch);
+        }
+        encode_tiles(
+            self.hexagon(),
+            self.values(),
+            encoding,
+            previous.map(|p| p.values()),
+        )
+    }
+
+    /// Decodes a map written by `encode`. `previous` is required if the data is delta encoded.
+    pub fn decode(bytes: &[u8], previous: Option<&HexMap<T>>) -> Result<Self, CodecError> {
+        let hexagon = decode_hexagon(bytes)?;
+        if previous.map(|p| *p.hexagon() != hexagon).unwrap_or(false) {
+            return Err(CodecError::SnapshotMismatch);
+        }
+        let (hexagon, tiles) = decode_tiles(bytes, previous.map(|p| p.values()))?;
+        HexMap::from_values(hexagon, tiles).ok_or(CodecError::LengthMismatch)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexMapf))]
+impl HexMapf {
+    /// Encodes the map into the compact binary format.
+    ///
+    /// Throws if the center or the radius of the hexagon is not an integer, or the radius is
+    /// larger than the format supports.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen]
+    pub fn encode(&self, encoding: Encoding) -> Result<Vec<u8>, JsValue> {
+        Ok(self.map.encode(encoding, None)?)
+    }
+
+    /// Encodes only the difference between this map and `previous`. Decoding needs the same
+    /// `previous` map.
+    ///
+    /// Throws if the maps are of different hexagons.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=encodeDelta)]
+    pub fn encode_delta(&self, encoding: Encoding, previous: &HexMapf) -> Result<Vec<u8>, JsValue> {
+        Ok(self.map.encode(encoding, Some(&previous.map))?)
+    }
+
+    /// Decodes a map written by `encode`. Throws if the data is invalid.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen]
+    pub fn decode(bytes: &[u8]) -> Result<HexMapf, JsValue> {
+        Ok(HexMap::decode(bytes, None)?.into())
+    }
+
+    /// Decodes a map written by `encodeDelta`. Throws if the data is invalid.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=decodeDelta)]
+    pub fn decode_delta(bytes: &[u8], previous: &HexMapf) -> Result<HexMapf, JsValue> {
+        Ok(HexMap::decode(bytes, Some(&previous.map))?.into())
+    }
+}
+
+/// Encodes a list of byte tiles of the hexagon, in the same order as `Hexagon.list_points`.
+///
+/// @param previous if given, only the difference to these tiles is encoded. The same tiles are
+/// needed for decoding.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=encodeHexTiles)]
+pub fn encode_hex_tiles(
+    hexagon: &Hexagon,
+    tiles: &[u8],
+    encoding: Encoding,
+    previous: Option<Vec<u8>>,
+) -> Result<Vec<u8>, JsValue> {
+    Ok(encode_tiles(hexagon, tiles, encoding, previous.as_deref())?)
+}
+
+/// Decodes tiles written by `encodeHexTiles`. Throws if the data is invalid.
+///
+/// Use `decodeHexagon` to read the hexagon of the tiles.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=decodeHexTiles)]
+pub fn decode_hex_tiles(bytes: &[u8], previous: Option<Vec<u8>>) -> Result<Vec<u8>, JsValue> {
+    let (_, tiles) = decode_tiles(bytes, previous.as_deref())?;
+    Ok(tiles)
+}
+
+/// Encodes a boolean layer of the hexagon using one bit per hex. Non-zero values are true.
+///
+/// @param previous if given, only the difference to this layer is encoded. The same layer is
+/// needed for decoding.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=encodeHexMask)]
+pub fn encode_hex_mask(
+    hexagon: &Hexagon,
+    mask: &[u8],
+    encoding: Encoding,
+    previous: Option<Vec<u8>>,
+) -> Result<Vec<u8>, JsValue> {
+    let to_bools = |values: &[u8]| values.iter().map(|v| *v != 0).collect::<Vec<_>>();
+    let previous = previous.as_deref().map(to_bools);
+    Ok(encode_tiles(
+        hexagon,
+        &to_bools(mask),
+        encoding,
+        previous.as_deref(),
+    )?)
+}
+
+/// Decodes a layer written by `encodeHexMask` into a list of 0s and 1s. Throws if the data is
+/// invalid.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=decodeHexMask)]
+pub fn decode_hex_mask(bytes: &[u8], previous: Option<Vec<u8>>) -> Result<Vec<u8>, JsValue> {
+    let previous = previous.map(|p| p.iter().map(|v| *v != 0).collect::<Vec<_>>());
+    let (_, mask) = decode_tiles::<bool>(bytes, previous.as_deref())?;
+    Ok(mask.into_iter().map(|v| v as u8).collect())
+}
+
+/// Reads the hexagon of data written by `encodeHexTiles`, `encodeHexMask` or `HexMapf.encode`.
+/// Throws if the data is invalid.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=decodeHexagon)]
+pub fn decode_hexagon_js(bytes: &[u8]) -> Result<Hexagon, JsValue> {
+    Ok(decode_hexagon(bytes)?)
+}
+
+struct Header {
+    kind: u8,
+    flags: u8,
+    hexagon: Hexagon,
+}
+
+impl Header {
+    fn read(bytes: &[u8]) -> Result<Self, CodecError> {
+        if bytes.len() < 4 {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        if bytes[..3] != MAGIC {
+            return Err(CodecError::InvalidMagic);
+        }
+        if bytes[3] != FORMAT_VERSION {
+            return Err(CodecError::UnsupportedVersion(bytes[3]));
+        }
+        if bytes.len() < HEADER_LEN {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        Ok(Self {
+            kind: bytes[4],
+            flags: bytes[5],
+            hexagon: read_hexagon(&bytes[6..])?,
+        })
+    }
+}
+
+/// Writes the hexagon as read by `read_hexagon`, rejecting hexagons that can not be read back
+pub(crate) fn write_hexagon(hexagon: &Hexagon, out: &mut Vec<u8>) -> Result<(), CodecError> {
+    let Vec2 { x, y } = hexagon.center;
+    let radius = hexagon.radius;
+    if !is_i32(x) || !is_i32(y) || !is_i32(radius) || radius < 0. {
+        return Err(CodecError::InvalidHexagon);
+    }
+    if radius > MAX_ENCODED_RADIUS as f32 {
+        return Err(CodecError::RadiusTooLarge(radius as u32));
+    }
+    out.extend_from_slice(&hexagon_bytes(hexagon));
+    Ok(())
+}
+
+/// The 12 bytes of the hexagon, with its center and radius truncated to integers
+pub(crate) fn hexagon_bytes(hexagon: &Hexagon) -> [u8; 12] {
+    let Vec2 { x, y } = hexagon.center;
+    let mut bytes = [0; 12];
+    bytes[..4].copy_from_slice(&(x as i32).to_le_bytes());
+    bytes[4..8].copy_from_slice(&(y as i32).to_le_bytes());
+    bytes[8..].copy_from_slice(&(hexagon.radius as u32).to_le_bytes());
+    bytes
+}
+
+fn is_i32(x: f32) -> bool {
+    // `i32::MAX as f32` rounds up to 2^31, which does not fit
+    x as i32 as f32 == x && x < i32::MAX as f32
+}
+
+/// Reads the 12 bytes written by `write_hexagon`
+pub(crate) fn read_hexagon(bytes: &[u8]) -> Result<Hexagon, CodecError> {
+    if bytes.len() < 12 {
+        return Err(CodecError::UnexpectedEnd);
+    }
+    let word = |i: usize| [bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]];
+    let q = i32::from_le_bytes(word(0));
+    let r = i32::from_le_bytes(word(4));
+    let radius = u32::from_le_bytes(word(8));
+    if radius > MAX_ENCODED_RADIUS {
+        return Err(CodecError::RadiusTooLarge(radius));
+    }
+    Ok(Hexagon {
+        center: Vec2::new(q as f32, r as f32),
+        radius: radius as f32,
+    })
+}
+
+/// Indices into the `iter_points` ordered values, in spiral order
+fn spiral_indices(hexagon: &Hexagon) -> impl Iterator<Item = usize> + '_ {
+    hexagon
+        .iter_spiral()
+        .filter_map(move |p| hexagon.index_of(p))
+}
+
+fn mask(bits: u32) -> u32 {
+    u32::MAX >> (32 - bits)
+}
+
+fn write_packed(words: impl Iterator<Item = u32>, bits: u32, out: &mut Vec<u8>) {
+    let mut acc = 0u64;
+    let mut len = 0;
+    for word in words {
+        acc |= ((word & mask(bits)) as u64) << len;
+        len += bits;
+        while len >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            len -= 8;
+        }
+    }
+    if len > 0 {
+        out.push(acc as u8);
+    }
+}
+
+fn read_packed(bytes: &[u8], n: usize, bits: u32) -> Result<Vec<u32>, CodecError> {
+    let len = (n * bits as usize).div_ceil(8);
+    if bytes.len() < len {
+        return Err(CodecError::UnexpectedEnd);
+    }
+    if bytes.len() > len {
+        return Err(CodecError::TrailingBytes);
+    }
+    let mut bytes = bytes.iter();
+    let mut acc = 0u64;
+    let mut available = 0;
+    // the length check above bounds `n` by the size of the input
+    let mut words = Vec::with_capacity(n);
+    for _ in 0..n {
+        while available < bits {
+            acc |= (*bytes.next().ok_or(CodecError::UnexpectedEnd)? as u64) << available;
+            available += 8;
+        }
+        words.push(acc as u32 & mask(bits));
+        acc >>= bits;
+        available -= bits;
+    }
+    Ok(words)
+}
+
+fn write_runs(words: impl Iterator<Item = u32>, bits: u32, out: &mut Vec<u8>) {
+    let write_run = |word: u32, count: u32, out: &mut Vec<u8>| {
+        write_varint(count, out);
+        write_word(word, bits, out);
+    };
+    let mut run: Option<(u32, u32)> = None;
+    for word in words {
+        run = match run {
+            Some((w, count)) if w == word => Some((w, count + 1)),
+            Some((w, count)) => {
+                write_run(w, count, out);
+                Some((word, 1))
+            }
+            None => Some((word, 1)),
+        };
+    }
+    if let Some((w, count)) = run {
+        write_run(w, count, out);
+    }
+}
+
+fn read_runs(bytes: &[u8], n: usize, bits: u32) -> Result<Vec<u32>, CodecError> {
+    // `n` comes from the untrusted header, long runs grow the vector as they are read
+    let mut words = Vec::with_capacity(n.min(bytes.len()));
+    let mut pos = 0;
+    while words.len() < n {
+        let count = read_varint(bytes, &mut pos)? as usize;
+        if count == 0 || words.len() + count > n {
+            return Err(CodecError::InvalidRunLength);
+        }
+        let word = read_word(bytes, &mut pos, bits)?;
+        words.resize(words.len() + count, word);
+    }
+    if pos != bytes.len() {
+        return Err(CodecError::TrailingBytes);
+    }
+    Ok(words)
+}
+
+/// Writes a single tile, padded to whole bytes
+pub(crate) fn write_word(word: u32, bits: u32, out: &mut Vec<u8>) {
+    let width = (bits as usize).div_ceil(8);
+    out.extend_from_slice(&word.to_le_bytes()[..width]);
+}
+
+pub(crate) fn read_word(bytes: &[u8], pos: &mut usize, bits: u32) -> Result<u32, CodecError> {
+    let width = (bits as usize).div_ceil(8);
+    let tile = bytes
+        .get(*pos..*pos + width)
+        .ok_or(CodecError::UnexpectedEnd)?;
+    *pos += width;
+    let mut word = [0; 4];
+    word[..width].copy_from_slice(tile);
+    Ok(u32::from_le_bytes(word) & mask(bits))
+}
+
+/// Writes `value` as unsigned LEB128
+pub(crate) fn write_varint(mut value: u32, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+pub(crate) fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u32, CodecError> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or(CodecError::UnexpectedEnd)?;
+        *pos += 1;
+        // the 5th byte only has room for the top 4 bits
+        if shift == 28 && byte & 0x70 != 0 {
+            return Err(CodecError::InvalidRunLength);
+        }
+        value |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(CodecError::InvalidRunLength)
+}
@@ -0,0 +1,132 @@
+//! Directional wedge and cone shaped areas of the hex grid
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{from_axial, to_axial, HexDirection, HexRegion, Hexagon, Orientation};
+use crate::array::Array2f;
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+
+/// Tolerance of the wedge boundaries, in hex-direction steps
+const WEDGE_EPSILON: f32 = 1e-4;
+
+/// Iterate over the hexes of the wedge facing `direction`, at most `radius` away from `origin`.
+///
+/// The wedge is `2 * half_width` hex-direction steps wide, where one step is 60°. A `half_width`
+/// of 0.5 gives a 60° wedge, 1 gives a 120° wedge. Angles are measured on the hex grid: on every
+/// ring the hexes are evenly spaced, so the wedge is symmetric around `direction`.
+///
+/// `origin` is not part of the wedge. Hexes are returned ring by ring, counter-clockwise.
+pub fn wedge_iter(
+    origin: [i32; 2],
+    direction: HexDirection,
+    half_width: f32,
+    radius: u32,
+) -> impl Iterator<Item = [i32; 2]> {
+    (1..=radius as i32).flat_map(move |n| {
+        let ring = Hexagon {
+            center: from_axial(&origin),
+            radius: n as f32,
+        };
+        let facing = direction.index() as i32 * n;
+        ring.iter_ring()
+            .enumerate()
+            .filter(move |(i, _)| {
+                // signed offset from `facing` along the ring, in (-3n, 3n]
+                let mut offset = (*i as i32 - facing).rem_euclid(6 * n);
+                if offset > 3 * n {
+                    offset -= 6 * n;
+                }
+                offset.abs() as f32 <= (half_width + WEDGE_EPSILON) * n as f32
+            })
+            .map(|(_, p)| p)
+    })
+}
+
+/// Iterate over the hexes whose centers are inside the pixel space cone at `origin`, facing
+/// `angle` and opening `half_angle` to both sides, at most `radius` away from `origin`.
+///
+/// Angles are in radians, measured from the `x` axis towards the `y` axis in pixel space.
+/// `origin` is not part of the cone. Hexes are returned ring by ring, counter-clockwise.
+pub fn cone_iter(
+    origin: [i32; 2],
+    angle: f32,
+    half_angle: f32,
+    radius: u32,
+    orientation: Orientation,
+) -> impl Iterator<Item = [i32; 2]> {
+    let facing = Vec2::new(angle.cos(), angle.sin());
+    let min_cos = half_angle.cos();
+    let to_pixel = orientation.axial_to_pixel();
+    let [q0, r0] = origin;
+    (1..=radius).flat_map(move |n| {
+        let ring = Hexagon {
+            center: from_axial(&origin),
+            radius: n as f32,
+        };
+        let to_pixel = to_pixel.clone();
+        ring.iter_ring().filter(move |[q, r]| {
+            let offset = to_pixel.right_prod(Vec2::new((q - q0) as f32, (r - r0) as f32));
+            facing.dot(&offset) >= min_cos * offset.len() - WEDGE_EPSILON
+        })
+    })
+}
+
+/// Returns the axial coordinates of the hexes of the wedge facing `direction`, at most `radius`
+/// away from `origin`, not including `origin`.
+///
+/// The wedge is `2 * halfWidth` hex-direction steps wide, where one step is 60°. A `halfWidth`
+/// of 0.5 gives a 60° wedge, 1 gives a 120° wedge.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=hexWedge))]
+pub fn hex_wedge(origin: &Vec2, direction: HexDirection, half_width: f32, radius: u32) -> Array2f {
+    let data = wedge_iter(to_axial(origin), direction, half_width, radius)
+        .map(|p| from_axial(&p))
+        .collect();
+    Array2f { data }
+}
+
+/// Returns the axial coordinates of the hexes whose centers are inside the pixel space cone at
+/// `origin`, facing `angle` and opening `halfAngle` to both sides, at most `radius` away from
+/// `origin`, not including `origin`.
+///
+/// Angles are in radians, measured from the `x` axis towards the `y` axis in pixel space.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=hexCone))]
+pub fn hex_cone(
+    origin: &Vec2,
+    angle: f32,
+    half_angle: f32,
+    radius: u32,
+    orientation: Orientation,
+) -> Array2f {
+    let data = cone_iter(to_axial(origin), angle, half_angle, radius, orientation)
+        .map(|p| from_axial(&p))
+        .collect();
+    Array2f { data }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexRegion))]
+impl HexRegion {
+    /// Region of the hexes returned by `hexWedge`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn wedge(
+        origin: &Vec2,
+        direction: HexDirection,
+        half_width: f32,
+        radius: u32,
+    ) -> HexRegion {
+        wedge_iter(to_axial(origin), direction, half_width, radius).collect()
+    }
+
+    /// Region of the hexes returned by `hexCone`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn cone(
+        origin: &Vec2,
+        angle: f32,
+        half_angle: f32,
+        radius: u32,
+        orientation: Orientation,
+    ) -> HexRegion {
+        cone_iter(to_axial(origin), angle, half_angle, radius, orientation).collect()
+    }
+}
@@ -0,0 +1,206 @@
+//! Convolution, diffusion and blurring of hex maps
+use alloc::vec::Vec;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{round_to_nearest_axial, HexMap, HexMapf, Hexagon, AXIAL_NEIGHBOURS};
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+
+/// Determines the values of the hexes outside of a map
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Hexes outside of the map have the given constant value
+    Constant = 0,
+    /// Hexes outside of the map take the value of the hex on the edge of the map, in the
+    /// direction of the map's center
+    Clamp = 1,
+    /// Hexes outside of the map are left out. Convolutions rescale the remaining weights to the
+    /// total weight of the kernel, diffusion treats the edge of the map as a wall
+    Ignore = 2,
+}
+
+impl HexMap<f32> {
+    /// Returns the value of the hex, handling points outside of the map according to `boundary`
+    fn sample(&self, axial: [i32; 2], boundary: Boundary, boundary_value: f32) -> Option<f32> {
+        if let Some(v) = self.get(axial) {
+            return Some(*v);
+        }
+        match boundary {
+            Boundary::Constant => Some(boundary_value),
+            Boundary::Clamp => self.get(self.clamp(axial)).copied(),
+            Boundary::Ignore => None,
+        }
+    }
+
+    /// Moves the point towards the center of the map until it is inside the map
+    fn clamp(&self, [q, r]: [i32; 2]) -> [i32; 2] {
+        let Hexagon { center, radius } = *self.hexagon();
+        let [x, z] = [q as f32 - center.x, r as f32 - center.y];
+        let distance = x.abs().max(z.abs()).max((x + z).abs());
+        // the second attempt leaves enough room for the rounding error
+        [radius, radius - 0.5]
+            .iter()
+            .map(|r| {
+                let t = r.max(0.) / distance;
+                let Vec2 { x, y } = round_to_nearest_axial(x * t, z * t) + center;
+                [x as i32, y as i32]
+            })
+            .find(|p| self.contains(*p))
+            .unwrap_or([center.x as i32, center.y as i32])
+    }
+
+    /// Convolve this map with the `kernel` and write the results into `out`.
+    ///
+    /// The kernel is centered on its hexagon's center. `out` must have the same shape as this map.
+    pub fn convolve_into(
+        &self,
+        kernel: &HexMap<f32>,
+        boundary: Boundary,
+        boundary_value: f32,
+        out: &mut HexMap<f32>,
+    ) {
+        assert_eq!(
+            self.values().len(),
+            out.values().len(),
+            "Output must have the same shape"
+        );
+        let center = kernel.hexagon().center;
+        let taps = kernel
+            .iter()
+            .map(|([q, r], w)| ([q - center.x as i32, r - center.y as i32], *w))
+            .collect::<Vec<_>>();
+        let total_weight: f32 = taps.iter().map(|(_, w)| w).sum();
+
+        for ([q, r], out) in self.hexagon().iter_points().zip(out.values_mut()) {
+            let mut sum = 0.;
+            let mut used_weight = 0.;
+            for ([dq, dr], w) in taps.iter() {
+                if let Some(v) = self.sample([q + dq, r + dr], boundary, boundary_value) {
+                    sum += w * v;
+                    used_weight += w;
+                }
+            }
+            *out = if boundary == Boundary::Ignore && used_weight != total_weight {
+                if used_weight == 0. {
+                    0.
+                } else {
+                    sum * total_weight / used_weight
+                }
+            } else {
+                sum
+            };
+        }
+    }
+
+    /// Performs an explicit diffusion step, then decays the result, writing it into `out`.
+    ///
+    /// Each hex exchanges `rate / 6` of the difference with each of its neighbours, then every
+    /// value is multiplied by `1 - decay`. The step is stable for `rate` in `[0, 1]`. With
+    /// `Boundary::Ignore` the total amount is conserved, apart from the decay.
+    pub fn diffuse_into(
+        &self,
+        rate: f32,
+        decay: f32,
+        boundary: Boundary,
+        boundary_value: f32,
+        out: &mut HexMap<f32>,
+    ) {
+        assert_eq!(
+            self.values().len(),
+            out.values().len(),
+            "Output must have the same shape"
+        );
+        let rate = rate / 6.;
+        let keep = 1. - decay;
+        let points = self.hexagon().iter_points();
+        for (([q, r], v), out) in points.zip(self.values()).zip(out.values_mut()) {
+            let flux: f32 = AXIAL_NEIGHBOURS
+                .iter()
+                .filter_map(|[dq, dr]| self.sample([q + dq, r + dr], boundary, boundary_value))
+                .map(|n| n - v)
+                .sum();
+            *out = (v + rate * flux) * keep;
+        }
+    }
+}
+
+/// Creates a normalized Gaussian kernel of the given radius.
+///
+/// Weights are calculated from the pixel space distance of the hex centers, where neighbouring
+/// hexes are at distance 1. A non-positive `sigma` gives the identity kernel, that keeps the
+/// map as it is.
+pub fn gaussian_kernel(radius: u32, sigma: f32) -> HexMap<f32> {
+    let hexagon = Hexagon {
+        center: Vec2::new(0., 0.),
+        radius: radius as f32,
+    };
+    let denom = 2. * sigma * sigma;
+    // also catches a NaN sigma and one so small that its square underflows to 0
+    if !(sigma > 0. && denom > 0.) {
+        return HexMap::from_fn(hexagon, |p| if p == [0, 0] { 1. } else { 0. });
+    }
+    let mut kernel = HexMap::from_fn(hexagon, |[q, r]| {
+        let [q, r] = [q as f32, r as f32];
+        (-(q * q + q * r + r * r) / denom).exp()
+    });
+    let sum: f32 = kernel.values().iter().sum();
+    kernel.values_mut().iter_mut().for_each(|w| *w /= sum);
+    kernel
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexMapf))]
+impl HexMapf {
+    /// Returns a new map, the convolution of this map and the `kernel`.
+    ///
+    /// The kernel is centered on its hexagon's center.
+    ///
+    /// @param boundaryValue value of the hexes outside of the map when using `Boundary.Constant`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn convolve(&self, kernel: &HexMapf, boundary: Boundary, boundary_value: f32) -> HexMapf {
+        let mut out = self.clone();
+        self.map
+            .convolve_into(&kernel.map, boundary, boundary_value, &mut out.map);
+        out
+    }
+
+    /// Performs an explicit diffusion step in place, then decays every value by `decay`.
+    ///
+    /// Each hex exchanges `rate / 6` of the difference with each of its neighbours. The step is
+    /// stable for `rate` in `[0, 1]`.
+    ///
+    /// @param boundaryValue value of the hexes outside of the map when using `Boundary.Constant`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn diffuse(&mut self, rate: f32, decay: f32, boundary: Boundary, boundary_value: f32) {
+        let mut out = self.map.clone();
+        self.map
+            .diffuse_into(rate, decay, boundary, boundary_value, &mut out);
+        self.map = out;
+    }
+
+    /// Returns a new map, blurred by a Gaussian kernel of the given radius.
+    ///
+    /// @param boundaryValue value of the hexes outside of the map when using `Boundary.Constant`
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=gaussianBlur))]
+    pub fn gaussian_blur(
+        &self,
+        radius: u32,
+        sigma: f32,
+        boundary: Boundary,
+        boundary_value: f32,
+    ) -> HexMapf {
+        let kernel = gaussian_kernel(radius, sigma);
+        let mut out = self.clone();
+        self.map
+            .convolve_into(&kernel, boundary, boundary_value, &mut out.map);
+        out
+    }
+
+    /// Creates a normalized Gaussian kernel of the given radius, centered on `(0, 0)`.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=gaussianKernel))]
+    pub fn gaussian_kernel(radius: u32, sigma: f32) -> HexMapf {
+        gaussian_kernel(radius, sigma).into()
+    }
+}
@@ -0,0 +1,405 @@
+//! Differences between hex maps of the same hexagon, for incremental sync
+//!
+//! A `HexPatch` lists the changed tiles together with checksums of the map before and after the
+//! change, so a client applying patches on top of each other notices when it went out of sync.
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::codec::{
+    hexagon_bytes, read_hexagon, read_varint, read_word, write_hexagon, write_varint, write_word,
+};
+use super::{from_axial, CodecError, HexMap, HexMapf, HexTile, Hexagon};
+use crate::array::Array2f;
+
+const PATCH_MAGIC: [u8; 3] = *b"HXP";
+/// Version written by `HexPatch::to_bytes`. Decoding rejects any other version.
+pub const PATCH_FORMAT_VERSION: u8 = 1;
+
+const FNV_OFFSET: u32 = 0x811c_9dc5;
+const FNV_PRIME: u32 = 0x0100_0193;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The maps, or the map and the patch, are of different hexagons
+    ShapeMismatch,
+    /// The checksum of the map does not match the one recorded in the patch
+    ChecksumMismatch { expected: u32, found: u32 },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::ShapeMismatch => write!(f, "The hexagons of the maps do not match"),
+            PatchError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "Checksum mismatch, expected {:08x} but found {:08x}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PatchError {}
+
+#[cfg(feature = "wasm")]
+impl_js_error!(PatchError);
+
+/// FNV-1a hash of the hexagon and its tiles, given in the order of `Hexagon::iter_points`.
+///
+/// The checksum only depends on the tile values, so it is the same on every platform.
+pub fn tiles_checksum<T: HexTile>(hexagon: &Hexagon, tiles: &[T]) -> u32 {
+    let mut hash = FNV_OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for b in bytes {
+            hash ^= *b as u32;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+    feed(&[T::KIND]);
+    feed(&hexagon_bytes(hexagon));
+    for tile in tiles {
+        feed(&tile.into_u32().to_le_bytes());
+    }
+    hash
+}
+
+/// Changed tiles between two snapshots of a hex map
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexPatch<T> {
+    hexagon: Hexagon,
+    changes: Vec<(u32, T)>,
+    checksum_before: u32,
+    checksum_after: u32,
+}
+
+impl<T: HexTile> HexPatch<T> {
+    /// Computes the patch turning `previous` into `current`. Both are given in the order of
+    /// `Hexagon::iter_points`.
+    pub fn between_tiles(
+        hexagon: &Hexagon,
+        previous: &[T],
+        current: &[T],
+    ) -> Result<Self, PatchError> {
+        let n = hexagon.num_points();
+        if previous.len() != n || current.len() != n {
+            return Err(PatchError::ShapeMismatch);
+        }
+        let changes = previous
+            .iter()
+            .zip(current.iter())
+            .enumerate()
+            .filter(|(_, (a, b))| a.into_u32() != b.into_u32())
+            .map(|(i, (_, b))| (i as u32, *b))
+            .collect();
+        Ok(Self {
+            hexagon: *hexagon,
+            changes,
+            checksum_before: tiles_checksum(hexagon, previous),
+            checksum_after: tiles_checksum(hexagon, current),
+        })
+    }
+
+    pub fn between(previous: &HexMap<T>, current: &HexMap<T>) -> Result<Self, PatchError> {
+        if previous.hexagon() != current.hexagon() {
+            return Err(PatchError::ShapeMismatch);
+        }
+        Self::between_tiles(previous.hexagon(), previous.values(), current.values())
+    }
+
+    pub fn hexagon(&self) -> &Hexagon {
+        &self.hexagon
+    }
+
+    /// Indices of the changed tiles in `Hexagon::iter_points`, in ascending order, and their new
+    /// values
+    pub fn changes(&self) -> &[(u32, T)] {
+        &self.changes
+    }
+
+    /// Number of changed tiles
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn checksum_before(&self) -> u32 {
+        self.checksum_before
+    }
+
+    pub fn checksum_after(&self) -> u32 {
+        self.checksum_after
+    }
+
+    /// Iterate over the axial coordinates and new values of the changed tiles
+    pub fn iter(&self) -> impl Iterator<Item = ([i32; 2], T)> + '_ {
+        self.changes.iter().filter_map(move |(i, tile)| {
+            let point = self.hexagon.nth_point(*i as usize)?;
+            Some((point, *tile))
+        })
+    }
+
+    /// Applies the patch to tiles given in the order of `Hexagon::iter_points`.
+    ///
+    /// The tiles are left unchanged if the checksum before or after applying the patch does not
+    /// match.
+    pub fn apply_to_tiles(&self, tiles: &mut [T]) -> Result<(), PatchError> {
+        if tiles.len() != self.hexagon.num_points() {
+            return Err(PatchError::ShapeMismatch);
+        }
+        let found = tiles_checksum(&self.hexagon, tiles);
+        if found != self.checksum_before {
+            return Err(PatchError::ChecksumMismatch {
+                expected: self.checksum_before,
+                found,
+            });
+        }
+        let replaced = self
+            .changes
+            .iter()
+            .map(|(i, tile)| core::mem::replace(&mut tiles[*i as usize], *tile))
+            .collect::<Vec<_>>();
+        let found = tiles_checksum(&self.hexagon, tiles);
+        if found != self.checksum_after {
+            for ((i, _), tile) in self.changes.iter().zip(replaced) {
+                tiles[*i as usize] = tile;
+            }
+            return Err(PatchError::ChecksumMismatch {
+                expected: self.checksum_after,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// Applies the patch to the map, see `apply_to_tiles`
+    pub fn apply(&self, map: &mut HexMap<T>) -> Result<(), PatchError> {
+        if *map.hexagon() != self.hexagon {
+            return Err(PatchError::ShapeMismatch);
+        }
+        self.apply_to_tiles(map.values_mut())
+    }
+
+    /// Encodes the patch.
+    ///
+    /// Layout, integers are little-endian: magic `HXP`, format version, tile kind, the hexagon as
+    /// in the map format, the checksums before and after as `u32`, the number of changes, then
+    /// for every change the gap to the previous changed index and the new tile. Counts and gaps
+    /// are unsigned LEB128.
+    ///
+    /// Fails if the hexagon can not be encoded, see `CodecError::InvalidHexagon`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CodecError> {
+        let mut out = Vec::with_capacity(32 + self.changes.len() * 2);
+        out.extend_from_slice(&PATCH_MAGIC);
+        out.push(PATCH_FORMAT_VERSION);
+        out.push(T::KIND);
+        write_hexagon(&self.hexagon, &mut out)?;
+        out.extend_from_slice(&self.checksum_before.to_le_bytes());
+        out.extend_from_slice(&self.checksum_after.to_le_bytes());
+        write_varint(self.changes.len() as u32, &mut out);
+        let mut next = 0;
+        for (i, tile) in self.changes.iter() {
+            write_varint(i - next, &mut out);
+            write_word(tile.into_u32(), T::BITS, &mut out);
+            next = i + 1;
+        }
+        Ok(out)
+    }
+
+    /// Decodes a patch written by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        if bytes.len() < 5 {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        if bytes[..3] != PATCH_MAGIC {
+            return Err(CodecError::InvalidMagic);
+        }
+        if bytes[3] != PATCH_FORMAT_VERSION {
+            return Err(CodecError::UnsupportedVersion(bytes[3]));
+        }
+        if bytes[4] != T::KIND {
+            return Err(CodecError::KindMismatch {
+                expected: T::KIND,
+                found: bytes[4],
+            });
+        }
+        let hexagon = read_hexagon(&bytes[5..])?;
+        let mut pos = 17;
+        let mut read_u32 = || {
+            let word = bytes.get(pos..pos + 4).ok_or(CodecError::UnexpectedEnd)?;
+            pos += 4;
+            Ok(u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+        };
+        let checksum_before = read_u32()?;
+        let checksum_after = read_u32()?;
+
+        let n = hexagon.num_points() as u64;
+        let count = read_varint(bytes, &mut pos)? as u64;
+        if count > n {
+            return Err(CodecError::InvalidIndex);
+        }
+        // every change takes at least a 1 byte gap and the tile
+        let min_change_size = 1 + (T::BITS as usize).div_ceil(8);
+        if count > ((bytes.len() - pos) / min_change_size) as u64 {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        let mut changes = Vec::with_capacity(count as usize);
+        let mut next = 0u64;
+        for _ in 0..count {
+            let index = next + read_varint(bytes, &mut pos)? as u64;
+            if index >= n {
+                return Err(CodecError::InvalidIndex);
+            }
+            let tile = T::from_u32(read_word(bytes, &mut pos, T::BITS)?);
+            changes.push((index as u32, tile));
+            next = index + 1;
+        }
+        if pos != bytes.len() {
+            return Err(CodecError::TrailingBytes);
+        }
+        Ok(Self {
+            hexagon,
+            changes,
+            checksum_before,
+            checksum_after,
+        })
+    }
+}
+
+impl<T: HexTile> HexMap<T> {
+    /// See `tiles_checksum`
+    pub fn checksum(&self) -> u32 {
+        tiles_checksum(self.hexagon(), self.values())
+    }
+
+    /// Computes the patch turning this map into `current`
+    pub fn diff(&self, current: &HexMap<T>) -> Result<HexPatch<T>, PatchError> {
+        HexPatch::between(self, current)
+    }
+}
+
+/// Changed values between two snapshots of a `HexMapf`
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=HexPatchf))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexPatchf {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub patch: HexPatch<f32>,
+}
+
+impl From<HexPatch<f32>> for HexPatchf {
+    fn from(patch: HexPatch<f32>) -> Self {
+        Self { patch }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexPatchf))]
+impl HexPatchf {
+    /// Number of changed values
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn len(&self) -> usize {
+        self.patch.len()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=isEmpty))]
+    pub fn is_empty(&self) -> bool {
+        self.patch.is_empty()
+    }
+
+    /// Checksum of the map the patch applies to
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=checksumBefore))]
+    pub fn checksum_before(&self) -> u32 {
+        self.patch.checksum_before()
+    }
+
+    /// Checksum of the map after applying the patch
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=checksumAfter))]
+    pub fn checksum_after(&self) -> u32 {
+        self.patch.checksum_after()
+    }
+
+    /// Returns the axial coordinates of the changed hexes
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn points(&self) -> Array2f {
+        let data = self.patch.iter().map(|(p, _)| from_axial(&p)).collect();
+        Array2f { data }
+    }
+
+    /// Returns the new values, in the same order as `points`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn values(&self) -> Vec<f32> {
+        self.patch.changes().iter().map(|(_, v)| *v).collect()
+    }
+
+    /// Encodes the patch. Throws if the hexagon can not be encoded.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        Ok(self.patch.to_bytes()?)
+    }
+
+    /// Decodes a patch written by `toBytes`. Throws if the data is invalid.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<HexPatchf, JsValue> {
+        Ok(HexPatch::from_bytes(bytes)?.into())
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexMapf))]
+impl HexMapf {
+    /// Checksum of the hexagon and the values of the map
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn checksum(&self) -> u32 {
+        self.map.checksum()
+    }
+
+    /// Computes the patch turning this map into `current`. Throws if the maps are of different
+    /// hexagons.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen]
+    pub fn diff(&self, current: &HexMapf) -> Result<HexPatchf, JsValue> {
+        Ok(self.map.diff(&current.map)?.into())
+    }
+
+    /// Applies the patch to this map.
+    ///
+    /// Throws, and leaves the map unchanged, if the patch was made for a different map.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=applyPatch)]
+    pub fn apply_patch(&mut self, patch: &HexPatchf) -> Result<(), JsValue> {
+        Ok(patch.patch.apply(&mut self.map)?)
+    }
+}
+
+/// Checksum of the hexagon and its byte tiles, given in the same order as `Hexagon.list_points`
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=hexTilesChecksum))]
+pub fn hex_tiles_checksum(hexagon: &Hexagon, tiles: &[u8]) -> u32 {
+    tiles_checksum(hexagon, tiles)
+}
+
+/// Computes the encoded patch turning the `previous` byte tiles of the hexagon into `current`.
+///
+/// Throws if the number of tiles does not match the hexagon, or the hexagon can not be encoded.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=diffHexTiles)]
+pub fn diff_hex_tiles(
+    hexagon: &Hexagon,
+    previous: &[u8],
+    current: &[u8],
+) -> Result<Vec<u8>, JsValue> {
+    Ok(HexPatch::between_tiles(hexagon, previous, current)?.to_bytes()?)
+}
+
+/// Applies a patch written by `diffHexTiles` to the tiles, in place.
+///
+/// Throws, and leaves the tiles unchanged, if the patch is invalid or was made for different
+/// tiles.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=applyHexTilesPatch)]
+pub fn apply_hex_tiles_patch(tiles: &mut [u8], patch: &[u8]) -> Result<(), JsValue> {
+    Ok(HexPatch::from_bytes(patch)?.apply_to_tiles(tiles)?)
+}
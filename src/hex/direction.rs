@@ -0,0 +1,92 @@
+//! The 6 directions of the hex grid and stepping between neighbours
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::AXIAL_NEIGHBOURS;
+use crate::vec::vec2::Vec2;
+
+/// The 6 directions of the neighbours of a hex, in counter-clockwise order.
+///
+/// Names assume a "pointy top grid" with the `y` axis pointing down, as on a screen.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub enum HexDirection {
+    /// axial offset `(1, 0)`
+    East = 0,
+    /// axial offset `(1, -1)`
+    NorthEast = 1,
+    /// axial offset `(0, -1)`
+    NorthWest = 2,
+    /// axial offset `(-1, 0)`
+    West = 3,
+    /// axial offset `(-1, 1)`
+    SouthWest = 4,
+    /// axial offset `(0, 1)`
+    SouthEast = 5,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    /// Index `i` is taken modulo 6
+    pub fn from_index(i: usize) -> Self {
+        Self::ALL[i % 6]
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the direction of the `offset` if it points to a neighbour
+    pub fn from_axial(offset: [i32; 2]) -> Option<Self> {
+        AXIAL_NEIGHBOURS
+            .iter()
+            .position(|d| *d == offset)
+            .map(Self::from_index)
+    }
+
+    pub fn axial(self) -> [i32; 2] {
+        AXIAL_NEIGHBOURS[self as usize]
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotated(3)
+    }
+
+    /// Rotate counter-clockwise by `steps` directions. Negative steps rotate clockwise.
+    pub fn rotated(self, steps: i32) -> Self {
+        Self::from_index((self as i32 + steps).rem_euclid(6) as usize)
+    }
+
+    /// Returns the neighbour of the `axial` hex in this direction
+    pub fn neighbour(self, [q, r]: [i32; 2]) -> [i32; 2] {
+        let [dq, dr] = self.axial();
+        [q + dq, r + dr]
+    }
+}
+
+/// Returns the axial coordinates of the neighbour of `axial` in the given direction
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=hexNeighbour))]
+pub fn hex_neighbour(axial: &Vec2, direction: HexDirection) -> Vec2 {
+    let [dq, dr] = direction.axial();
+    Vec2::new(axial.x + dq as f32, axial.y + dr as f32)
+}
+
+/// Returns the direction from `from` to `to`, or __undefined__ if they are not neighbours
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=hexDirectionBetween))]
+pub fn hex_direction_between(from: &Vec2, to: &Vec2) -> Option<HexDirection> {
+    let offset = *to - *from;
+    HexDirection::from_axial([offset.x.round() as i32, offset.y.round() as i32])
+}
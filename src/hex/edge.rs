@@ -0,0 +1,337 @@
+//! Addressing the edges and vertices of the hex grid.
+//!
+//! Every edge is shared by 2 hexes and every vertex by 3 hexes. To give them unique ids, each hex
+//! owns 3 of its edges (`East`, `NorthEast` and `NorthWest`) and 2 of its vertices (corners `0`
+//! and `1`). Corner `k` of a hex lies between its neighbours in direction `k` and `k + 1`.
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{from_axial, to_axial, HexDirection, Orientation};
+use crate::array::Array2f;
+use crate::vec::vec2::Vec2;
+
+/// Edge between two neighbouring hexes, in canonical form
+#[cfg_attr(feature = "wasm", wasm_bindgen(inspectable))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HexEdge {
+    q: i32,
+    r: i32,
+    direction: HexDirection,
+}
+
+/// Vertex shared by three hexes, in canonical form
+#[cfg_attr(feature = "wasm", wasm_bindgen(inspectable))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HexVertex {
+    q: i32,
+    r: i32,
+    corner: u32,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl HexEdge {
+    /// The edge of the axial hex `(q, r)` in the given direction
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(q: i32, r: i32, direction: HexDirection) -> Self {
+        Self::from_hex([q, r], direction)
+    }
+
+    /// Returns the edge between the two axial hexes, or __undefined__ if they are not neighbours
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn between(a: &Vec2, b: &Vec2) -> Option<HexEdge> {
+        let a = to_axial(a);
+        let b = to_axial(b);
+        HexDirection::from_axial([b[0] - a[0], b[1] - a[1]]).map(|d| Self::from_hex(a, d))
+    }
+
+    /// Axial `q` coordinate of the owning hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn q(&self) -> i32 {
+        self.q
+    }
+
+    /// Axial `r` coordinate of the owning hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn r(&self) -> i32 {
+        self.r
+    }
+
+    /// Direction of the edge from the owning hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn direction(&self) -> HexDirection {
+        self.direction
+    }
+
+    /// Returns the axial coordinates of the 2 hexes separated by this edge
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn hexes(&self) -> Array2f {
+        let data = self.hex_pair().iter().map(from_axial).collect();
+        Array2f { data }
+    }
+
+    /// Returns the 2 endpoints of this edge
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn vertices(&self) -> Vec<HexVertex> {
+        self.vertex_pair().to_vec()
+    }
+
+    /// Returns the 4 edges sharing a vertex with this edge
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=adjacentEdges))]
+    pub fn adjacent_edges(&self) -> Vec<HexEdge> {
+        self.vertex_pair()
+            .iter()
+            .flat_map(|v| v.edge_triple().to_vec())
+            .filter(|e| e != self)
+            .collect()
+    }
+
+    /// Pixel position of the midpoint of this edge
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn center(&self, orientation: Orientation, size: f32) -> Vec2 {
+        let [a, b] = self.hex_pair();
+        (orientation.hex_center(from_axial(&a), size)
+            + orientation.hex_center(from_axial(&b), size))
+            / 2.
+    }
+
+    /// Pixel positions of the 2 endpoints of this edge
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn endpoints(&self, orientation: Orientation, size: f32) -> Array2f {
+        let data = self
+            .vertex_pair()
+            .iter()
+            .map(|v| v.position(orientation, size))
+            .collect();
+        Array2f { data }
+    }
+}
+
+impl HexEdge {
+    /// The edge of the `axial` hex in the given direction
+    pub fn from_hex(axial: [i32; 2], direction: HexDirection) -> Self {
+        let ([q, r], direction) = if direction.index() < 3 {
+            (axial, direction)
+        } else {
+            (direction.neighbour(axial), direction.opposite())
+        };
+        Self { q, r, direction }
+    }
+
+    /// Returns the 6 edges of the `axial` hex, in the order of `HexDirection::ALL`
+    pub fn of_hex(axial: [i32; 2]) -> [HexEdge; 6] {
+        let mut edges = [Self::from_hex(axial, HexDirection::East); 6];
+        for (e, d) in edges.iter_mut().zip(HexDirection::ALL.iter()) {
+            *e = Self::from_hex(axial, *d);
+        }
+        edges
+    }
+
+    pub fn hex(&self) -> [i32; 2] {
+        [self.q, self.r]
+    }
+
+    pub fn hex_pair(&self) -> [[i32; 2]; 2] {
+        [self.hex(), self.direction.neighbour(self.hex())]
+    }
+
+    pub fn vertex_pair(&self) -> [HexVertex; 2] {
+        let k = self.direction.index();
+        [
+            HexVertex::from_hex(self.hex(), k + 5),
+            HexVertex::from_hex(self.hex(), k),
+        ]
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl HexVertex {
+    /// Corner `corner` of the axial hex `(q, r)`, taken modulo 6
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(q: i32, r: i32, corner: u32) -> Self {
+        Self::from_hex([q, r], corner as usize)
+    }
+
+    /// Axial `q` coordinate of the owning hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn q(&self) -> i32 {
+        self.q
+    }
+
+    /// Axial `r` coordinate of the owning hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn r(&self) -> i32 {
+        self.r
+    }
+
+    /// Corner of the owning hex, either 0 or 1
+    #[cfg_attr(feature = "wasm", wasm_bindgen(getter))]
+    pub fn corner(&self) -> u32 {
+        self.corner
+    }
+
+    /// Returns the axial coordinates of the 3 hexes meeting at this vertex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn hexes(&self) -> Array2f {
+        let data = self.hex_triple().iter().map(from_axial).collect();
+        Array2f { data }
+    }
+
+    /// Returns the 3 edges meeting at this vertex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn edges(&self) -> Vec<HexEdge> {
+        self.edge_triple().to_vec()
+    }
+
+    /// Returns the 3 vertices connected to this vertex by an edge
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=adjacentVertices))]
+    pub fn adjacent_vertices(&self) -> Vec<HexVertex> {
+        self.edge_triple()
+            .iter()
+            .flat_map(|e| e.vertex_pair().to_vec())
+            .filter(|v| v != self)
+            .collect()
+    }
+
+    /// Pixel position of this vertex
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn position(&self, orientation: Orientation, size: f32) -> Vec2 {
+        let sum = self.hex_triple().iter().fold(Vec2::new(0., 0.), |sum, p| {
+            sum + orientation.hex_center(from_axial(p), size)
+        });
+        sum / 3.
+    }
+}
+
+impl HexVertex {
+    /// Corner `corner` of the `axial` hex, taken modulo 6
+    pub fn from_hex(axial: [i32; 2], corner: usize) -> Self {
+        // corner `k` of a hex is corner `k + 2` of its neighbour in direction `k` and corner
+        // `k + 4` of its neighbour in direction `k + 1`
+        let (hex, corner) = match corner % 6 {
+            c @ 0 | c @ 1 => (axial, c),
+            2 => (HexDirection::West.neighbour(axial), 0),
+            3 => (HexDirection::SouthWest.neighbour(axial), 1),
+            4 => (HexDirection::SouthWest.neighbour(axial), 0),
+            _ => (HexDirection::SouthEast.neighbour(axial), 1),
+        };
+        let [q, r] = hex;
+        Self {
+            q,
+            r,
+            corner: corner as u32,
+        }
+    }
+
+    /// Returns the 6 vertices of the `axial` hex, in counter-clockwise order starting at corner 0
+    pub fn of_hex(axial: [i32; 2]) -> [HexVertex; 6] {
+        let mut vertices = [Self::from_hex(axial, 0); 6];
+        for (k, v) in vertices.iter_mut().enumerate() {
+            *v = Self::from_hex(axial, k);
+        }
+        vertices
+    }
+
+    pub fn hex(&self) -> [i32; 2] {
+        [self.q, self.r]
+    }
+
+    pub fn hex_triple(&self) -> [[i32; 2]; 3] {
+        let k = self.corner as usize;
+        let hex = self.hex();
+        [
+            hex,
+            HexDirection::from_index(k).neighbour(hex),
+            HexDirection::from_index(k + 1).neighbour(hex),
+        ]
+    }
+
+    pub fn edge_triple(&self) -> [HexEdge; 3] {
+        let k = self.corner as usize;
+        let hex = self.hex();
+        let d = HexDirection::from_index(k);
+        [
+            HexEdge::from_hex(hex, d),
+            HexEdge::from_hex(hex, d.rotated(1)),
+            HexEdge::from_hex(d.neighbour(hex), d.rotated(2)),
+        ]
+    }
+}
+
+/// Set of blocked edges, to be used by pathfinding to treat them as walls
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HexWalls {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub edges: BTreeSet<HexEdge>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl HexWalls {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Blocks the edge. Returns false if it was already blocked.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn add(&mut self, edge: &HexEdge) -> bool {
+        self.edges.insert(*edge)
+    }
+
+    /// Unblocks the edge. Returns false if it was not blocked.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn remove(&mut self, edge: &HexEdge) -> bool {
+        self.edges.remove(edge)
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn contains(&self, edge: &HexEdge) -> bool {
+        self.edges.contains(edge)
+    }
+
+    /// Blocks the edge between the two axial hexes.
+    ///
+    /// Returns false if they are not neighbours or the edge was already blocked.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=addBetween))]
+    pub fn add_between(&mut self, a: &Vec2, b: &Vec2) -> bool {
+        HexEdge::between(a, b)
+            .map(|e| self.edges.insert(e))
+            .unwrap_or(false)
+    }
+
+    /// Returns whether a unit can step from the axial hex `from` to the axial hex `to`: they are
+    /// neighbours and the edge between them is not blocked.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=isPassable))]
+    pub fn is_passable(&self, from: &Vec2, to: &Vec2) -> bool {
+        HexEdge::between(from, to)
+            .map(|e| !self.edges.contains(&e))
+            .unwrap_or(false)
+    }
+
+    /// Returns the axial coordinates of the neighbours of `hex` that are not behind a wall
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=passableNeighbours))]
+    pub fn passable_neighbours(&self, hex: &Vec2) -> Array2f {
+        let axial = to_axial(hex);
+        let data = HexDirection::ALL
+            .iter()
+            .filter(|d| !self.edges.contains(&HexEdge::from_hex(axial, **d)))
+            .map(|d| from_axial(&d.neighbour(axial)))
+            .collect();
+        Array2f { data }
+    }
+
+    /// Returns the blocked edges, in ascending order
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=listEdges))]
+    pub fn list_edges(&self) -> Vec<HexEdge> {
+        self.edges.iter().copied().collect()
+    }
+}
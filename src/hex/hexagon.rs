@@ -1,21 +1,24 @@
+#[cfg(feature = "alloc")]
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 use crate::vec::vec2::Vec2;
 
-use super::axial_to_cube;
+use super::{axial_to_cube, HexDirection};
 
-#[wasm_bindgen(js_name=Hexagon, inspectable)]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=Hexagon, inspectable))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
 pub struct Hexagon {
     /// center point in Vec2 coordinate system
     pub center: Vec2,
     pub radius: f32,
 }
 
-#[wasm_bindgen(js_class=Hexagon)]
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=Hexagon))]
 impl Hexagon {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         Self {
             center: Vec2::new(0.0, 0.0),
@@ -40,21 +43,10 @@ impl Hexagon {
     /// Generates a list of point in the Axial coordinate system that build up this hexagon grid.
     ///
     /// @return list of tuples of [q, r] integer coordinates.
+    #[cfg(feature = "wasm")]
     pub fn list_points(&self) -> Box<[JsValue]> {
-        let radius = self.radius as i32;
-        let Vec2 { x, y } = self.center;
-        let center = [x as i32, y as i32];
-        (-radius..=radius)
-            .flat_map(move |x| {
-                let fromy = (-radius).max(-x - radius);
-                let toy = radius.min(-x + radius);
-                (fromy..=toy)
-                    .map(move |y| {
-                        let y = -x - y;
-                        [x + center[0], y + center[1]]
-                    })
-                    .map(|p| JsValue::from_serde(&p).unwrap())
-            })
+        self.iter_points()
+            .map(|p| JsValue::from_serde(&p).unwrap())
             .collect()
     }
 
@@ -73,3 +65,105 @@ impl Hexagon {
         *self
     }
 }
+
+impl Hexagon {
+    /// Iterate over the points in the Axial coordinate system that build up this hexagon grid.
+    ///
+    /// Points are returned in the same order as `list_points`.
+    pub fn iter_points(&self) -> impl Iterator<Item = [i32; 2]> {
+        let radius = self.radius as i32;
+        let Vec2 { x, y } = self.center;
+        let center = [x as i32, y as i32];
+        (-radius..=radius).flat_map(move |x| {
+            let fromy = (-radius).max(-x - radius);
+            let toy = radius.min(-x + radius);
+            (fromy..=toy).map(move |y| {
+                let y = -x - y;
+                [x + center[0], y + center[1]]
+            })
+        })
+    }
+
+    /// Iterate over the points of the outermost ring of this hexagon grid, in Axial coordinates.
+    ///
+    /// The ring starts at the corner in the `East` direction and goes counter-clockwise, so the
+    /// `k * radius`th point is the corner in direction `k`.
+    pub fn iter_ring(&self) -> impl Iterator<Item = [i32; 2]> {
+        let radius = self.radius as i32;
+        let Vec2 { x, y } = self.center;
+        let center = [x as i32, y as i32];
+        let sides = if radius == 0 { 1 } else { 6 };
+        (0..sides).flat_map(move |k| {
+            let [aq, ar] = HexDirection::from_index(k).axial();
+            let [bq, br] = HexDirection::from_index(k + 1).axial();
+            (0..radius.max(1)).map(move |b| {
+                let a = radius - b;
+                [center[0] + a * aq + b * bq, center[1] + a * ar + b * br]
+            })
+        })
+    }
+
+    /// Iterate over the points of this hexagon grid ring by ring, starting at the center and
+    /// walking each ring in the order of `iter_ring`.
+    pub fn iter_spiral(&self) -> impl Iterator<Item = [i32; 2]> {
+        let center = self.center;
+        (0..=self.radius as u32).flat_map(move |radius| {
+            Hexagon {
+                center,
+                radius: radius as f32,
+            }
+            .iter_ring()
+        })
+    }
+
+    /// Number of points that build up this hexagon grid, 0 if the radius is negative.
+    pub fn num_points(&self) -> usize {
+        let radius = self.radius as i32;
+        if radius < 0 {
+            return 0;
+        }
+        let radius = radius as usize;
+        3 * radius * (radius + 1) + 1
+    }
+
+    /// Returns the position of the axial `point` in `iter_points`, or `None` if the point is not
+    /// part of this hexagon.
+    pub fn index_of(&self, [q, r]: [i32; 2]) -> Option<usize> {
+        let radius = self.radius as i32;
+        let Vec2 { x, y } = self.center;
+        let [x, z] = [q - x as i32, r - y as i32];
+        if x.abs() > radius || z.abs() > radius || (x + z).abs() > radius {
+            return None;
+        }
+        // number of points in the first `rows` rows
+        let prefix = |rows: i32| rows * (radius + 1) + rows * (rows - 1) / 2;
+        let row = x + radius;
+        let offset = if row <= radius + 1 {
+            prefix(row)
+        } else {
+            self.num_points() as i32 - prefix(2 * radius + 1 - row)
+        };
+        let fromy = (-radius).max(-x - radius);
+        let y = -x - z;
+        Some((offset + y - fromy) as usize)
+    }
+
+    /// Returns the `index`th point of `iter_points` without iterating over the preceding rows.
+    pub fn nth_point(&self, mut index: usize) -> Option<[i32; 2]> {
+        let radius = self.radius as i32;
+        let Vec2 { x, y } = self.center;
+        let center = [x as i32, y as i32];
+        for x in -radius..=radius {
+            let fromy = (-radius).max(-x - radius);
+            let toy = radius.min(-x + radius);
+            let row_len = (toy - fromy + 1) as usize;
+            if index < row_len {
+                let y = fromy + index as i32;
+                let y = -x - y;
+                return Some([x + center[0], y + center[1]]);
+            }
+            index -= row_len;
+        }
+        None
+    }
+}
@@ -0,0 +1,131 @@
+//! Dense hexagon shaped maps storing one value per hex
+use alloc::{vec, vec::Vec};
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::Hexagon;
+
+/// Dense storage of one value per hex of a `Hexagon`.
+///
+/// Values are stored in the order of `Hexagon::iter_points`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HexMap<T> {
+    hexagon: Hexagon,
+    values: Vec<T>,
+}
+
+impl<T> HexMap<T> {
+    /// Returns `None` if the number of values does not match the number of hexes.
+    pub fn from_values(hexagon: Hexagon, values: Vec<T>) -> Option<Self> {
+        if values.len() != hexagon.num_points() {
+            return None;
+        }
+        Some(Self { hexagon, values })
+    }
+
+    pub fn from_fn(hexagon: Hexagon, f: impl FnMut([i32; 2]) -> T) -> Self {
+        let values = hexagon.iter_points().map(f).collect();
+        Self { hexagon, values }
+    }
+
+    pub fn hexagon(&self) -> &Hexagon {
+        &self.hexagon
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    pub fn into_values(self) -> Vec<T> {
+        self.values
+    }
+
+    pub fn contains(&self, axial: [i32; 2]) -> bool {
+        self.hexagon.index_of(axial).is_some()
+    }
+
+    pub fn get(&self, axial: [i32; 2]) -> Option<&T> {
+        let i = self.hexagon.index_of(axial)?;
+        self.values.get(i)
+    }
+
+    pub fn get_mut(&mut self, axial: [i32; 2]) -> Option<&mut T> {
+        let i = self.hexagon.index_of(axial)?;
+        self.values.get_mut(i)
+    }
+
+    /// Iterate over the axial coordinates and values of this map
+    pub fn iter(&self) -> impl Iterator<Item = ([i32; 2], &T)> {
+        self.hexagon.iter_points().zip(self.values.iter())
+    }
+}
+
+impl<T: Clone> HexMap<T> {
+    pub fn filled(hexagon: Hexagon, value: T) -> Self {
+        let values = vec![value; hexagon.num_points()];
+        Self { hexagon, values }
+    }
+}
+
+/// Hexagon shaped map of floats
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=HexMapf))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HexMapf {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub map: HexMap<f32>,
+}
+
+impl From<HexMap<f32>> for HexMapf {
+    fn from(map: HexMap<f32>) -> Self {
+        Self { map }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexMapf))]
+impl HexMapf {
+    /// @param value initial value of every hex, defaults to 0
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(hexagon: &Hexagon, value: Option<f32>) -> Self {
+        HexMap::filled(*hexagon, value.unwrap_or_default()).into()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn hexagon(&self) -> Hexagon {
+        *self.map.hexagon()
+    }
+
+    /// Returns the value at the axial point `(q, r)`, or __undefined__ if the point is not on the
+    /// map.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get(&self, q: i32, r: i32) -> Option<f32> {
+        self.map.get([q, r]).copied()
+    }
+
+    /// Sets the value at the axial point `(q, r)`. Panics if the point is not on the map.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn set(&mut self, q: i32, r: i32, value: f32) {
+        *self
+            .map
+            .get_mut([q, r])
+            .expect("Point is outside of the map") = value;
+    }
+
+    /// Returns a copy of the values, in the same order as `Hexagon.list_points`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn values(&self) -> Vec<f32> {
+        self.map.values().to_vec()
+    }
+
+    /// Overwrites the values, given in the same order as `Hexagon.list_points`.
+    ///
+    /// Panics if the number of values does not match the number of hexes.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=setValues))]
+    pub fn set_values(&mut self, values: &[f32]) {
+        self.map.values_mut().copy_from_slice(values);
+    }
+}
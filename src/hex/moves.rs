@@ -0,0 +1,270 @@
+//! Compact movement commands along hex paths
+//!
+//! A path of adjacent hexes is stored as its first hex and runs of steps in the same direction,
+//! so straight stretches of any length take a single command.
+use alloc::{vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::codec::{read_varint, write_varint};
+#[cfg(feature = "wasm")]
+use super::to_axial;
+use super::{from_axial, CodecError, HexDirection};
+use crate::array::Array2f;
+use crate::vec::vec2::Vec2;
+
+const MOVES_MAGIC: [u8; 3] = *b"HXR";
+/// Version written by `HexMoves::to_bytes`. Decoding rejects any other version.
+pub const MOVES_FORMAT_VERSION: u8 = 1;
+
+/// Longest run written as a single command, longer runs are split
+const MAX_ENCODED_RUN: u32 = 1 << 29;
+/// Most steps `HexMoves::from_bytes` accepts, so untrusted input can not make `toPath` allocate
+/// more than a few megabytes
+pub const MAX_PATH_STEPS: u32 = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// The path has no hexes
+    Empty,
+    /// The hex at the index is not a neighbour of the one before it
+    NotAdjacent { index: usize },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathError::Empty => write!(f, "The path is empty"),
+            PathError::NotAdjacent { index } => write!(
+                f,
+                "Hex {} of the path is not a neighbour of the previous one",
+                index
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PathError {}
+
+#[cfg(feature = "wasm")]
+impl_js_error!(PathError);
+
+/// A hex path as runs of `(direction, count)` steps from its first hex
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=HexMoves))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexMoves {
+    start: [i32; 2],
+    runs: Vec<(HexDirection, u32)>,
+}
+
+impl HexMoves {
+    /// Moves that stay on the `start` hex
+    pub fn new(start: [i32; 2]) -> Self {
+        Self {
+            start,
+            runs: vec![],
+        }
+    }
+
+    /// Encodes the path of axial hexes, every hex must be a neighbour of the one before it
+    pub fn from_axial_path(path: &[[i32; 2]]) -> Result<Self, PathError> {
+        let (start, rest) = path.split_first().ok_or(PathError::Empty)?;
+        let mut moves = Self::new(*start);
+        let mut last = *start;
+        for (i, p) in rest.iter().enumerate() {
+            let direction = HexDirection::from_axial([p[0] - last[0], p[1] - last[1]])
+                .ok_or(PathError::NotAdjacent { index: i + 1 })?;
+            moves.push(direction, 1);
+            last = *p;
+        }
+        Ok(moves)
+    }
+
+    /// Appends `count` steps in the `direction`, extending the last run if it is in the same
+    /// direction
+    pub fn push(&mut self, direction: HexDirection, count: u32) {
+        if count == 0 {
+            return;
+        }
+        match self.runs.last_mut() {
+            Some((d, n)) if *d == direction => *n += count,
+            _ => self.runs.push((direction, count)),
+        }
+    }
+
+    pub fn start_axial(&self) -> [i32; 2] {
+        self.start
+    }
+
+    pub fn runs(&self) -> &[(HexDirection, u32)] {
+        &self.runs
+    }
+
+    /// Iterates over the hexes of the path, starting with the first one
+    pub fn iter(&self) -> impl Iterator<Item = [i32; 2]> + '_ {
+        let steps = self
+            .runs
+            .iter()
+            .flat_map(|(d, n)| core::iter::repeat_n(*d, *n as usize));
+        core::iter::once(self.start).chain(steps.scan(self.start, |p, d| {
+            *p = d.neighbour(*p);
+            Some(*p)
+        }))
+    }
+
+    /// Returns the last hex of the path
+    pub fn end_axial(&self) -> [i32; 2] {
+        // runs may be longer than `i32::MAX`
+        let [q, r] = self.runs.iter().fold(
+            [self.start[0] as i64, self.start[1] as i64],
+            |[q, r], (d, n)| {
+                let [dq, dr] = d.axial();
+                let n = *n as i64;
+                [q + dq as i64 * n, r + dr as i64 * n]
+            },
+        );
+        [q as i32, r as i32]
+    }
+
+    /// Decodes moves written by `to_bytes`. Paths longer than `MAX_PATH_STEPS` are rejected.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        if bytes.len() < 12 {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        if bytes[..3] != MOVES_MAGIC {
+            return Err(CodecError::InvalidMagic);
+        }
+        if bytes[3] != MOVES_FORMAT_VERSION {
+            return Err(CodecError::UnsupportedVersion(bytes[3]));
+        }
+        let q = i32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        let r = i32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        let mut moves = Self::new([q, r]);
+        let mut pos = 12;
+        let count = read_varint(bytes, &mut pos)?;
+        let mut steps = 0u32;
+        for _ in 0..count {
+            let command = read_varint(bytes, &mut pos)?;
+            let direction = (command & 7) as u8;
+            if direction >= 6 {
+                return Err(CodecError::InvalidDirection(direction));
+            }
+            let n = (command >> 3) + 1;
+            steps = steps
+                .checked_add(n)
+                .filter(|steps| *steps <= MAX_PATH_STEPS)
+                .ok_or(CodecError::InvalidRunLength)?;
+            moves.push(HexDirection::from_index(direction as usize), n);
+        }
+        if pos != bytes.len() {
+            return Err(CodecError::TrailingBytes);
+        }
+        Ok(moves)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexMoves))]
+impl HexMoves {
+    /// Encodes the path of axial points. Throws if the path is empty or a point is not a
+    /// neighbour of the one before it.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=fromPath)]
+    pub fn from_path(path: &Array2f) -> Result<HexMoves, JsValue> {
+        let path = path.data.iter().map(to_axial).collect::<Vec<_>>();
+        Ok(Self::from_axial_path(&path)?)
+    }
+
+    /// Returns the axial points of the path, starting with the first one
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=toPath))]
+    pub fn to_path(&self) -> Array2f {
+        let data = self.iter().map(|p| from_axial(&p)).collect();
+        Array2f { data }
+    }
+
+    /// Number of steps
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn len(&self) -> u32 {
+        self.runs.iter().map(|(_, n)| n).sum()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=isEmpty))]
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Returns the first point of the path
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn start(&self) -> Vec2 {
+        from_axial(&self.start)
+    }
+
+    /// Returns the last point of the path
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn end(&self) -> Vec2 {
+        from_axial(&self.end_axial())
+    }
+
+    /// Returns the `HexDirection` of each run
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn directions(&self) -> Vec<u8> {
+        self.runs.iter().map(|(d, _)| *d as u8).collect()
+    }
+
+    /// Returns the number of steps of each run, in the same order as `directions`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn counts(&self) -> Vec<u32> {
+        self.runs.iter().map(|(_, n)| *n).collect()
+    }
+
+    /// Writes the moves as the magic bytes, the format version, the first hex as two
+    /// little-endian `i32`, the number of runs, then each run as `(count - 1) << 3 | direction`.
+    /// Varints are unsigned LEB128, so a run of up to 16 steps takes a single byte.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=toBytes))]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(13 + self.runs.len());
+        out.extend_from_slice(&MOVES_MAGIC);
+        out.push(MOVES_FORMAT_VERSION);
+        out.extend_from_slice(&self.start[0].to_le_bytes());
+        out.extend_from_slice(&self.start[1].to_le_bytes());
+        let commands = self
+            .runs
+            .iter()
+            .flat_map(|(d, n)| {
+                let full = n / MAX_ENCODED_RUN;
+                let rest = n % MAX_ENCODED_RUN;
+                core::iter::repeat_n((*d, MAX_ENCODED_RUN), full as usize)
+                    .chain(Some((*d, rest)).filter(|(_, n)| *n > 0))
+            })
+            .collect::<Vec<_>>();
+        write_varint(commands.len() as u32, &mut out);
+        for (d, n) in commands {
+            write_varint((n - 1) << 3 | d as u32, &mut out);
+        }
+        out
+    }
+
+    /// Decodes moves written by `toBytes`. Throws if the data is invalid or the path is longer
+    /// than `MAX_PATH_STEPS`.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=fromBytes)]
+    pub fn from_bytes_js(bytes: &[u8]) -> Result<HexMoves, JsValue> {
+        Ok(Self::from_bytes(bytes)?)
+    }
+}
+
+/// Encodes the path of axial points as movement commands, see `HexMoves`.
+///
+/// Throws if the path is empty or a point is not a neighbour of the one before it.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=encodeHexPath)]
+pub fn encode_hex_path(path: &Array2f) -> Result<Vec<u8>, JsValue> {
+    Ok(HexMoves::from_path(path)?.to_bytes())
+}
+
+/// Decodes the axial points of a path encoded by `encodeHexPath`. Throws if the data is invalid.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=decodeHexPath)]
+pub fn decode_hex_path(bytes: &[u8]) -> Result<Array2f, JsValue> {
+    Ok(HexMoves::from_bytes(bytes)?.to_path())
+}
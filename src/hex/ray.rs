@@ -0,0 +1,148 @@
+//! Exact traversal of pixel space rays through the hexes of the grid
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{round_to_nearest_axial, HexDirection, HexEdge, Orientation};
+use crate::vec::vec2::Vec2;
+
+/// A hex crossed by a ray `origin + t * direction`
+#[cfg_attr(feature = "wasm", wasm_bindgen(inspectable))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RayHit {
+    /// Axial `q` coordinate of the hex
+    pub q: i32,
+    /// Axial `r` coordinate of the hex
+    pub r: i32,
+    /// Ray parameter where the ray enters the hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=tEnter))]
+    pub t_enter: f32,
+    /// Ray parameter where the ray leaves the hex, or the end of the ray
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=tExit))]
+    pub t_exit: f32,
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub entered_from: Option<HexDirection>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl RayHit {
+    /// Axial coordinates of the hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn hex(&self) -> Vec2 {
+        Vec2::new(self.q as f32, self.r as f32)
+    }
+
+    /// Direction the ray travelled in to enter this hex, __undefined__ for the first hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn direction(&self) -> Option<HexDirection> {
+        self.entered_from
+    }
+
+    /// Edge crossed to enter this hex, __undefined__ for the first hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn edge(&self) -> Option<HexEdge> {
+        self.entered_from
+            .map(|d| HexEdge::from_hex([self.q, self.r], d.opposite()))
+    }
+}
+
+/// Walks the hexes crossed by the ray `origin + t * direction` for `t` in `[0, max_t]`, in order.
+///
+/// The traversal stops at the first hex `stop` returns true for, and returns that hex. Returns
+/// `None` if the end of the ray was reached.
+///
+/// If the ray passes exactly through a vertex one of the hexes touching the vertex is skipped,
+/// or reported with an empty `[t_enter, t_exit]` range.
+///
+/// `size` is the distance between the center and the vertices of a hex
+pub fn cast_ray(
+    origin: &Vec2,
+    direction: &Vec2,
+    max_t: f32,
+    orientation: Orientation,
+    size: f32,
+    mut stop: impl FnMut(&RayHit) -> bool,
+) -> Option<RayHit> {
+    let to_pixel = orientation.axial_to_pixel();
+    // work on a grid of unit sized hexes, `t` is unaffected by the scaling
+    let origin = *origin / size;
+    let direction = *direction / size;
+
+    // offsets between the centers of neighbours; the edge towards the neighbour in direction `k`
+    // is the set of points `p` where `(p - center) . offsets[k] == 3/2`
+    let mut offsets = [Vec2::new(0., 0.); 6];
+    for (o, d) in offsets.iter_mut().zip(HexDirection::ALL.iter()) {
+        let [dq, dr] = d.axial();
+        *o = to_pixel.right_prod(Vec2::new(dq as f32, dr as f32));
+    }
+
+    let Vec2 { x, y } = orientation.pixel_to_axial().right_prod(origin);
+    let Vec2 { x, y } = round_to_nearest_axial(x, y);
+    let mut hex = [x as i32, y as i32];
+    let mut t_enter = 0.;
+    let mut entered_from = None;
+    loop {
+        let [q, r] = hex;
+        let relative = origin - to_pixel.right_prod(Vec2::new(q as f32, r as f32));
+        let exit = HexDirection::ALL
+            .iter()
+            .zip(offsets.iter())
+            .filter_map(|(d, o)| {
+                let speed = direction.dot(o);
+                if speed > 0. {
+                    Some(((1.5 - relative.dot(o)) / speed, *d))
+                } else {
+                    None
+                }
+            })
+            .fold(
+                None,
+                |best: Option<(f32, HexDirection)>, (t, d)| match best {
+                    Some((best_t, _)) if best_t <= t => best,
+                    _ => Some((t, d)),
+                },
+            )
+            .filter(|(t, _)| *t < max_t);
+
+        let t_exit = exit.map(|(t, _)| t.max(t_enter)).unwrap_or(max_t);
+        let hit = RayHit {
+            q,
+            r,
+            t_enter,
+            t_exit,
+            entered_from,
+        };
+        if stop(&hit) {
+            return Some(hit);
+        }
+        let (_, d) = exit?;
+        hex = d.neighbour(hex);
+        t_enter = t_exit;
+        entered_from = Some(d);
+    }
+}
+
+/// Walks the hexes crossed by the ray `origin + t * direction` for `t` in `[0, maxT]`, in order,
+/// calling `callback` with each `RayHit`.
+///
+/// The traversal stops at the first hex the callback returns a truthy value for, and returns that
+/// hex. Returns __undefined__ if the end of the ray was reached.
+///
+/// @param size distance between the center and the vertices of a hex
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name=castRay)]
+pub fn cast_ray_js(
+    origin: &Vec2,
+    direction: &Vec2,
+    max_t: f32,
+    orientation: Orientation,
+    size: f32,
+    callback: &js_sys::Function,
+) -> Option<RayHit> {
+    cast_ray(origin, direction, max_t, orientation, size, |hit| {
+        callback
+            .call1(&JsValue::NULL, &JsValue::from(*hit))
+            .map(|stop| stop.is_truthy())
+            .unwrap_or(true)
+    })
+}
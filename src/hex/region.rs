@@ -0,0 +1,99 @@
+//! Sets of hexes with union, intersection and difference
+use alloc::collections::BTreeSet;
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{from_axial, to_axial};
+use crate::array::Array2f;
+use crate::vec::vec2::Vec2;
+
+/// Set of hexes in axial coordinates.
+///
+/// Hexes are kept in ascending `(q, r)` order, so iteration order does not depend on the order of
+/// insertion.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=HexRegion))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HexRegion {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub hexes: BTreeSet<[i32; 2]>,
+}
+
+impl core::iter::FromIterator<[i32; 2]> for HexRegion {
+    fn from_iter<I: IntoIterator<Item = [i32; 2]>>(iter: I) -> Self {
+        Self {
+            hexes: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexRegion))]
+impl HexRegion {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a region from a list of axial points. Duplicates are dropped.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=fromPoints))]
+    pub fn from_points(points: &Array2f) -> Self {
+        points.data.iter().map(to_axial).collect()
+    }
+
+    /// Returns false if the hex was already in the region
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn add(&mut self, axial: &Vec2) -> bool {
+        self.hexes.insert(to_axial(axial))
+    }
+
+    /// Returns false if the hex was not in the region
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn remove(&mut self, axial: &Vec2) -> bool {
+        self.hexes.remove(&to_axial(axial))
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn contains(&self, axial: &Vec2) -> bool {
+        self.hexes.contains(&to_axial(axial))
+    }
+
+    /// Number of hexes in the region
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn len(&self) -> usize {
+        self.hexes.len()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=isEmpty))]
+    pub fn is_empty(&self) -> bool {
+        self.hexes.is_empty()
+    }
+
+    /// Returns the axial coordinates of the hexes, in ascending `(q, r)` order
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=toArray))]
+    pub fn to_array(&self) -> Array2f {
+        let data = self.hexes.iter().map(from_axial).collect();
+        Array2f { data }
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn union(&self, other: &HexRegion) -> HexRegion {
+        self.hexes.union(&other.hexes).copied().collect()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn intersection(&self, other: &HexRegion) -> HexRegion {
+        self.hexes.intersection(&other.hexes).copied().collect()
+    }
+
+    /// Returns the hexes of this region that are not in `other`
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn difference(&self, other: &HexRegion) -> HexRegion {
+        self.hexes.difference(&other.hexes).copied().collect()
+    }
+}
+
+impl HexRegion {
+    pub fn iter(&self) -> impl Iterator<Item = [i32; 2]> + '_ {
+        self.hexes.iter().copied()
+    }
+}
@@ -0,0 +1,284 @@
+//! Smooth pixel space curves along hex paths
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::Orientation;
+use crate::array::Array2f;
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+
+/// Corners flatter than this, relative to the squared length of the corner, are not rounded
+const COLLINEAR_EPSILON: f32 = 1e-4;
+
+/// Chaikin iterations are capped at this, every iteration doubles the number of points
+pub const MAX_CHAIKIN_ITERATIONS: u32 = 8;
+
+/// How the corners of a path are smoothed
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=PathSmoothing))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Smoothing {
+    /// Straight lines between the hex centers
+    None = 0,
+    /// Corner cutting, `detail` is the number of iterations, at most `MAX_CHAIKIN_ITERATIONS`.
+    /// Does not pass through the hex centers, except the first and the last one.
+    Chaikin = 1,
+    /// Catmull-Rom spline through every hex center, `detail` is the number of points per segment
+    CatmullRom = 2,
+    /// Quadratic curves between the midpoints of the edges around each corner, `detail` is the
+    /// number of points per corner. Straight parts of the path stay straight.
+    RoundedCorners = 3,
+}
+
+/// Smooths the polyline through `points`, keeping its first and last point
+pub fn smooth_points(points: &[Vec2], smoothing: Smoothing, detail: u32) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    match smoothing {
+        Smoothing::None => points.to_vec(),
+        Smoothing::Chaikin => chaikin(points, detail.min(MAX_CHAIKIN_ITERATIONS)),
+        Smoothing::CatmullRom => catmull_rom(points, detail.max(1)),
+        Smoothing::RoundedCorners => rounded_corners(points, detail.max(1)),
+    }
+}
+
+/// Smooths the path through the centers of the axial `path` hexes
+///
+/// `size` is the distance between the center and the vertices of a hex
+pub fn smooth_hex_path(
+    path: &[Vec2],
+    orientation: Orientation,
+    size: f32,
+    smoothing: Smoothing,
+    detail: u32,
+) -> Vec<Vec2> {
+    let centers = path
+        .iter()
+        .map(|p| orientation.hex_center(*p, size))
+        .collect::<Vec<_>>();
+    smooth_points(&centers, smoothing, detail)
+}
+
+fn chaikin(points: &[Vec2], iterations: u32) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    for _ in 0..iterations {
+        let mut next = Vec::with_capacity(points.len() * 2);
+        next.push(points[0]);
+        for w in points.windows(2) {
+            let [a, b] = [w[0], w[1]];
+            next.push(a * 0.75 + b * 0.25);
+            next.push(a * 0.25 + b * 0.75);
+        }
+        next.push(points[points.len() - 1]);
+        points = next;
+    }
+    points
+}
+
+fn catmull_rom(points: &[Vec2], detail: u32) -> Vec<Vec2> {
+    let n = points.len();
+    let mut out = Vec::with_capacity((n - 1) * detail as usize + 1);
+    for i in 0..n - 1 {
+        // the missing neighbours of the endpoints are the endpoints themselves
+        let p0 = points[i.saturating_sub(1)];
+        let [p1, p2] = [points[i], points[i + 1]];
+        let p3 = points[(i + 2).min(n - 1)];
+        for s in 0..detail {
+            let t = s as f32 / detail as f32;
+            let [t2, t3] = [t * t, t * t * t];
+            let p = p1 * 2.
+                + (p2 - p0) * t
+                + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * t2
+                + (p1 * 3. - p0 - p2 * 3. + p3) * t3;
+            out.push(p * 0.5);
+        }
+    }
+    out.push(points[n - 1]);
+    out
+}
+
+fn rounded_corners(points: &[Vec2], detail: u32) -> Vec<Vec2> {
+    let n = points.len();
+    let mut out = vec![points[0]];
+    for w in points.windows(3) {
+        let [a, corner, b] = [w[0], w[1], w[2]];
+        if Vec2::orient2d(&a, &corner, &b).abs() <= COLLINEAR_EPSILON * (b - a).len_sq() {
+            // straight through, the neighbouring curves are connected by a line
+            continue;
+        }
+        let start = (a + corner) * 0.5;
+        let end = (corner + b) * 0.5;
+        for s in 0..=detail {
+            let t = s as f32 / detail as f32;
+            let u = 1. - t;
+            out.push(start * (u * u) + corner * (2. * u * t) + end * (t * t));
+        }
+    }
+    out.push(points[n - 1]);
+    out
+}
+
+/// Polyline parameterized by arc length, for moving along it at constant speed
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=SmoothPath))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothPath {
+    points: Vec<Vec2>,
+    /// Distance of each point from the start, along the path
+    distances: Vec<f32>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=SmoothPath))]
+impl SmoothPath {
+    /// Path along the pixel space points
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(points: &Array2f) -> Self {
+        Self::from_points(points.data.iter().copied())
+    }
+
+    /// Smoothed path through the centers of the axial `path` hexes
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    /// @param detail see `PathSmoothing`
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=fromHexPath))]
+    pub fn from_hex_path(
+        path: &Array2f,
+        orientation: Orientation,
+        size: f32,
+        smoothing: Smoothing,
+        detail: u32,
+    ) -> Self {
+        let points = smooth_hex_path(&path.data, orientation, size, smoothing, detail);
+        Self::from_points(points)
+    }
+
+    /// Length of the path
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.)
+    }
+
+    /// Returns the points of the polyline
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn points(&self) -> Array2f {
+        Array2f {
+            data: self.points.clone(),
+        }
+    }
+
+    /// Returns the point `distance` away from the start, along the path. The distance is clamped
+    /// to the length of the path.
+    ///
+    /// Returns __undefined__ if the path is empty.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=pointAt))]
+    pub fn point_at(&self, distance: f32) -> Option<Vec2> {
+        let (i, t) = self.locate(distance)?;
+        match self.points.get(i + 1) {
+            Some(b) => Some(self.points[i] + (*b - self.points[i]) * t),
+            None => Some(self.points[i]),
+        }
+    }
+
+    /// Returns the unit direction of the path `distance` away from the start.
+    ///
+    /// Returns __undefined__ if the path has fewer than two distinct points.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=tangentAt))]
+    pub fn tangent_at(&self, distance: f32) -> Option<Vec2> {
+        if self.points.len() < 2 {
+            return None;
+        }
+        let (i, _) = self.locate(distance)?;
+        let i = i.min(self.points.len() - 2);
+        Some((self.points[i + 1] - self.points[i]).normalized())
+    }
+
+    /// Returns points `spacing` apart along the path, starting with the first point and ending
+    /// with the last.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=sampleEvery))]
+    pub fn sample_every(&self, spacing: f32) -> Array2f {
+        let length = self.length();
+        let count = if spacing > 0. {
+            (length / spacing).ceil() as usize
+        } else {
+            0
+        };
+        let mut data = (0..count)
+            .filter_map(|i| self.point_at(i as f32 * spacing))
+            .collect::<Vec<_>>();
+        if data.last() != self.points.last() {
+            data.extend(self.points.last().copied());
+        }
+        Array2f { data }
+    }
+
+    /// Returns `count` points evenly spaced along the path, including the first and the last
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn sample(&self, count: u32) -> Array2f {
+        let length = self.length();
+        let step = length / (count.max(2) - 1) as f32;
+        let data = (0..count)
+            .filter_map(|i| self.point_at(i as f32 * step))
+            .collect();
+        Array2f { data }
+    }
+}
+
+impl SmoothPath {
+    /// Consecutive duplicate points are dropped
+    pub fn from_points(points: impl IntoIterator<Item = Vec2>) -> Self {
+        let mut path = Self {
+            points: vec![],
+            distances: vec![],
+        };
+        for p in points {
+            match path.points.last() {
+                Some(last) if *last == p => {}
+                Some(last) => {
+                    let d = path.length() + (p - *last).len();
+                    path.points.push(p);
+                    path.distances.push(d);
+                }
+                None => {
+                    path.points.push(p);
+                    path.distances.push(0.);
+                }
+            }
+        }
+        path
+    }
+
+    /// Returns the segment containing the point `distance` away from the start and the position
+    /// of the point on the segment, in `[0, 1]`
+    fn locate(&self, distance: f32) -> Option<(usize, f32)> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let distance = distance.clamp(0., self.length());
+        let i = self
+            .distances
+            .partition_point(|d| *d <= distance)
+            .saturating_sub(1)
+            .min(self.points.len().saturating_sub(2));
+        let [d0, d1] = match self.distances.get(i + 1) {
+            Some(d1) => [self.distances[i], *d1],
+            None => return Some((i, 0.)),
+        };
+        Some((i, ((distance - d0) / (d1 - d0)).clamp(0., 1.)))
+    }
+}
+
+/// Smooths the path through the centers of the axial `path` hexes, see `PathSmoothing`
+///
+/// @param size distance between the center and the vertices of a hex
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=smoothHexPath))]
+pub fn smooth_hex_path_js(
+    path: &Array2f,
+    orientation: Orientation,
+    size: f32,
+    smoothing: Smoothing,
+    detail: u32,
+) -> Array2f {
+    let data = smooth_hex_path(&path.data, orientation, size, smoothing, detail);
+    Array2f { data }
+}
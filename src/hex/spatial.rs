@@ -0,0 +1,249 @@
+//! Spatial index of entities on the hex grid
+use std::collections::HashMap;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{axial_to_cube, cube_distance, from_axial, to_axial, Hexagon, Orientation};
+use crate::vec::vec2::Vec2;
+
+/// Stores entity ids by the axial coordinates of the hex they are on.
+///
+/// Query results are deterministic: they only depend on the performed operations, not on the
+/// internal layout of the index.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=HexSpatialIndex))]
+#[derive(Debug, Clone, Default)]
+pub struct HexSpatialIndex {
+    cells: HashMap<[i32; 2], Vec<u32>>,
+    positions: HashMap<u32, [i32; 2]>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=HexSpatialIndex))]
+impl HexSpatialIndex {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of entities in the index
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=isEmpty))]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Inserts the entity at the axial position, or moves it if it is already in the index.
+    ///
+    /// Returns false if the entity was already in the index.
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn insert(&mut self, id: u32, axial: &Vec2) -> bool {
+        self.insert_at(id, to_axial(axial))
+    }
+
+    /// Moves the entity to the axial position.
+    ///
+    /// Returns false, and does nothing, if the entity is not in the index.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=move))]
+    pub fn move_to(&mut self, id: u32, axial: &Vec2) -> bool {
+        if !self.positions.contains_key(&id) {
+            return false;
+        }
+        self.insert_at(id, to_axial(axial));
+        true
+    }
+
+    /// Returns false if the entity was not in the index
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn remove(&mut self, id: u32) -> bool {
+        match self.positions.remove(&id) {
+            Some(pos) => {
+                self.remove_from_cell(id, pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the axial position of the entity, or __undefined__ if it is not in the index
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn position(&self, id: u32) -> Option<Vec2> {
+        self.positions.get(&id).map(from_axial)
+    }
+
+    /// Returns the entities on the hex, in ascending order
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn at(&self, axial: &Vec2) -> Vec<u32> {
+        let mut ids = self.cell(to_axial(axial)).to_vec();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Returns the entities at most `radius` away from `center`, ordered by distance, then id
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn within(&self, center: &Vec2, radius: u32) -> Vec<u32> {
+        let center = to_axial(center);
+        // number of hexes in range, it does not fit a 32 bit `usize` for large radii
+        let r = radius as u64;
+        let hexes = (3 * r).saturating_mul(r + 1).saturating_add(1);
+        let mut keys = if hexes <= self.cells.len() as u64 {
+            (0..=radius)
+                .flat_map(|n| {
+                    let ring = Hexagon {
+                        center: from_axial(&center),
+                        radius: n as f32,
+                    };
+                    ring.iter_ring()
+                        .flat_map(move |p| self.cell(p).iter().map(move |id| (n, *id)))
+                })
+                .collect::<Vec<_>>()
+        } else {
+            self.keyed_by_distance(center)
+                .filter(|(d, _)| *d <= radius)
+                .collect()
+        };
+        keys.sort_unstable();
+        keys.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Returns the `k` entities closest to `center`, ordered by distance, then id
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn nearest(&self, center: &Vec2, k: usize) -> Vec<u32> {
+        let center = to_axial(center);
+        let k = k.min(self.len());
+        if k == 0 {
+            return Vec::new();
+        }
+        // search ring by ring while that is cheaper than looking at every occupied hex
+        let mut keys = Vec::new();
+        let mut visited = 0;
+        for n in 0.. {
+            let ring = Hexagon {
+                center: from_axial(&center),
+                radius: n as f32,
+            };
+            for p in ring.iter_ring() {
+                keys.extend(self.cell(p).iter().map(|id| (n, *id)));
+                visited += 1;
+            }
+            if keys.len() >= k {
+                keys.sort_unstable();
+                keys.truncate(k);
+                return keys.into_iter().map(|(_, id)| id).collect();
+            }
+            if visited > self.cells.len() {
+                break;
+            }
+        }
+
+        let mut keys = self.keyed_by_distance(center).collect::<Vec<_>>();
+        if k < keys.len() {
+            keys.select_nth_unstable(k - 1);
+            keys.truncate(k);
+        }
+        keys.sort_unstable();
+        keys.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Returns the entities on hexes whose centers are in the pixel space rectangle spanned by
+    /// `min` and `max`, in ascending order.
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=inPixelRect))]
+    pub fn in_pixel_rect(
+        &self,
+        min: &Vec2,
+        max: &Vec2,
+        orientation: Orientation,
+        size: f32,
+    ) -> Vec<u32> {
+        let to_pixel = orientation.axial_to_pixel();
+        let is_inside = |p: &[i32; 2]| {
+            let c = to_pixel.right_prod(from_axial(p)) * size;
+            min.x <= c.x && c.x <= max.x && min.y <= c.y && c.y <= max.y
+        };
+
+        // the axial bounding box of the rectangle's corners
+        let to_axial = orientation.pixel_to_axial();
+        let corners = [
+            Vec2::new(min.x, min.y),
+            Vec2::new(max.x, min.y),
+            Vec2::new(min.x, max.y),
+            Vec2::new(max.x, max.y),
+        ];
+        let [mut lo, mut hi] = [[i32::MAX; 2], [i32::MIN; 2]];
+        for c in corners.iter() {
+            let a = to_axial.right_prod(*c / size);
+            for i in 0..2 {
+                lo[i] = lo[i].min(a[i].floor() as i32);
+                hi[i] = hi[i].max(a[i].ceil() as i32);
+            }
+        }
+        let candidates = (hi[0] - lo[0] + 1).max(0) as usize * (hi[1] - lo[1] + 1).max(0) as usize;
+
+        let mut ids = if candidates <= self.cells.len() {
+            (lo[0]..=hi[0])
+                .flat_map(|q| (lo[1]..=hi[1]).map(move |r| [q, r]))
+                .filter(|p| is_inside(p))
+                .flat_map(|p| self.cell(p).iter().copied())
+                .collect::<Vec<_>>()
+        } else {
+            self.cells
+                .iter()
+                .filter(|(p, _)| is_inside(p))
+                .flat_map(|(_, ids)| ids.iter().copied())
+                .collect()
+        };
+        ids.sort_unstable();
+        ids
+    }
+}
+
+impl HexSpatialIndex {
+    /// Inserts the entity at the axial position, or moves it if it is already in the index.
+    ///
+    /// Returns false if the entity was already in the index.
+    pub fn insert_at(&mut self, id: u32, axial: [i32; 2]) -> bool {
+        let previous = self.positions.insert(id, axial);
+        if let Some(pos) = previous {
+            if pos == axial {
+                return false;
+            }
+            self.remove_from_cell(id, pos);
+        }
+        self.cells.entry(axial).or_default().push(id);
+        previous.is_none()
+    }
+
+    /// Returns the entities on the hex, in no particular order
+    pub fn cell(&self, axial: [i32; 2]) -> &[u32] {
+        self.cells
+            .get(&axial)
+            .map(|ids| ids.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Iterate over the entities and their axial positions, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (u32, [i32; 2])> + '_ {
+        self.positions.iter().map(|(id, pos)| (*id, *pos))
+    }
+
+    fn remove_from_cell(&mut self, id: u32, axial: [i32; 2]) {
+        if let Some(ids) = self.cells.get_mut(&axial) {
+            ids.retain(|x| *x != id);
+            if ids.is_empty() {
+                self.cells.remove(&axial);
+            }
+        }
+    }
+
+    fn keyed_by_distance(&self, center: [i32; 2]) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let center = axial_to_cube(&from_axial(&center));
+        self.positions.iter().map(move |(id, p)| {
+            let d = cube_distance(&axial_to_cube(&from_axial(p)), &center);
+            (d, *id)
+        })
+    }
+}
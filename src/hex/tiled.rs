@@ -0,0 +1,517 @@
+//! Loading and writing hexagonal maps in the JSON format of the [Tiled](https://www.mapeditor.org)
+//! map editor
+//!
+//! Only finite maps with `"orientation": "hexagonal"` and CSV encoded (plain array) tile layers
+//! are supported. Fields this module does not know about are kept, so a map survives a load and
+//! write roundtrip.
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use super::{from_axial, to_axial, HexMap, Hexagon, Orientation};
+use crate::array::Array2f;
+use crate::mat::mat2::Mat2f;
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StaggerAxis {
+    /// Columns are staggered, hexes are flat topped
+    X,
+    /// Rows are staggered, hexes are pointy topped
+    Y,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StaggerIndex {
+    /// Odd rows or columns are shifted
+    Odd,
+    /// Even rows or columns are shifted
+    Even,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TiledObject {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub name: String,
+    /// Position in Tiled's pixel space
+    pub x: f32,
+    pub y: f32,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TiledLayer {
+    /// `tilelayer`, `objectgroup`, ...
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub name: String,
+    /// Global tile ids of a tile layer, row by row. 0 is an empty tile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<u32>>,
+    /// Objects of an object group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub objects: Option<Vec<TiledObject>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A hexagonal Tiled map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TiledMap {
+    /// Number of columns
+    pub width: u32,
+    /// Number of rows
+    pub height: u32,
+    pub tilewidth: u32,
+    pub tileheight: u32,
+    /// Length of the side of a hex parallel to the stagger axis
+    pub hexsidelength: u32,
+    pub staggeraxis: StaggerAxis,
+    pub staggerindex: StaggerIndex,
+    pub layers: Vec<TiledLayer>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TiledError {
+    /// The input is not a valid Tiled JSON map
+    Json(String),
+    /// The map is not of `hexagonal` orientation
+    NotHexagonal,
+    /// The map is infinite
+    Infinite,
+    /// The tile layer does not have `width * height` tiles
+    LayerSize(String),
+    /// The map has more tiles than `i32::MAX`
+    TooLarge,
+    /// `tilewidth` or `tileheight` is zero, or the hex size is too large
+    InvalidTileSize,
+    /// The number of tiles does not match the hexagon
+    HexagonSize,
+}
+
+impl fmt::Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TiledError::Json(err) => write!(f, "Invalid Tiled map: {}", err),
+            TiledError::NotHexagonal => write!(f, "Only hexagonal maps are supported"),
+            TiledError::Infinite => write!(f, "Infinite maps are not supported"),
+            TiledError::LayerSize(name) => {
+                write!(f, "Layer '{}' does not match the size of the map", name)
+            }
+            TiledError::TooLarge => write!(f, "The map has too many tiles"),
+            TiledError::InvalidTileSize => write!(f, "Invalid tile size"),
+            TiledError::HexagonSize => write!(f, "Number of tiles does not match the hexagon"),
+        }
+    }
+}
+
+impl core::error::Error for TiledError {}
+
+impl From<serde_json::Error> for TiledError {
+    fn from(err: serde_json::Error) -> Self {
+        TiledError::Json(err.to_string())
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl_js_error!(TiledError);
+
+impl TiledMap {
+    /// Creates an empty finite map of regular hexes, without layers and tilesets.
+    ///
+    /// `size` is the distance between the center and the vertices of a hex, in pixels
+    ///
+    /// Fails if `size` is zero or too large, or the map has more tiles than `i32::MAX`.
+    pub fn new(
+        width: u32,
+        height: u32,
+        orientation: Orientation,
+        staggerindex: StaggerIndex,
+        size: u32,
+    ) -> Result<Self, TiledError> {
+        // regular hexes are 2 * size long along the stagger axis and sqrt(3) * size wide
+        let long = size
+            .checked_mul(2)
+            .filter(|l| *l > 0)
+            .ok_or(TiledError::InvalidTileSize)?;
+        let wide = (3f32.sqrt() * size as f32).round() as u32;
+        let (staggeraxis, tilewidth, tileheight) = match orientation {
+            Orientation::Pointy => (StaggerAxis::Y, wide, long),
+            Orientation::Flat => (StaggerAxis::X, long, wide),
+        };
+        let mut extra = Map::new();
+        extra.insert("type".to_owned(), "map".into());
+        extra.insert("version".to_owned(), "1.10".into());
+        extra.insert("orientation".to_owned(), "hexagonal".into());
+        extra.insert("renderorder".to_owned(), "right-down".into());
+        extra.insert("infinite".to_owned(), false.into());
+        extra.insert("nextlayerid".to_owned(), 1.into());
+        extra.insert("nextobjectid".to_owned(), 1.into());
+        extra.insert("tilesets".to_owned(), Value::Array(vec![]));
+        let map = Self {
+            width,
+            height,
+            tilewidth,
+            tileheight,
+            hexsidelength: size,
+            staggeraxis,
+            staggerindex,
+            layers: vec![],
+            extra,
+        };
+        map.num_tiles()?;
+        Ok(map)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, TiledError> {
+        let value: Value = serde_json::from_str(json)?;
+        if value.get("orientation").and_then(Value::as_str) != Some("hexagonal") {
+            return Err(TiledError::NotHexagonal);
+        }
+        if value.get("infinite").and_then(Value::as_bool) == Some(true) {
+            return Err(TiledError::Infinite);
+        }
+        let map: TiledMap = serde_json::from_value(value)?;
+        if map.tilewidth == 0 || map.tileheight == 0 {
+            return Err(TiledError::InvalidTileSize);
+        }
+        let n = map.num_tiles()?;
+        if let Some(layer) = map
+            .layers
+            .iter()
+            .find(|l| l.data.as_ref().map(|d| d.len() != n).unwrap_or(false))
+        {
+            return Err(TiledError::LayerSize(layer.name.clone()));
+        }
+        Ok(map)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize the map")
+    }
+
+    /// `width * height`, at most `i32::MAX` so the offset coordinates of every tile fit an `i32`
+    fn num_tiles(&self) -> Result<usize, TiledError> {
+        self.width
+            .checked_mul(self.height)
+            .filter(|n| *n <= i32::MAX as u32)
+            .map(|n| n as usize)
+            .ok_or(TiledError::TooLarge)
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        match self.staggeraxis {
+            StaggerAxis::X => Orientation::Flat,
+            StaggerAxis::Y => Orientation::Pointy,
+        }
+    }
+
+    /// Converts Tiled's `[column, row]` coordinates to axial coordinates
+    pub fn offset_to_axial(&self, [col, row]: [i32; 2]) -> [i32; 2] {
+        // `parity` is 1 for the shifted rows or columns
+        let parity = |i: i32| match self.staggerindex {
+            StaggerIndex::Odd => i & 1,
+            StaggerIndex::Even => -(i & 1),
+        };
+        match self.staggeraxis {
+            StaggerAxis::Y => [col - (row - parity(row)) / 2, row],
+            StaggerAxis::X => [col, row - (col - parity(col)) / 2],
+        }
+    }
+
+    /// Converts axial coordinates to Tiled's `[column, row]` coordinates
+    pub fn axial_to_offset(&self, [q, r]: [i32; 2]) -> [i32; 2] {
+        let parity = |i: i32| match self.staggerindex {
+            StaggerIndex::Odd => i & 1,
+            StaggerIndex::Even => -(i & 1),
+        };
+        match self.staggeraxis {
+            StaggerAxis::Y => [q + (r - parity(r)) / 2, r],
+            StaggerAxis::X => [q, r + (q - parity(q)) / 2],
+        }
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&TiledLayer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    /// Returns the axial coordinates and global tile ids of the non-empty tiles of a tile layer.
+    ///
+    /// Returns `None` if there is no tile layer called `name`.
+    pub fn tiles(&self, name: &str) -> Option<Vec<([i32; 2], u32)>> {
+        let data = self.layer(name)?.data.as_ref()?;
+        let width = self.width.max(1) as usize;
+        let tiles = data
+            .iter()
+            .enumerate()
+            .filter(|(_, gid)| **gid != 0)
+            .map(|(i, gid)| {
+                let offset = [(i % width) as i32, (i / width) as i32];
+                (self.offset_to_axial(offset), *gid)
+            })
+            .collect();
+        Some(tiles)
+    }
+
+    /// Rasterizes a tile layer into a map of the `hexagon`. Hexes without a tile are 0, tiles
+    /// outside of the hexagon are dropped.
+    ///
+    /// Returns `None` if there is no tile layer called `name`.
+    pub fn layer_to_hex_map(&self, name: &str, hexagon: Hexagon) -> Option<HexMap<u32>> {
+        let mut map = HexMap::filled(hexagon, 0);
+        for (p, gid) in self.tiles(name)? {
+            if let Some(tile) = map.get_mut(p) {
+                *tile = gid;
+            }
+        }
+        Some(map)
+    }
+
+    /// Writes the tiles of `map` into the tile layer called `name`, creating the layer if it does
+    /// not exist. Tiles outside of the Tiled map are dropped, the rest of the layer is cleared.
+    ///
+    /// Fails if the map has more tiles than `i32::MAX`.
+    pub fn set_layer_from_hex_map(
+        &mut self,
+        name: &str,
+        map: &HexMap<u32>,
+    ) -> Result<(), TiledError> {
+        let mut data = vec![0; self.num_tiles()?];
+        let (width, height) = (self.width as i32, self.height as i32);
+        for (p, gid) in map.iter() {
+            let [col, row] = self.axial_to_offset(p);
+            if 0 <= col && col < width && 0 <= row && row < height {
+                data[(row * width + col) as usize] = *gid;
+            }
+        }
+        match self.layers.iter_mut().find(|l| l.name == name) {
+            Some(layer) => layer.data = Some(data),
+            None => {
+                let id = self
+                    .extra
+                    .get("nextlayerid")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(1);
+                self.extra.insert("nextlayerid".to_owned(), (id + 1).into());
+                let mut extra = Map::new();
+                extra.insert("id".to_owned(), id.into());
+                extra.insert("width".to_owned(), self.width.into());
+                extra.insert("height".to_owned(), self.height.into());
+                extra.insert("x".to_owned(), 0.into());
+                extra.insert("y".to_owned(), 0.into());
+                extra.insert("opacity".to_owned(), 1.into());
+                extra.insert("visible".to_owned(), true.into());
+                self.layers.push(TiledLayer {
+                    kind: "tilelayer".to_owned(),
+                    name: name.to_owned(),
+                    data: Some(data),
+                    objects: None,
+                    extra,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts a point of Tiled's pixel space to the pixel space of `orientation`'s matrices,
+    /// where the center of the hex `(0, 0)` is the origin.
+    ///
+    /// `size` is the distance between the center and the vertices of a hex in the target space
+    ///
+    /// Panics if `tilewidth` or `tileheight` was set to zero after the map was created.
+    pub fn tiled_to_pixel(&self, point: &Vec2, size: f32) -> Vec2 {
+        let (origin, layout) = self.layout();
+        let axial = layout
+            .inverted()
+            .expect("Tile sizes must be positive")
+            .right_prod(*point - origin);
+        self.orientation().axial_to_pixel().right_prod(axial) * size
+    }
+
+    /// Inverse of `tiled_to_pixel`
+    pub fn pixel_to_tiled(&self, point: &Vec2, size: f32) -> Vec2 {
+        let (origin, layout) = self.layout();
+        let axial = self
+            .orientation()
+            .pixel_to_axial()
+            .right_prod(*point / size);
+        origin + layout.right_prod(axial)
+    }
+
+    /// Returns the positions of the objects of an object group, see `tiled_to_pixel`.
+    ///
+    /// Returns `None` if there is no object group called `name`.
+    pub fn object_positions(&self, name: &str, size: f32) -> Option<Vec<Vec2>> {
+        let objects = self.layer(name)?.objects.as_ref()?;
+        let positions = objects
+            .iter()
+            .map(|o| self.tiled_to_pixel(&Vec2::new(o.x, o.y), size))
+            .collect();
+        Some(positions)
+    }
+
+    /// Tiled's pixel position of the center of hex `(0, 0)` and the matrix from axial offsets to
+    /// Tiled's pixel offsets
+    fn layout(&self) -> (Vec2, Mat2f) {
+        let w = self.tilewidth as f32;
+        let h = self.tileheight as f32;
+        let s = self.hexsidelength as f32;
+        let shifted = self.staggerindex == StaggerIndex::Even;
+        match self.staggeraxis {
+            StaggerAxis::Y => {
+                let x = if shifted { w } else { w / 2. };
+                let layout = [[w, 0.], [w / 2., (h + s) / 2.]].into();
+                (Vec2::new(x, h / 2.), layout)
+            }
+            StaggerAxis::X => {
+                let y = if shifted { h } else { h / 2. };
+                let layout = [[(w + s) / 2., h / 2.], [0., h]].into();
+                (Vec2::new(w / 2., y), layout)
+            }
+        }
+    }
+}
+
+/// Hexagonal map of the Tiled map editor
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=TiledMap))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledMapJs {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(skip))]
+    pub map: TiledMap,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=TiledMap))]
+impl TiledMapJs {
+    /// Creates an empty map of regular hexes
+    ///
+    /// @param oddShifted whether the odd or the even rows (columns for flat hexes) are shifted
+    /// @param size distance between the center and the vertices of a hex, in pixels
+    ///
+    /// Throws if `size` is zero or too large, or the map has more tiles than the largest `i32`.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        width: u32,
+        height: u32,
+        orientation: Orientation,
+        odd_shifted: bool,
+        size: u32,
+    ) -> Result<TiledMapJs, JsValue> {
+        let index = if odd_shifted {
+            StaggerIndex::Odd
+        } else {
+            StaggerIndex::Even
+        };
+        let map = TiledMap::new(width, height, orientation, index, size)?;
+        Ok(Self { map })
+    }
+
+    /// Parses a map exported by Tiled. Throws if the map is invalid or not supported.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=fromJson)]
+    pub fn from_json(json: &str) -> Result<TiledMapJs, JsValue> {
+        let map = TiledMap::from_json(json)?;
+        Ok(Self { map })
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=toJson))]
+    pub fn to_json(&self) -> String {
+        self.map.to_json()
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn orientation(&self) -> Orientation {
+        self.map.orientation()
+    }
+
+    /// Returns the names of the layers
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=layerNames)]
+    pub fn layer_names(&self) -> Box<[JsValue]> {
+        self.map
+            .layers
+            .iter()
+            .map(|l| JsValue::from_str(&l.name))
+            .collect()
+    }
+
+    /// Converts Tiled's `(column, row)` coordinates to axial coordinates
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=offsetToAxial))]
+    pub fn offset_to_axial(&self, offset: &Vec2) -> Vec2 {
+        from_axial(&self.map.offset_to_axial(to_axial(offset)))
+    }
+
+    /// Converts axial coordinates to Tiled's `(column, row)` coordinates
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=axialToOffset))]
+    pub fn axial_to_offset(&self, axial: &Vec2) -> Vec2 {
+        from_axial(&self.map.axial_to_offset(to_axial(axial)))
+    }
+
+    /// Returns the global tile ids of the tile layer on the hexes of `hexagon`, in the same order
+    /// as `Hexagon.list_points`. Hexes without a tile are 0.
+    ///
+    /// Returns __undefined__ if there is no tile layer called `name`.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=layerTiles))]
+    pub fn layer_tiles(&self, name: &str, hexagon: &Hexagon) -> Option<Vec<u32>> {
+        let map = self.map.layer_to_hex_map(name, *hexagon)?;
+        Some(map.into_values())
+    }
+
+    /// Writes the global tile ids of the hexes of `hexagon`, given in the same order as
+    /// `Hexagon.list_points`, into the tile layer called `name`. The layer is created if it does
+    /// not exist.
+    ///
+    /// Throws if the number of tiles does not match the hexagon.
+    #[cfg(feature = "wasm")]
+    #[wasm_bindgen(js_name=setLayerTiles)]
+    pub fn set_layer_tiles(
+        &mut self,
+        name: &str,
+        hexagon: &Hexagon,
+        tiles: &[u32],
+    ) -> Result<(), JsValue> {
+        let map = HexMap::from_values(*hexagon, tiles.to_vec()).ok_or(TiledError::HexagonSize)?;
+        Ok(self.map.set_layer_from_hex_map(name, &map)?)
+    }
+
+    /// Returns the positions of the objects of an object group in the pixel space of the
+    /// orientation matrices, where the center of hex `(0, 0)` is the origin.
+    ///
+    /// Returns __undefined__ if there is no object group called `name`.
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=objectPositions))]
+    pub fn object_positions(&self, name: &str, size: f32) -> Option<Array2f> {
+        let data = self.map.object_positions(name, size)?;
+        Some(Array2f { data })
+    }
+
+    /// Converts a point of Tiled's pixel space to the pixel space of the orientation matrices
+    ///
+    /// @param size distance between the center and the vertices of a hex
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=tiledToPixel))]
+    pub fn tiled_to_pixel(&self, point: &Vec2, size: f32) -> Vec2 {
+        self.map.tiled_to_pixel(point, size)
+    }
+
+    /// Inverse of `tiledToPixel`
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=pixelToTiled))]
+    pub fn pixel_to_tiled(&self, point: &Vec2, size: f32) -> Vec2 {
+        self.map.pixel_to_tiled(point, size)
+    }
+}
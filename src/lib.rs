@@ -1,14 +1,33 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "wasm")]
+#[macro_use]
+mod bindings;
+
+#[cfg(feature = "alloc")]
+pub mod array;
+pub mod fixed;
 pub mod hex;
 pub mod mat;
-pub mod array;
+pub mod quat;
+#[cfg(feature = "alloc")]
+pub mod rand;
+pub mod scalar;
+#[cfg(feature = "alloc")]
+pub mod svg;
 pub mod vec;
 
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace=console)]
@@ -18,6 +37,7 @@ extern "C" {
 /// Initialize the global state of the library.
 ///
 /// Users should not have to call this method.
+#[cfg(feature = "wasm")]
 #[wasm_bindgen(start)]
 pub fn _start() {
     #[cfg(feature = "console_error_panic_hook")]
@@ -0,0 +1,314 @@
+//! 2D affine transformations
+use crate::mat::mat2::Mat2;
+use crate::mat::mat3::Mat3;
+use crate::scalar::{Float, Scalar};
+use crate::vec::vec2::Vec2;
+#[cfg(feature = "wasm")]
+use crate::{
+    mat::{mat2::Mat2f, mat3::Mat3f},
+    vec::vec2::Vec2f,
+};
+use core::ops::{Mul, MulAssign};
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// 2D affine transformation: a linear transformation followed by a translation
+///
+/// Equivalent to a `Mat3` with `(0, 0, 1)` as the last row, but only stores the 6 meaningful
+/// numbers.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Affine2<T = f32> {
+    pub linear: Mat2<T>,
+    pub translation: Vec2<T>,
+}
+
+pub type Affine2f = Affine2<f32>;
+pub type Affine2d = Affine2<f64>;
+pub type Affine2i = Affine2<i32>;
+
+impl<T: Scalar> Affine2<T> {
+    pub fn new(linear: Mat2<T>, translation: Vec2<T>) -> Self {
+        Self {
+            linear,
+            translation,
+        }
+    }
+
+    /// Returns the identity transformation
+    pub fn identity() -> Self {
+        Self::scale(T::ONE)
+    }
+
+    /// Uniform scale transformation
+    pub fn scale(a: T) -> Self {
+        Self::new(Mat2::scale(a), Vec2::new(T::ZERO, T::ZERO))
+    }
+
+    /// Creates a transformation for the given translation `t`
+    /// Where `b = A*a` equals `a+t`
+    pub fn translate(t: Vec2<T>) -> Self {
+        Self::new(Mat2::identity(), t)
+    }
+
+    /// Returns __null__ if the last row of `mat` is not `(0, 0, 1)`, because then it is not an
+    /// affine transformation
+    pub fn from_mat3(mat: &Mat3<T>) -> Option<Self> {
+        let (o, i) = (T::ZERO, T::ONE);
+        if mat.x_axis[2] != o || mat.y_axis[2] != o || mat.w_axis[2] != i {
+            return None;
+        }
+        let [x1, x2, _] = mat.x_axis;
+        let [y1, y2, _] = mat.y_axis;
+        let [t1, t2, _] = mat.w_axis;
+        Some(Self::new([[x1, x2], [y1, y2]].into(), Vec2::new(t1, t2)))
+    }
+
+    /// Returns the equivalent 3 by 3 homogeneous matrix
+    pub fn to_mat3(&self) -> Mat3<T> {
+        let [x1, x2] = self.linear.x_axis;
+        let [y1, y2] = self.linear.y_axis;
+        let Vec2 { x, y } = self.translation;
+        let (o, i) = (T::ZERO, T::ONE);
+        Mat3 {
+            x_axis: [x1, x2, o],
+            y_axis: [y1, y2, o],
+            w_axis: [x, y, i],
+        }
+    }
+
+    /// Returns the linear part.
+    ///
+    /// If `this` has a translation then __null__ is returned.
+    pub fn to_mat2(&self) -> Option<Mat2<T>> {
+        if self.translation != Vec2::new(T::ZERO, T::ZERO) {
+            return None;
+        }
+        Some(self.linear.clone())
+    }
+
+    /// Applies the linear part and the translation to the point `p`
+    #[inline]
+    pub fn transform_point(&self, p: Vec2<T>) -> Vec2<T> {
+        let Vec2 { x, y } = self.transform_vector(p);
+        Vec2::new(x + self.translation.x, y + self.translation.y)
+    }
+
+    /// Applies the linear part to the direction `v`, directions are not translated
+    #[inline]
+    pub fn transform_vector(&self, Vec2 { x, y }: Vec2<T>) -> Vec2<T> {
+        let [x1, x2] = self.linear.x_axis;
+        let [y1, y2] = self.linear.y_axis;
+        Vec2::new(x1 * x + y1 * y, x2 * x + y2 * y)
+    }
+
+    /// Calculate `A*B=C` where `A` is self. `C` applies `B` first, then `A`.
+    pub fn compose(&self, b: &Self) -> Self {
+        let linear = Mat2 {
+            x_axis: self.transform_vector(b.linear.x_axis.into()).into(),
+            y_axis: self.transform_vector(b.linear.y_axis.into()).into(),
+        };
+        Self::new(linear, self.transform_point(b.translation))
+    }
+
+    /// Calculate the determinant of the linear part
+    pub fn det(&self) -> T {
+        self.linear.det()
+    }
+}
+
+impl<T: Float> Affine2<T> {
+    /// Returns a rotation with `rads` radians counter-clockwise around the origin
+    pub fn rotation(rads: T) -> Self {
+        let (sin, cos) = (rads.sin(), rads.cos());
+        Self::new(
+            [[cos, sin], [-sin, cos]].into(),
+            Vec2::new(T::ZERO, T::ZERO),
+        )
+    }
+
+    /// Returns a new transformation which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Self> {
+        let mut res = Self::from(self.linear.inverted()?);
+        let t = res.transform_vector(self.translation);
+        res.translation = Vec2::new(-t.x, -t.y);
+        Some(res)
+    }
+
+    /// Check if the two transformations are equal, within `epsilon` range.
+    pub fn almost_equal(&self, other: &Self, epsilon: T) -> bool {
+        self.linear.almost_equal(&other.linear, epsilon)
+            && (self.translation.x - other.translation.x).abs() < epsilon
+            && (self.translation.y - other.translation.y).abs() < epsilon
+    }
+}
+
+impl<T: Scalar> From<Mat2<T>> for Affine2<T> {
+    fn from(linear: Mat2<T>) -> Self {
+        Self::new(linear, Vec2::new(T::ZERO, T::ZERO))
+    }
+}
+
+impl<T: Scalar> From<Affine2<T>> for Mat3<T> {
+    fn from(a: Affine2<T>) -> Self {
+        a.to_mat3()
+    }
+}
+
+impl<'a, T: Scalar> Mul<&'a Affine2<T>> for &'a Affine2<T> {
+    type Output = Affine2<T>;
+
+    fn mul(self, rhs: &'a Affine2<T>) -> Self::Output {
+        self.compose(rhs)
+    }
+}
+
+impl<T: Scalar> Mul<Affine2<T>> for Affine2<T> {
+    type Output = Affine2<T>;
+
+    fn mul(self, rhs: Affine2<T>) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+impl<T: Scalar> MulAssign<&Affine2<T>> for Affine2<T> {
+    fn mul_assign(&mut self, rhs: &Affine2<T>) {
+        *self = self.compose(rhs);
+    }
+}
+
+impl<T: Scalar> Mul<Vec2<T>> for &Affine2<T> {
+    type Output = Vec2<T>;
+
+    fn mul(self, p: Vec2<T>) -> Vec2<T> {
+        self.transform_point(p)
+    }
+}
+
+/// `Affine2f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Affine2f)]
+#[derive(Debug, Clone, Default)]
+pub struct Affine2fJs(Affine2f);
+
+#[cfg(feature = "wasm")]
+impl From<Affine2f> for Affine2fJs {
+    fn from(a: Affine2f) -> Self {
+        Self(a)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Affine2fJs> for Affine2f {
+    fn from(Affine2fJs(a): Affine2fJs) -> Self {
+        a
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Affine2f, Affine2fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Affine2f)]
+impl Affine2fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new(linear: &Mat2f, translation: Vec2f) -> Self {
+        Self(Affine2::new(linear.clone(), translation))
+    }
+
+    pub fn linear(&self) -> Mat2f {
+        self.0.linear.clone()
+    }
+
+    pub fn translation(&self) -> Vec2f {
+        self.0.translation
+    }
+
+    /// Returns the identity transformation
+    pub fn identity() -> Affine2f {
+        Affine2::identity()
+    }
+
+    /// Uniform scale transformation
+    pub fn scale(a: f32) -> Affine2f {
+        Affine2::scale(a)
+    }
+
+    /// Creates a transformation for the given translation `t`
+    /// Where `b = A*a` equals `a+t`
+    pub fn translate(t: Vec2f) -> Affine2f {
+        Affine2::translate(t)
+    }
+
+    /// Returns a rotation with `rads` radians counter-clockwise around the origin
+    pub fn rotation(rads: f32) -> Affine2f {
+        Affine2::rotation(rads)
+    }
+
+    /// The linear transformation `mat` without translation
+    #[wasm_bindgen(js_name=fromMat2)]
+    pub fn from_mat2(mat: &Mat2f) -> Affine2f {
+        mat.clone().into()
+    }
+
+    /// Returns __null__ if the last row of `mat` is not `(0, 0, 1)`, because then it is not an
+    /// affine transformation
+    #[wasm_bindgen(js_name=fromMat3)]
+    pub fn from_mat3(mat: &Mat3f) -> Option<Affine2f> {
+        Affine2::from_mat3(mat)
+    }
+
+    /// Returns the equivalent 3 by 3 homogeneous matrix
+    #[wasm_bindgen(js_name=toMat3)]
+    pub fn to_mat3(&self) -> Mat3f {
+        self.0.to_mat3()
+    }
+
+    /// Returns the linear part.
+    ///
+    /// If `this` has a translation then __null__ is returned.
+    #[wasm_bindgen(js_name=toMat2)]
+    pub fn to_mat2(&self) -> Option<Mat2f> {
+        self.0.to_mat2()
+    }
+
+    /// Applies the linear part and the translation to the point `p`
+    #[wasm_bindgen(js_name=transformPoint)]
+    pub fn transform_point(&self, p: Vec2f) -> Vec2f {
+        self.0.transform_point(p)
+    }
+
+    /// Applies the linear part to the direction `v`, directions are not translated
+    #[wasm_bindgen(js_name=transformVector)]
+    pub fn transform_vector(&self, v: Vec2f) -> Vec2f {
+        self.0.transform_vector(v)
+    }
+
+    /// Calculate `A*B=C` where `A` is this. `C` applies `B` first, then `A`.
+    pub fn compose(&self, b: &Affine2f) -> Affine2f {
+        self.0.compose(b)
+    }
+
+    /// Returns a new transformation which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Affine2f> {
+        self.0.inverted()
+    }
+
+    /// Calculate the determinant of the linear part
+    pub fn det(&self) -> f32 {
+        self.0.det()
+    }
+
+    /// Check if the two transformations are equal, within `epsilon` range.
+    #[wasm_bindgen(js_name=almostEqual)]
+    pub fn almost_equal(&self, other: &Affine2f, epsilon: f32) -> bool {
+        self.0.almost_equal(other, epsilon)
+    }
+}
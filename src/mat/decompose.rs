@@ -0,0 +1,232 @@
+//! Decomposition and interpolation of 2D homogeneous transformations
+use crate::mat::affine2::Affine2;
+use crate::mat::mat3::Mat3;
+#[cfg(feature = "wasm")]
+use crate::mat::mat3::{Mat3f, Mat3fJs};
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+#[cfg(feature = "wasm")]
+use crate::vec::vec2::Vec2f;
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Components of a 2D affine transformation
+///
+/// The transformation scales by `scale`, then shears the `X` axis by `shear` times the `Y`
+/// coordinate, rotates with `rotation` radians counter-clockwise, and finally translates by
+/// `translation`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Transform2<T = f32> {
+    pub translation: Vec2<T>,
+    pub rotation: T,
+    pub scale: Vec2<T>,
+    pub shear: T,
+}
+
+pub type Transform2f = Transform2<f32>;
+pub type Transform2d = Transform2<f64>;
+
+impl<T: Float> Default for Transform2<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T: Float> Transform2<T> {
+    /// Returns the components of the identity transformation
+    pub fn identity() -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            translation: Vec2::new(o, o),
+            rotation: o,
+            scale: Vec2::new(i, i),
+            shear: o,
+        }
+    }
+
+    /// Interpolates the components linearly, except for the rotation, which turns along the
+    /// shorter arc. `t = 0` returns `a`, `t = 1` returns `b`.
+    pub fn interpolate(a: &Self, b: &Self, t: T) -> Self {
+        let lerp = |a: T, b: T| a + (b - a) * t;
+        let lerp2 = |a: Vec2<T>, b: Vec2<T>| Vec2::new(lerp(a.x, b.x), lerp(a.y, b.y));
+
+        let pi = (-T::ONE).acos();
+        let tau = pi + pi;
+        let turn = b.rotation - a.rotation;
+        let turn = turn - tau * (turn / tau).round();
+        Self {
+            translation: lerp2(a.translation, b.translation),
+            rotation: a.rotation + turn * t,
+            scale: lerp2(a.scale, b.scale),
+            shear: lerp(a.shear, b.shear),
+        }
+    }
+}
+
+impl<T: Float> Mat3<T> {
+    /// Splits this transformation into its translation, rotation, scale and shear.
+    ///
+    /// The `X` scale is never negative, reflections are represented by a negative `Y` scale.
+    ///
+    /// If the last row is not `(0, 0, 1)` or the matrix is not invertible then __null__ is
+    /// returned.
+    pub fn decompose(&self) -> Option<Transform2<T>> {
+        let affine = Affine2::from_mat3(self)?;
+        if affine.det() == T::ZERO {
+            return None;
+        }
+        let [x1, x2] = affine.linear.x_axis;
+        let [y1, y2] = affine.linear.y_axis;
+
+        let scale_x = (x1 * x1 + x2 * x2).sqrt();
+        let rotation = x2.atan2(x1);
+        let (sin, cos) = (rotation.sin(), rotation.cos());
+        // undo the rotation of the `Y` axis
+        let sheared = cos * y1 + sin * y2;
+        let scale_y = cos * y2 - sin * y1;
+
+        Some(Transform2 {
+            translation: affine.translation,
+            rotation,
+            scale: Vec2::new(scale_x, scale_y),
+            shear: sheared / scale_y,
+        })
+    }
+
+    /// Builds the transformation from its components, the inverse of `decompose`
+    pub fn compose(transform: &Transform2<T>) -> Self {
+        let Transform2 {
+            translation,
+            rotation,
+            scale,
+            shear,
+        } = *transform;
+        let (sin, cos) = (rotation.sin(), rotation.cos());
+        let (o, i) = (T::ZERO, T::ONE);
+        // rotation * shear * scale
+        let y1 = shear * scale.y;
+        Self {
+            x_axis: [cos * scale.x, sin * scale.x, o],
+            y_axis: [cos * y1 - sin * scale.y, sin * y1 + cos * scale.y, o],
+            w_axis: [translation.x, translation.y, i],
+        }
+    }
+
+    /// Interpolates between the transformations `a` and `b` by decomposing them, interpolating
+    /// the components and composing the result. The rotation turns along the shorter arc.
+    ///
+    /// If either can not be decomposed then __null__ is returned.
+    pub fn interpolate(a: &Self, b: &Self, t: T) -> Option<Self> {
+        let a = a.decompose()?;
+        let b = b.decompose()?;
+        Some(Self::compose(&Transform2::interpolate(&a, &b, t)))
+    }
+}
+
+/// `Transform2f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Transform2f, inspectable)]
+#[derive(Debug, Clone, Copy)]
+pub struct Transform2fJs {
+    pub translation: Vec2f,
+    pub rotation: f32,
+    pub scale: Vec2f,
+    pub shear: f32,
+}
+
+#[cfg(feature = "wasm")]
+impl From<Transform2f> for Transform2fJs {
+    fn from(
+        Transform2 {
+            translation,
+            rotation,
+            scale,
+            shear,
+        }: Transform2f,
+    ) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+            shear,
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Transform2fJs> for Transform2f {
+    fn from(
+        Transform2fJs {
+            translation,
+            rotation,
+            scale,
+            shear,
+        }: Transform2fJs,
+    ) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+            shear,
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Transform2f, Transform2fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Transform2f)]
+impl Transform2fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new(translation: Vec2f, rotation: f32, scale: Vec2f, shear: f32) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+            shear,
+        }
+    }
+
+    /// Returns the components of the identity transformation
+    pub fn identity() -> Transform2f {
+        Transform2::identity()
+    }
+
+    /// Interpolates the components linearly, except for the rotation, which turns along the
+    /// shorter arc. `t = 0` returns `a`, `t = 1` returns `b`.
+    pub fn interpolate(a: &Transform2f, b: &Transform2f, t: f32) -> Transform2f {
+        Transform2::interpolate(a, b, t)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Mat3f)]
+impl Mat3fJs {
+    /// Splits this transformation into its translation, rotation, scale and shear.
+    ///
+    /// The `X` scale is never negative, reflections are represented by a negative `Y` scale.
+    ///
+    /// If the last row is not `(0, 0, 1)` or the matrix is not invertible then __null__ is
+    /// returned.
+    pub fn decompose(&self) -> Option<Transform2f> {
+        Mat3f::from(self.clone()).decompose()
+    }
+
+    /// Builds the transformation from its components, the inverse of `decompose`
+    pub fn compose(transform: &Transform2f) -> Mat3f {
+        Mat3::compose(transform)
+    }
+
+    /// Interpolates between the transformations `a` and `b` by decomposing them, interpolating
+    /// the components and composing the result. The rotation turns along the shorter arc.
+    ///
+    /// If either can not be decomposed then __null__ is returned.
+    pub fn interpolate(a: &Mat3f, b: &Mat3f, t: f32) -> Option<Mat3f> {
+        Mat3::interpolate(a, b, t)
+    }
+}
@@ -1,29 +1,37 @@
-//! Basic 2 by 2 float matrices
-use crate::mat::mat3::Mat3f;
+//! Basic 2 by 2 matrices
+use crate::mat::mat3::Mat3;
+use crate::scalar::{Float, Scalar};
 use crate::vec::vec2::Vec2;
 use crate::vec::vec3::Vec3;
+#[cfg(feature = "wasm")]
+use crate::{mat::mat3::Mat3f, vec::vec2::Vec2f, vec::vec3::Vec3f};
+use core::mem::swap;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "alloc")]
 use serde_derive::{Deserialize, Serialize};
-use std::mem::swap;
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 /// 2 by 2 column major matrix
-#[wasm_bindgen(js_name=Mat2f)]
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
-pub struct Mat2f {
-    #[wasm_bindgen(skip)]
-    pub x_axis: [f32; 2],
-    #[wasm_bindgen(skip)]
-    pub y_axis: [f32; 2],
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Mat2<T = f32> {
+    pub x_axis: [T; 2],
+    pub y_axis: [T; 2],
 }
 
-impl From<[[f32; 2]; 2]> for Mat2f {
-    fn from([x_axis, y_axis]: [[f32; 2]; 2]) -> Self {
+pub type Mat2f = Mat2<f32>;
+pub type Mat2d = Mat2<f64>;
+pub type Mat2i = Mat2<i32>;
+
+impl<T> From<[[T; 2]; 2]> for Mat2<T> {
+    fn from([x_axis, y_axis]: [[T; 2]; 2]) -> Self {
         Self { x_axis, y_axis }
     }
 }
-impl Mat2f {
-    pub fn axis_mut(&mut self, col: usize) -> &mut [f32; 2] {
+
+impl<T: Scalar> Mat2<T> {
+    pub fn axis_mut(&mut self, col: usize) -> &mut [T; 2] {
         match col {
             0 => &mut self.x_axis,
             1 => &mut self.y_axis,
@@ -31,14 +39,10 @@ impl Mat2f {
         }
     }
 
-    pub fn at_mut(&mut self, col: usize, row: usize) -> &mut f32 {
+    pub fn at_mut(&mut self, col: usize, row: usize) -> &mut T {
         &mut self.axis_mut(col)[row]
     }
-}
 
-#[wasm_bindgen(js_name=Mat2f)]
-impl Mat2f {
-    #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
         Default::default()
     }
@@ -64,36 +68,32 @@ impl Mat2f {
     /// | this21    this22    0 |
     /// | 0         0         1 |
     /// ```
-    #[wasm_bindgen(js_name=toMat3)]
-    pub fn to_mat3(&self, last_column: Option<Vec3>, last_row: Option<Vec2>) -> Mat3f {
+    pub fn to_mat3(&self, last_column: Option<Vec3<T>>, last_row: Option<Vec2<T>>) -> Mat3<T> {
         let [x1, x2] = self.x_axis;
         let [y1, y2] = self.y_axis;
 
-        let last = last_row.unwrap_or_else(|| Vec2::new(0., 0.));
-        Mat3f {
+        let last = last_row.unwrap_or_else(|| Vec2::new(T::ZERO, T::ZERO));
+        Mat3 {
             x_axis: [x1, x2, last.x],
             y_axis: [y1, y2, last.y],
             w_axis: last_column
                 .map(|v| v.into())
-                .unwrap_or_else(|| [0., 0., 1.]),
+                .unwrap_or_else(|| [T::ZERO, T::ZERO, T::ONE]),
         }
     }
 
-    #[wasm_bindgen]
-    pub fn scale(a: f32) -> Self {
+    pub fn scale(a: T) -> Self {
         Self {
-            x_axis: [a, 0.],
-            y_axis: [0., a],
+            x_axis: [a, T::ZERO],
+            y_axis: [T::ZERO, a],
         }
     }
 
-    #[wasm_bindgen]
-    pub fn swap(&mut self, other: &mut Mat2f) {
+    pub fn swap(&mut self, other: &mut Self) {
         swap(self, other);
     }
 
-    #[wasm_bindgen]
-    pub fn axis(&self, col: usize) -> Vec2 {
+    pub fn axis(&self, col: usize) -> Vec2<T> {
         match col {
             0 => self.x_axis.into(),
             1 => self.y_axis.into(),
@@ -101,18 +101,16 @@ impl Mat2f {
         }
     }
 
-    #[wasm_bindgen]
-    pub fn at(&self, col: usize, row: usize) -> f32 {
+    pub fn at(&self, col: usize, row: usize) -> T {
         self.axis(col)[row]
     }
 
-    pub fn set(&mut self, col: usize, row: usize, val: f32) {
+    pub fn set(&mut self, col: usize, row: usize, val: T) {
         self.axis_mut(col)[row] = val;
     }
 
     /// `v*M` where `M` is self
-    #[wasm_bindgen(js_name=leftProd)]
-    pub fn left_prod(&self, v: Vec2) -> Vec2 {
+    pub fn left_prod(&self, v: Vec2<T>) -> Vec2<T> {
         [
             v[0] * self.at(0, 0) + v[1] * self.at(0, 1),
             v[0] * self.at(1, 0) + v[1] * self.at(1, 1),
@@ -121,8 +119,7 @@ impl Mat2f {
     }
 
     /// `M*v` where `M` is self
-    #[wasm_bindgen(js_name=rightProd)]
-    pub fn right_prod(&self, v: Vec2) -> Vec2 {
+    pub fn right_prod(&self, v: Vec2<T>) -> Vec2<T> {
         [
             v[0] * self.at(0, 0) + v[1] * self.at(1, 0),
             v[0] * self.at(0, 1) + v[1] * self.at(1, 1),
@@ -131,45 +128,16 @@ impl Mat2f {
     }
 
     /// Calculate the determinant
-    #[wasm_bindgen]
-    pub fn det(&self) -> f32 {
+    pub fn det(&self) -> T {
         self.x_axis[0] * self.y_axis[1] - self.x_axis[1] * self.y_axis[0]
     }
 
-    /// Returns a new matrix which is the inverse of this.
-    ///
-    /// If `this` is not invertible then __null__ is returned.
-    #[wasm_bindgen]
-    pub fn inverted(&self) -> Option<Mat2f> {
-        let det = self.det();
-        if det == 0.0 {
-            return None;
-        }
-
-        let [u11, u21] = self.x_axis;
-        let [u12, u22] = self.y_axis;
-
-        let dev_inv = 1.0 / det;
-
-        let mat = Self {
-            x_axis: [dev_inv * u22, -dev_inv * u21],
-            y_axis: [-dev_inv * u12, dev_inv * u11],
-        };
-
-        Some(mat)
-    }
-
     /// Returns the identity matrix
-    #[wasm_bindgen]
-    pub fn identity() -> Mat2f {
-        Mat2f {
-            x_axis: [1., 0.],
-            y_axis: [0., 1.],
-        }
+    pub fn identity() -> Self {
+        Self::scale(T::ONE)
     }
 
     /// Returns a new matrix that is the transponent of this
-    #[wasm_bindgen]
     pub fn transposed(&self) -> Self {
         Self {
             x_axis: [self.x_axis[0], self.y_axis[0]],
@@ -178,9 +146,8 @@ impl Mat2f {
     }
 
     /// Calculate `A*B=C` where `A` is self
-    #[wasm_bindgen(js_name=matMul)]
     #[allow(non_snake_case)]
-    pub fn mat_mul(&self, B: &Mat2f) -> Mat2f {
+    pub fn mat_mul(&self, B: &Self) -> Self {
         let mut C = Self::default();
         for c in 0..2 {
             for r in 0..2 {
@@ -190,87 +157,110 @@ impl Mat2f {
         }
         C
     }
+}
+
+impl<T: Float> Mat2<T> {
+    /// Returns a new matrix which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Self> {
+        let det = self.det();
+        if det == T::ZERO {
+            return None;
+        }
+
+        let [u11, u21] = self.x_axis;
+        let [u12, u22] = self.y_axis;
+
+        let dev_inv = T::ONE / det;
+
+        let mat = Self {
+            x_axis: [dev_inv * u22, -dev_inv * u21],
+            y_axis: [-dev_inv * u12, dev_inv * u11],
+        };
+
+        Some(mat)
+    }
 
     /// Check if the two matrices are equal, within `epsilon` range.
-    #[wasm_bindgen(js_name=almostEqual)]
-    pub fn almost_equal(&self, other: &Mat2f, epsilon: f32) -> bool {
+    pub fn almost_equal(&self, other: &Self, epsilon: T) -> bool {
         self.x_axis
             .iter()
             .zip(other.x_axis.iter())
-            .all(|(a, b)| (a - b).abs() < epsilon)
+            .all(|(a, b)| (*a - *b).abs() < epsilon)
             && self
                 .y_axis
                 .iter()
                 .zip(other.y_axis.iter())
-                .all(|(a, b)| (a - b).abs() < epsilon)
+                .all(|(a, b)| (*a - *b).abs() < epsilon)
     }
 }
 
-impl<'a> Mul<Mat2f> for &'a Mat2f {
-    type Output = Mat2f;
+impl<'a, T: Scalar> Mul<Mat2<T>> for &'a Mat2<T> {
+    type Output = Mat2<T>;
 
-    fn mul(self, rhs: Mat2f) -> Self::Output {
+    fn mul(self, rhs: Mat2<T>) -> Self::Output {
         self.mat_mul(&rhs)
     }
 }
 
-impl<'a> Mul<&'a Mat2f> for &'a Mat2f {
-    type Output = Mat2f;
+impl<'a, T: Scalar> Mul<&'a Mat2<T>> for &'a Mat2<T> {
+    type Output = Mat2<T>;
 
-    fn mul(self, rhs: &'a Mat2f) -> Self::Output {
+    fn mul(self, rhs: &'a Mat2<T>) -> Self::Output {
         self.mat_mul(rhs)
     }
 }
 
-impl<'a> Mul<&'a Mat2f> for Mat2f {
-    type Output = Mat2f;
+impl<'a, T: Scalar> Mul<&'a Mat2<T>> for Mat2<T> {
+    type Output = Mat2<T>;
 
-    fn mul(self, rhs: &'a Mat2f) -> Self::Output {
+    fn mul(self, rhs: &'a Mat2<T>) -> Self::Output {
         self.mat_mul(rhs)
     }
 }
 
-impl Mul<Mat2f> for Mat2f {
-    type Output = Mat2f;
+impl<T: Scalar> Mul<Mat2<T>> for Mat2<T> {
+    type Output = Mat2<T>;
 
-    fn mul(self, rhs: Mat2f) -> Self::Output {
+    fn mul(self, rhs: Mat2<T>) -> Self::Output {
         self.mat_mul(&rhs)
     }
 }
 
-impl Mul<f32> for Mat2f {
+impl<T: Scalar> Mul<T> for Mat2<T> {
     type Output = Self;
 
-    fn mul(mut self, rhs: f32) -> Self::Output {
+    fn mul(mut self, rhs: T) -> Self::Output {
         self *= rhs;
         self
     }
 }
 
-impl MulAssign<f32> for Mat2f {
-    fn mul_assign(&mut self, rhs: f32) {
+impl<T: Scalar> MulAssign<T> for Mat2<T> {
+    fn mul_assign(&mut self, rhs: T) {
         self.x_axis.iter_mut().for_each(|x| *x *= rhs);
         self.y_axis.iter_mut().for_each(|x| *x *= rhs);
     }
 }
 
-impl Div<f32> for Mat2f {
+impl<T: Scalar> Div<T> for Mat2<T> {
     type Output = Self;
 
-    fn div(mut self, rhs: f32) -> Self::Output {
+    fn div(mut self, rhs: T) -> Self::Output {
         self /= rhs;
         self
     }
 }
 
-impl DivAssign<f32> for Mat2f {
-    fn div_assign(&mut self, rhs: f32) {
+impl<T: Scalar> DivAssign<T> for Mat2<T> {
+    fn div_assign(&mut self, rhs: T) {
         self.x_axis.iter_mut().for_each(|x| *x /= rhs);
         self.y_axis.iter_mut().for_each(|x| *x /= rhs);
     }
 }
 
-impl Add<&Self> for Mat2f {
+impl<T: Scalar> Add<&Self> for Mat2<T> {
     type Output = Self;
 
     fn add(mut self, rhs: &Self) -> Self::Output {
@@ -279,20 +269,20 @@ impl Add<&Self> for Mat2f {
     }
 }
 
-impl AddAssign<&Self> for Mat2f {
+impl<T: Scalar> AddAssign<&Self> for Mat2<T> {
     fn add_assign(&mut self, rhs: &Self) {
         self.x_axis
             .iter_mut()
             .zip(rhs.x_axis.iter())
-            .for_each(|(a, b)| *a += b);
+            .for_each(|(a, b)| *a += *b);
         self.y_axis
             .iter_mut()
             .zip(rhs.y_axis.iter())
-            .for_each(|(a, b)| *a += b);
+            .for_each(|(a, b)| *a += *b);
     }
 }
 
-impl Sub<&Self> for Mat2f {
+impl<T: Scalar> Sub<&Self> for Mat2<T> {
     type Output = Self;
 
     fn sub(mut self, rhs: &Self) -> Self::Output {
@@ -301,15 +291,140 @@ impl Sub<&Self> for Mat2f {
     }
 }
 
-impl SubAssign<&Self> for Mat2f {
+impl<T: Scalar> SubAssign<&Self> for Mat2<T> {
     fn sub_assign(&mut self, rhs: &Self) {
         self.x_axis
             .iter_mut()
             .zip(rhs.x_axis.iter())
-            .for_each(|(a, b)| *a -= b);
+            .for_each(|(a, b)| *a -= *b);
         self.y_axis
             .iter_mut()
             .zip(rhs.y_axis.iter())
-            .for_each(|(a, b)| *a -= b);
+            .for_each(|(a, b)| *a -= *b);
+    }
+}
+
+/// `Mat2f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Mat2f)]
+#[derive(Debug, Clone, Default)]
+pub struct Mat2fJs(Mat2f);
+
+#[cfg(feature = "wasm")]
+impl From<Mat2f> for Mat2fJs {
+    fn from(mat: Mat2f) -> Self {
+        Self(mat)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Mat2fJs> for Mat2f {
+    fn from(Mat2fJs(mat): Mat2fJs) -> Self {
+        mat
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Mat2f, Mat2fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Mat2f)]
+impl Mat2fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self(Mat2::new())
+    }
+
+    /// Converts `this` to a 3 by 3 matrix
+    ///
+    /// __Layout__
+    ///
+    /// ```txt
+    /// | this11    this12    last_column1 |
+    /// | this21    this22    last_column2 |
+    /// | last_row1 last_row2 last_column3 |
+    /// ```
+    ///
+    /// __Defaults__
+    ///
+    /// `last_column` defaults to (0, 0, 1)
+    ///
+    /// `last_row` defaults to (0, 0)
+    ///
+    /// ```txt
+    /// | this11    this12    0 |
+    /// | this21    this22    0 |
+    /// | 0         0         1 |
+    /// ```
+    #[wasm_bindgen(js_name=toMat3)]
+    pub fn to_mat3(&self, last_column: Option<Vec3f>, last_row: Option<Vec2f>) -> Mat3f {
+        self.0.to_mat3(last_column, last_row)
+    }
+
+    pub fn scale(a: f32) -> Mat2f {
+        Mat2::scale(a)
+    }
+
+    pub fn swap(&mut self, other: &mut Mat2f) {
+        self.0.swap(other);
+    }
+
+    pub fn axis(&self, col: usize) -> Vec2f {
+        self.0.axis(col)
+    }
+
+    pub fn at(&self, col: usize, row: usize) -> f32 {
+        self.0.at(col, row)
+    }
+
+    pub fn set(&mut self, col: usize, row: usize, val: f32) {
+        self.0.set(col, row, val);
+    }
+
+    /// `v*M` where `M` is self
+    #[wasm_bindgen(js_name=leftProd)]
+    pub fn left_prod(&self, v: Vec2f) -> Vec2f {
+        self.0.left_prod(v)
+    }
+
+    /// `M*v` where `M` is self
+    #[wasm_bindgen(js_name=rightProd)]
+    pub fn right_prod(&self, v: Vec2f) -> Vec2f {
+        self.0.right_prod(v)
+    }
+
+    /// Calculate the determinant
+    pub fn det(&self) -> f32 {
+        self.0.det()
+    }
+
+    /// Returns a new matrix which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Mat2f> {
+        self.0.inverted()
+    }
+
+    /// Returns the identity matrix
+    pub fn identity() -> Mat2f {
+        Mat2::identity()
+    }
+
+    /// Returns a new matrix that is the transponent of this
+    pub fn transposed(&self) -> Mat2f {
+        self.0.transposed()
+    }
+
+    /// Calculate `A*B=C` where `A` is self
+    #[wasm_bindgen(js_name=matMul)]
+    pub fn mat_mul(&self, b: &Mat2f) -> Mat2f {
+        self.0.mat_mul(b)
+    }
+
+    /// Check if the two matrices are equal, within `epsilon` range.
+    #[wasm_bindgen(js_name=almostEqual)]
+    pub fn almost_equal(&self, other: &Mat2f, epsilon: f32) -> bool {
+        self.0.almost_equal(other, epsilon)
     }
 }
@@ -1,25 +1,35 @@
-//! Basic 3 by 3 float matrices
+//! Basic 3 by 3 matrices
+use crate::mat::mat4::Mat4;
+use crate::scalar::{Float, Scalar};
 use crate::vec::vec2::Vec2;
 use crate::vec::vec3::Vec3;
+#[cfg(feature = "wasm")]
+use crate::{
+    mat::mat4::Mat4f,
+    vec::{vec2::Vec2f, vec3::Vec3f},
+};
+use core::mem::swap;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "alloc")]
 use serde_derive::{Deserialize, Serialize};
-use std::mem::swap;
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 /// 3 by 3 column major matrix
-#[wasm_bindgen(js_class=Mat3f)]
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
-pub struct Mat3f {
-    #[wasm_bindgen(skip)]
-    pub x_axis: [f32; 3],
-    #[wasm_bindgen(skip)]
-    pub y_axis: [f32; 3],
-    #[wasm_bindgen(skip)]
-    pub w_axis: [f32; 3],
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Mat3<T = f32> {
+    pub x_axis: [T; 3],
+    pub y_axis: [T; 3],
+    pub w_axis: [T; 3],
 }
 
-impl From<[[f32; 3]; 3]> for Mat3f {
-    fn from([x_axis, y_axis, w_axis]: [[f32; 3]; 3]) -> Self {
+pub type Mat3f = Mat3<f32>;
+pub type Mat3d = Mat3<f64>;
+pub type Mat3i = Mat3<i32>;
+
+impl<T> From<[[T; 3]; 3]> for Mat3<T> {
+    fn from([x_axis, y_axis, w_axis]: [[T; 3]; 3]) -> Self {
         Self {
             x_axis,
             y_axis,
@@ -28,114 +38,64 @@ impl From<[[f32; 3]; 3]> for Mat3f {
     }
 }
 
-#[wasm_bindgen(js_class=Mat3f)]
-impl Mat3f {
-    #[wasm_bindgen(constructor)]
+impl<T: Scalar> Mat3<T> {
     pub fn new() -> Self {
         Self::default()
     }
 
-    #[wasm_bindgen(js_name=xAxis)]
-    pub fn x_axis(&self) -> Vec3 {
+    pub fn x_axis(&self) -> Vec3<T> {
         self.x_axis.into()
     }
 
-    #[wasm_bindgen(js_name=yAxis)]
-    pub fn y_axis(&self) -> Vec3 {
+    pub fn y_axis(&self) -> Vec3<T> {
         self.y_axis.into()
     }
 
-    #[wasm_bindgen(js_name=wAxis)]
-    pub fn w_axis(&self) -> Vec3 {
+    pub fn w_axis(&self) -> Vec3<T> {
         self.w_axis.into()
     }
 
     /// Returns a flat list in column-major order
-    #[wasm_bindgen(js_name=asList)]
-    pub fn as_list(&self) -> Vec<f32> {
-        let mut v = Vec::with_capacity(9);
+    #[cfg(feature = "alloc")]
+    pub fn as_list(&self) -> alloc::vec::Vec<T> {
+        let mut v = alloc::vec::Vec::with_capacity(9);
         v.extend_from_slice(&self.x_axis);
         v.extend_from_slice(&self.y_axis);
         v.extend_from_slice(&self.w_axis);
         v
     }
 
-    /// Returns a new matrix which is the inverse of this.
-    ///
-    /// If `this` is not invertible then __null__ is returned.
-    #[wasm_bindgen]
-    pub fn inverted(&self) -> Option<Mat3f> {
-        let det = self.det();
-        if det == 0.0 {
-            return None;
-        }
-
-        let u = Vec3::from(self.x_axis);
-        let v = Vec3::from(self.y_axis);
-        let w = Vec3::from(self.w_axis);
-
-        let dev_inv = 1.0 / det;
-        let x_axis = v.cross(&w) * dev_inv;
-        let y_axis = w.cross(&u) * dev_inv;
-        let w_axis = u.cross(&v) * dev_inv;
-
-        let mat = Self {
-            x_axis: x_axis.into(),
-            y_axis: y_axis.into(),
-            w_axis: w_axis.into(),
-        }
-        .transposed();
-
-        Some(mat)
-    }
-
     /// Returns the identity matrix
-    #[wasm_bindgen]
-    pub fn identity() -> Mat3f {
-        Mat3f::scale(1.)
+    pub fn identity() -> Self {
+        Self::scale(T::ONE)
     }
 
     /// 2D scale transformation matrix.
-    #[wasm_bindgen]
-    pub fn scale(a: f32) -> Self {
+    pub fn scale(a: T) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
         Self {
             // note:
             // what you see is the transposed view of the actual matrix
-            x_axis: [a, 0., 0.],
-            y_axis: [0., a, 0.],
-            w_axis: [0., 0., 1.],
-        }
-    }
-
-    /// Returns a 2D rotation matrix, rotatig with `rads` radians counter-clockwise around the origin
-    #[wasm_bindgen]
-    pub fn rotation(rads: f32) -> Self {
-        let cos = rads.cos();
-        let sin = rads.sin();
-        Self {
-            // note:
-            // what you see is the transposed view of the actual matrix
-            x_axis: [cos, sin, 0.],
-            y_axis: [-sin, cos, 0.],
-            w_axis: [0., 0., 1.],
+            x_axis: [a, o, o],
+            y_axis: [o, a, o],
+            w_axis: [o, o, i],
         }
     }
 
     /// Creates a matrix for the given translation `t`
     /// Where `b = M*a` equals `a+t`
-    #[wasm_bindgen]
-    pub fn translate(Vec2 { x, y }: Vec2) -> Self {
+    pub fn translate(Vec2 { x, y }: Vec2<T>) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
         Self {
             // note:
             // what you see is the transposed view of the actual matrix
-            x_axis: [1., 0., 0.],
-            y_axis: [0., 1., 0.],
-            w_axis: [x, y, 1.],
+            x_axis: [i, o, o],
+            y_axis: [o, i, o],
+            w_axis: [x, y, i],
         }
     }
 
     /// Returns a new matrix that is the transponent of this
-    #[wasm_bindgen]
     pub fn transposed(&self) -> Self {
         Self {
             x_axis: [self.x_axis[0], self.y_axis[0], self.w_axis[0]],
@@ -145,37 +105,26 @@ impl Mat3f {
     }
 
     /// Swaps the matrices in place
-    #[wasm_bindgen]
-    pub fn swap(&mut self, other: &mut Mat3f) {
+    pub fn swap(&mut self, other: &mut Self) {
         swap(self, other);
     }
 
     /// Returns axis 0, 1 or 2. Panics if `col` is greater than 2.
-    #[wasm_bindgen]
-    pub fn axis(&self, col: usize) -> Vec3 {
-        let axis = match col {
-            0 => &self.x_axis,
-            1 => &self.y_axis,
-            2 => &self.w_axis,
-            _ => unreachable!(),
-        };
-        axis.clone().into()
+    pub fn axis(&self, col: usize) -> Vec3<T> {
+        (*self.axis_ref(col)).into()
     }
 
-    #[wasm_bindgen]
-    pub fn at(&self, col: usize, row: usize) -> f32 {
+    pub fn at(&self, col: usize, row: usize) -> T {
         self.axis_ref(col)[row]
     }
 
-    #[wasm_bindgen]
-    pub fn set(&mut self, col: usize, row: usize, val: f32) {
+    pub fn set(&mut self, col: usize, row: usize, val: T) {
         self.axis_mut(col)[row] = val;
     }
 
     /// `v*M` where `M` is self
-    #[wasm_bindgen(js_name=leftProd)]
-    pub fn left_prod(&self, v: &Vec3) -> Vec3 {
-        let mut res = [0.0; 3];
+    pub fn left_prod(&self, v: &Vec3<T>) -> Vec3<T> {
+        let mut res = [T::ZERO; 3];
         for c in 0..3 {
             res[c] += v[0] * self.at(c, 0);
             res[c] += v[1] * self.at(c, 1);
@@ -185,9 +134,8 @@ impl Mat3f {
     }
 
     /// `M*v` where `M` is self
-    #[wasm_bindgen(js_name=rightProd)]
-    pub fn right_prod(&self, v: &Vec3) -> Vec3 {
-        let mut res = [0.0; 3];
+    pub fn right_prod(&self, v: &Vec3<T>) -> Vec3<T> {
+        let mut res = [T::ZERO; 3];
         for r in 0..3 {
             res[r] += v[0] * self.at(0, r);
             res[r] += v[1] * self.at(1, r);
@@ -197,9 +145,8 @@ impl Mat3f {
     }
 
     /// Calculate `A*B=C` where `A` is self
-    #[wasm_bindgen(js_name=matMul)]
     #[allow(non_snake_case)]
-    pub fn mat_mul(&self, B: &Mat3f) -> Mat3f {
+    pub fn mat_mul(&self, B: &Self) -> Self {
         let mut C = Self::default();
         for c in 0..3 {
             for r in 0..3 {
@@ -213,7 +160,7 @@ impl Mat3f {
     }
 
     /// Calculate the determinant
-    pub fn det(&self) -> f32 {
+    pub fn det(&self) -> T {
         (self.x_axis[0]
             * det2(
                 [self.y_axis[1], self.y_axis[2]],
@@ -231,33 +178,7 @@ impl Mat3f {
                 ))
     }
 
-    /// Check if the two matrices are equal, within `epsilon` range.
-    #[wasm_bindgen(js_name=almostEqual)]
-    pub fn almost_equal(&self, other: &Mat3f, epsilon: f32) -> bool {
-        self.x_axis
-            .iter()
-            .zip(other.x_axis.iter())
-            .all(|(a, b)| (a - b).abs() < epsilon)
-            && self
-                .y_axis
-                .iter()
-                .zip(other.y_axis.iter())
-                .all(|(a, b)| (a - b).abs() < epsilon)
-            && self
-                .w_axis
-                .iter()
-                .zip(other.w_axis.iter())
-                .all(|(a, b)| (a - b).abs() < epsilon)
-    }
-}
-
-#[inline]
-fn det2([x1, y1]: [f32; 2], [x2, y2]: [f32; 2]) -> f32 {
-    x1 * y2 - x2 * y1
-}
-
-impl Mat3f {
-    pub fn axis_mut(&mut self, col: usize) -> &mut [f32; 3] {
+    pub fn axis_mut(&mut self, col: usize) -> &mut [T; 3] {
         match col {
             0 => &mut self.x_axis,
             1 => &mut self.y_axis,
@@ -266,11 +187,17 @@ impl Mat3f {
         }
     }
 
-    pub fn at_mut(&mut self, col: usize, row: usize) -> &mut f32 {
+    pub fn at_mut(&mut self, col: usize, row: usize) -> &mut T {
         &mut self.axis_mut(col)[row]
     }
 
-    pub fn axis_ref(&self, col: usize) -> &[f32; 3] {
+    /// Converts `this` to a 4 by 4 matrix, with `this` in the top left corner and the rest of
+    /// the identity
+    pub fn to_mat4(&self) -> Mat4<T> {
+        self.clone().into()
+    }
+
+    pub fn axis_ref(&self, col: usize) -> &[T; 3] {
         match col {
             0 => &self.x_axis,
             1 => &self.y_axis,
@@ -280,73 +207,140 @@ impl Mat3f {
     }
 }
 
-impl<'a> Mul<&'a Mat3f> for &'a Mat3f {
-    type Output = Mat3f;
+impl<T: Float> Mat3<T> {
+    /// Returns a new matrix which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Self> {
+        let det = self.det();
+        if det == T::ZERO {
+            return None;
+        }
+
+        let u = Vec3::from(self.x_axis);
+        let v = Vec3::from(self.y_axis);
+        let w = Vec3::from(self.w_axis);
+
+        let dev_inv = T::ONE / det;
+        let x_axis = v.cross(&w) * dev_inv;
+        let y_axis = w.cross(&u) * dev_inv;
+        let w_axis = u.cross(&v) * dev_inv;
 
-    fn mul(self, rhs: &'a Mat3f) -> Self::Output {
+        let mat = Self {
+            x_axis: x_axis.into(),
+            y_axis: y_axis.into(),
+            w_axis: w_axis.into(),
+        }
+        .transposed();
+
+        Some(mat)
+    }
+
+    /// Returns a 2D rotation matrix, rotatig with `rads` radians counter-clockwise around the origin
+    pub fn rotation(rads: T) -> Self {
+        let cos = rads.cos();
+        let sin = rads.sin();
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            // note:
+            // what you see is the transposed view of the actual matrix
+            x_axis: [cos, sin, o],
+            y_axis: [-sin, cos, o],
+            w_axis: [o, o, i],
+        }
+    }
+
+    /// Check if the two matrices are equal, within `epsilon` range.
+    pub fn almost_equal(&self, other: &Self, epsilon: T) -> bool {
+        self.x_axis
+            .iter()
+            .zip(other.x_axis.iter())
+            .all(|(a, b)| (*a - *b).abs() < epsilon)
+            && self
+                .y_axis
+                .iter()
+                .zip(other.y_axis.iter())
+                .all(|(a, b)| (*a - *b).abs() < epsilon)
+            && self
+                .w_axis
+                .iter()
+                .zip(other.w_axis.iter())
+                .all(|(a, b)| (*a - *b).abs() < epsilon)
+    }
+}
+
+#[inline]
+fn det2<T: Scalar>([x1, y1]: [T; 2], [x2, y2]: [T; 2]) -> T {
+    x1 * y2 - x2 * y1
+}
+
+impl<'a, T: Scalar> Mul<&'a Mat3<T>> for &'a Mat3<T> {
+    type Output = Mat3<T>;
+
+    fn mul(self, rhs: &'a Mat3<T>) -> Self::Output {
         self.mat_mul(rhs)
     }
 }
 
-impl<'a> Mul<&'a Mat3f> for Mat3f {
-    type Output = Mat3f;
+impl<'a, T: Scalar> Mul<&'a Mat3<T>> for Mat3<T> {
+    type Output = Mat3<T>;
 
-    fn mul(self, rhs: &'a Mat3f) -> Self::Output {
+    fn mul(self, rhs: &'a Mat3<T>) -> Self::Output {
         self.mat_mul(rhs)
     }
 }
 
-impl<'a> Mul<Mat3f> for &'a Mat3f {
-    type Output = Mat3f;
+impl<'a, T: Scalar> Mul<Mat3<T>> for &'a Mat3<T> {
+    type Output = Mat3<T>;
 
-    fn mul(self, rhs: Mat3f) -> Self::Output {
+    fn mul(self, rhs: Mat3<T>) -> Self::Output {
         self.mat_mul(&rhs)
     }
 }
 
-impl Mul<Mat3f> for Mat3f {
-    type Output = Mat3f;
+impl<T: Scalar> Mul<Mat3<T>> for Mat3<T> {
+    type Output = Mat3<T>;
 
-    fn mul(self, rhs: Mat3f) -> Self::Output {
+    fn mul(self, rhs: Mat3<T>) -> Self::Output {
         self.mat_mul(&rhs)
     }
 }
 
-impl Mul<f32> for Mat3f {
+impl<T: Scalar> Mul<T> for Mat3<T> {
     type Output = Self;
 
-    fn mul(mut self, rhs: f32) -> Self::Output {
+    fn mul(mut self, rhs: T) -> Self::Output {
         self *= rhs;
         self
     }
 }
 
-impl MulAssign<f32> for Mat3f {
-    fn mul_assign(&mut self, rhs: f32) {
+impl<T: Scalar> MulAssign<T> for Mat3<T> {
+    fn mul_assign(&mut self, rhs: T) {
         self.x_axis.iter_mut().for_each(|x| *x *= rhs);
         self.y_axis.iter_mut().for_each(|x| *x *= rhs);
         self.w_axis.iter_mut().for_each(|x| *x *= rhs);
     }
 }
 
-impl Div<f32> for Mat3f {
+impl<T: Scalar> Div<T> for Mat3<T> {
     type Output = Self;
 
-    fn div(mut self, rhs: f32) -> Self::Output {
+    fn div(mut self, rhs: T) -> Self::Output {
         self /= rhs;
         self
     }
 }
 
-impl DivAssign<f32> for Mat3f {
-    fn div_assign(&mut self, rhs: f32) {
+impl<T: Scalar> DivAssign<T> for Mat3<T> {
+    fn div_assign(&mut self, rhs: T) {
         self.x_axis.iter_mut().for_each(|x| *x /= rhs);
         self.y_axis.iter_mut().for_each(|x| *x /= rhs);
         self.w_axis.iter_mut().for_each(|x| *x /= rhs);
     }
 }
 
-impl Add<&Self> for Mat3f {
+impl<T: Scalar> Add<&Self> for Mat3<T> {
     type Output = Self;
 
     fn add(mut self, rhs: &Self) -> Self::Output {
@@ -355,24 +349,24 @@ impl Add<&Self> for Mat3f {
     }
 }
 
-impl AddAssign<&Self> for Mat3f {
+impl<T: Scalar> AddAssign<&Self> for Mat3<T> {
     fn add_assign(&mut self, rhs: &Self) {
         self.x_axis
             .iter_mut()
             .zip(rhs.x_axis.iter())
-            .for_each(|(a, b)| *a += b);
+            .for_each(|(a, b)| *a += *b);
         self.y_axis
             .iter_mut()
             .zip(rhs.y_axis.iter())
-            .for_each(|(a, b)| *a += b);
+            .for_each(|(a, b)| *a += *b);
         self.w_axis
             .iter_mut()
             .zip(rhs.w_axis.iter())
-            .for_each(|(a, b)| *a += b);
+            .for_each(|(a, b)| *a += *b);
     }
 }
 
-impl Sub<&Self> for Mat3f {
+impl<T: Scalar> Sub<&Self> for Mat3<T> {
     type Output = Self;
 
     fn sub(mut self, rhs: &Self) -> Self::Output {
@@ -381,19 +375,159 @@ impl Sub<&Self> for Mat3f {
     }
 }
 
-impl SubAssign<&Self> for Mat3f {
+impl<T: Scalar> SubAssign<&Self> for Mat3<T> {
     fn sub_assign(&mut self, rhs: &Self) {
         self.x_axis
             .iter_mut()
             .zip(rhs.x_axis.iter())
-            .for_each(|(a, b)| *a -= b);
+            .for_each(|(a, b)| *a -= *b);
         self.y_axis
             .iter_mut()
             .zip(rhs.y_axis.iter())
-            .for_each(|(a, b)| *a -= b);
+            .for_each(|(a, b)| *a -= *b);
         self.w_axis
             .iter_mut()
             .zip(rhs.w_axis.iter())
-            .for_each(|(a, b)| *a -= b);
+            .for_each(|(a, b)| *a -= *b);
+    }
+}
+
+/// `Mat3f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Mat3f)]
+#[derive(Debug, Clone, Default)]
+pub struct Mat3fJs(Mat3f);
+
+#[cfg(feature = "wasm")]
+impl From<Mat3f> for Mat3fJs {
+    fn from(mat: Mat3f) -> Self {
+        Self(mat)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Mat3fJs> for Mat3f {
+    fn from(Mat3fJs(mat): Mat3fJs) -> Self {
+        mat
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Mat3f, Mat3fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Mat3f)]
+impl Mat3fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self(Mat3::new())
+    }
+
+    #[wasm_bindgen(js_name=xAxis)]
+    pub fn x_axis(&self) -> Vec3f {
+        self.0.x_axis()
+    }
+
+    #[wasm_bindgen(js_name=yAxis)]
+    pub fn y_axis(&self) -> Vec3f {
+        self.0.y_axis()
+    }
+
+    #[wasm_bindgen(js_name=wAxis)]
+    pub fn w_axis(&self) -> Vec3f {
+        self.0.w_axis()
+    }
+
+    /// Returns a flat list in column-major order
+    #[wasm_bindgen(js_name=asList)]
+    pub fn as_list(&self) -> Vec<f32> {
+        self.0.as_list()
+    }
+
+    /// Returns a new matrix which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Mat3f> {
+        self.0.inverted()
+    }
+
+    /// Returns the identity matrix
+    pub fn identity() -> Mat3f {
+        Mat3::identity()
+    }
+
+    /// 2D scale transformation matrix.
+    pub fn scale(a: f32) -> Mat3f {
+        Mat3::scale(a)
+    }
+
+    /// Returns a 2D rotation matrix, rotatig with `rads` radians counter-clockwise around the origin
+    pub fn rotation(rads: f32) -> Mat3f {
+        Mat3::rotation(rads)
+    }
+
+    /// Creates a matrix for the given translation `t`
+    /// Where `b = M*a` equals `a+t`
+    pub fn translate(t: Vec2f) -> Mat3f {
+        Mat3::translate(t)
+    }
+
+    /// Returns a new matrix that is the transponent of this
+    pub fn transposed(&self) -> Mat3f {
+        self.0.transposed()
+    }
+
+    /// Swaps the matrices in place
+    pub fn swap(&mut self, other: &mut Mat3f) {
+        self.0.swap(other);
+    }
+
+    /// Returns axis 0, 1 or 2. Panics if `col` is greater than 2.
+    pub fn axis(&self, col: usize) -> Vec3f {
+        self.0.axis(col)
+    }
+
+    pub fn at(&self, col: usize, row: usize) -> f32 {
+        self.0.at(col, row)
+    }
+
+    pub fn set(&mut self, col: usize, row: usize, val: f32) {
+        self.0.set(col, row, val);
+    }
+
+    /// `v*M` where `M` is self
+    #[wasm_bindgen(js_name=leftProd)]
+    pub fn left_prod(&self, v: &Vec3f) -> Vec3f {
+        self.0.left_prod(v)
+    }
+
+    /// `M*v` where `M` is self
+    #[wasm_bindgen(js_name=rightProd)]
+    pub fn right_prod(&self, v: &Vec3f) -> Vec3f {
+        self.0.right_prod(v)
+    }
+
+    /// Calculate `A*B=C` where `A` is self
+    #[wasm_bindgen(js_name=matMul)]
+    pub fn mat_mul(&self, b: &Mat3f) -> Mat3f {
+        self.0.mat_mul(b)
+    }
+
+    /// Calculate the determinant
+    pub fn det(&self) -> f32 {
+        self.0.det()
+    }
+
+    /// Converts `this` to a 4 by 4 matrix, with `this` in the top left corner
+    #[wasm_bindgen(js_name=toMat4)]
+    pub fn to_mat4(&self) -> Mat4f {
+        self.0.to_mat4()
+    }
+
+    /// Check if the two matrices are equal, within `epsilon` range.
+    #[wasm_bindgen(js_name=almostEqual)]
+    pub fn almost_equal(&self, other: &Mat3f, epsilon: f32) -> bool {
+        self.0.almost_equal(other, epsilon)
     }
 }
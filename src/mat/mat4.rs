@@ -0,0 +1,624 @@
+//! Basic 4 by 4 matrices
+use crate::mat::mat3::Mat3;
+use crate::scalar::{Float, Scalar};
+use crate::vec::vec3::Vec3;
+use crate::vec::vec4::Vec4;
+#[cfg(feature = "wasm")]
+use crate::{
+    mat::mat3::Mat3f,
+    vec::{vec3::Vec3f, vec4::Vec4f},
+};
+use core::mem::swap;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// 4 by 4 column major matrix, transforming homogeneous 3d points
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Mat4<T = f32> {
+    pub x_axis: [T; 4],
+    pub y_axis: [T; 4],
+    pub z_axis: [T; 4],
+    pub w_axis: [T; 4],
+}
+
+pub type Mat4f = Mat4<f32>;
+pub type Mat4d = Mat4<f64>;
+pub type Mat4i = Mat4<i32>;
+
+impl<T> From<[[T; 4]; 4]> for Mat4<T> {
+    fn from([x_axis, y_axis, z_axis, w_axis]: [[T; 4]; 4]) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            z_axis,
+            w_axis,
+        }
+    }
+}
+
+/// Puts the 3 by 3 matrix in the top left corner, the rest is the identity
+impl<T: Scalar> From<Mat3<T>> for Mat4<T> {
+    fn from(mat: Mat3<T>) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        let [x, y, w] = [mat.x_axis, mat.y_axis, mat.w_axis];
+        Self {
+            x_axis: [x[0], x[1], x[2], o],
+            y_axis: [y[0], y[1], y[2], o],
+            z_axis: [w[0], w[1], w[2], o],
+            w_axis: [o, o, o, i],
+        }
+    }
+}
+
+impl<T: Scalar> Mat4<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts a 2D transformation, like the ones created by `Mat3f.translate` or
+    /// `Mat3f.rotation`, to the same transformation of the `z = 0` plane. The `z` coordinate is
+    /// left unchanged.
+    pub fn from_2d_transform(mat: &Mat3<T>) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        let [x, y, w] = [mat.x_axis, mat.y_axis, mat.w_axis];
+        Self {
+            x_axis: [x[0], x[1], o, x[2]],
+            y_axis: [y[0], y[1], o, y[2]],
+            z_axis: [o, o, i, o],
+            w_axis: [w[0], w[1], o, w[2]],
+        }
+    }
+
+    pub fn x_axis(&self) -> Vec4<T> {
+        self.x_axis.into()
+    }
+
+    pub fn y_axis(&self) -> Vec4<T> {
+        self.y_axis.into()
+    }
+
+    pub fn z_axis(&self) -> Vec4<T> {
+        self.z_axis.into()
+    }
+
+    pub fn w_axis(&self) -> Vec4<T> {
+        self.w_axis.into()
+    }
+
+    /// Returns a flat list in column-major order, as expected by WebGL
+    #[cfg(feature = "alloc")]
+    pub fn as_list(&self) -> alloc::vec::Vec<T> {
+        let mut v = alloc::vec::Vec::with_capacity(16);
+        v.extend_from_slice(&self.x_axis);
+        v.extend_from_slice(&self.y_axis);
+        v.extend_from_slice(&self.z_axis);
+        v.extend_from_slice(&self.w_axis);
+        v
+    }
+
+    /// Returns the identity matrix
+    pub fn identity() -> Self {
+        Self::scale(T::ONE)
+    }
+
+    /// 3D scale transformation matrix.
+    pub fn scale(a: T) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            // note:
+            // what you see is the transposed view of the actual matrix
+            x_axis: [a, o, o, o],
+            y_axis: [o, a, o, o],
+            z_axis: [o, o, a, o],
+            w_axis: [o, o, o, i],
+        }
+    }
+
+    /// Creates a matrix for the given translation `t`
+    /// Where `b = M*a` equals `a+t`
+    pub fn translate(Vec3 { x, y, z }: Vec3<T>) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            // note:
+            // what you see is the transposed view of the actual matrix
+            x_axis: [i, o, o, o],
+            y_axis: [o, i, o, o],
+            z_axis: [o, o, i, o],
+            w_axis: [x, y, z, i],
+        }
+    }
+
+    /// Returns a new matrix that is the transponent of this
+    pub fn transposed(&self) -> Self {
+        let mut res = Self::default();
+        for c in 0..4 {
+            for r in 0..4 {
+                *res.at_mut(c, r) = self.at(r, c);
+            }
+        }
+        res
+    }
+
+    /// Swaps the matrices in place
+    pub fn swap(&mut self, other: &mut Self) {
+        swap(self, other);
+    }
+
+    /// Returns axis 0, 1, 2 or 3. Panics if `col` is greater than 3.
+    pub fn axis(&self, col: usize) -> Vec4<T> {
+        (*self.axis_ref(col)).into()
+    }
+
+    pub fn at(&self, col: usize, row: usize) -> T {
+        self.axis_ref(col)[row]
+    }
+
+    pub fn set(&mut self, col: usize, row: usize, val: T) {
+        self.axis_mut(col)[row] = val;
+    }
+
+    /// `v*M` where `M` is self
+    pub fn left_prod(&self, v: &Vec4<T>) -> Vec4<T> {
+        let mut res = [T::ZERO; 4];
+        for (c, res) in res.iter_mut().enumerate() {
+            for r in 0..4 {
+                *res += v[r] * self.at(c, r);
+            }
+        }
+        res.into()
+    }
+
+    /// `M*v` where `M` is self
+    pub fn right_prod(&self, v: &Vec4<T>) -> Vec4<T> {
+        let mut res = [T::ZERO; 4];
+        for (r, res) in res.iter_mut().enumerate() {
+            for c in 0..4 {
+                *res += v[c] * self.at(c, r);
+            }
+        }
+        res.into()
+    }
+
+    /// Calculate `A*B=C` where `A` is self
+    #[allow(non_snake_case)]
+    pub fn mat_mul(&self, B: &Self) -> Self {
+        let mut C = Self::default();
+        for c in 0..4 {
+            for r in 0..4 {
+                let mut x = T::ZERO;
+                for k in 0..4 {
+                    x += self.at(k, r) * B.at(c, k);
+                }
+                *C.at_mut(c, r) = x;
+            }
+        }
+        C
+    }
+
+    /// Calculate the determinant
+    pub fn det(&self) -> T {
+        // expand along the first row
+        let mut det = T::ZERO;
+        for c in 0..4 {
+            let term = self.at(c, 0) * self.minor(c, 0);
+            if c % 2 == 0 {
+                det += term;
+            } else {
+                det -= term;
+            }
+        }
+        det
+    }
+
+    /// Determinant of the 3 by 3 matrix left after removing column `col` and row `row`
+    fn minor(&self, col: usize, row: usize) -> T {
+        let mut m = Mat3::default();
+        for (mc, c) in (0..4).filter(|c| *c != col).enumerate() {
+            for (mr, r) in (0..4).filter(|r| *r != row).enumerate() {
+                *m.at_mut(mc, mr) = self.at(c, r);
+            }
+        }
+        m.det()
+    }
+
+    pub fn axis_mut(&mut self, col: usize) -> &mut [T; 4] {
+        match col {
+            0 => &mut self.x_axis,
+            1 => &mut self.y_axis,
+            2 => &mut self.z_axis,
+            3 => &mut self.w_axis,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn at_mut(&mut self, col: usize, row: usize) -> &mut T {
+        &mut self.axis_mut(col)[row]
+    }
+
+    pub fn axis_ref(&self, col: usize) -> &[T; 4] {
+        match col {
+            0 => &self.x_axis,
+            1 => &self.y_axis,
+            2 => &self.z_axis,
+            3 => &self.w_axis,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<T: Float> Mat4<T> {
+    /// Returns a new matrix which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Self> {
+        let det = self.det();
+        if det == T::ZERO {
+            return None;
+        }
+
+        let dev_inv = T::ONE / det;
+        // the inverse is the transposed matrix of cofactors divided by the determinant
+        let mut mat = Self::default();
+        for c in 0..4 {
+            for r in 0..4 {
+                let cofactor = self.minor(r, c) * dev_inv;
+                *mat.at_mut(c, r) = if (c + r) % 2 == 0 {
+                    cofactor
+                } else {
+                    -cofactor
+                };
+            }
+        }
+
+        Some(mat)
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around the
+    /// X axis
+    pub fn rotation_x(rads: T) -> Self {
+        let (cos, sin) = (rads.cos(), rads.sin());
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            x_axis: [i, o, o, o],
+            y_axis: [o, cos, sin, o],
+            z_axis: [o, -sin, cos, o],
+            w_axis: [o, o, o, i],
+        }
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around the
+    /// Y axis
+    pub fn rotation_y(rads: T) -> Self {
+        let (cos, sin) = (rads.cos(), rads.sin());
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            x_axis: [cos, o, -sin, o],
+            y_axis: [o, i, o, o],
+            z_axis: [sin, o, cos, o],
+            w_axis: [o, o, o, i],
+        }
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around the
+    /// Z axis
+    pub fn rotation_z(rads: T) -> Self {
+        let (cos, sin) = (rads.cos(), rads.sin());
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            x_axis: [cos, sin, o, o],
+            y_axis: [-sin, cos, o, o],
+            z_axis: [o, o, i, o],
+            w_axis: [o, o, o, i],
+        }
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around
+    /// `axis`. The axis does not have to be normalized.
+    pub fn rotation(axis: &Vec3<T>, rads: T) -> Self {
+        let Vec3 { x, y, z } = axis.normalized();
+        let (cos, sin) = (rads.cos(), rads.sin());
+        let t = T::ONE - cos;
+        let (o, i) = (T::ZERO, T::ONE);
+        Self {
+            x_axis: [cos + x * x * t, y * x * t + z * sin, z * x * t - y * sin, o],
+            y_axis: [x * y * t - z * sin, cos + y * y * t, z * y * t + x * sin, o],
+            z_axis: [x * z * t + y * sin, y * z * t - x * sin, cos + z * z * t, o],
+            w_axis: [o, o, o, i],
+        }
+    }
+
+    /// Check if the two matrices are equal, within `epsilon` range.
+    pub fn almost_equal(&self, other: &Self, epsilon: T) -> bool {
+        (0..4).all(|c| {
+            self.axis_ref(c)
+                .iter()
+                .zip(other.axis_ref(c).iter())
+                .all(|(a, b)| (*a - *b).abs() < epsilon)
+        })
+    }
+}
+
+impl<'a, T: Scalar> Mul<&'a Mat4<T>> for &'a Mat4<T> {
+    type Output = Mat4<T>;
+
+    fn mul(self, rhs: &'a Mat4<T>) -> Self::Output {
+        self.mat_mul(rhs)
+    }
+}
+
+impl<'a, T: Scalar> Mul<&'a Mat4<T>> for Mat4<T> {
+    type Output = Mat4<T>;
+
+    fn mul(self, rhs: &'a Mat4<T>) -> Self::Output {
+        self.mat_mul(rhs)
+    }
+}
+
+impl<T: Scalar> Mul<Mat4<T>> for &Mat4<T> {
+    type Output = Mat4<T>;
+
+    fn mul(self, rhs: Mat4<T>) -> Self::Output {
+        self.mat_mul(&rhs)
+    }
+}
+
+impl<T: Scalar> Mul<Mat4<T>> for Mat4<T> {
+    type Output = Mat4<T>;
+
+    fn mul(self, rhs: Mat4<T>) -> Self::Output {
+        self.mat_mul(&rhs)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Mat4<T> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: T) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<T: Scalar> MulAssign<T> for Mat4<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        for c in 0..4 {
+            self.axis_mut(c).iter_mut().for_each(|x| *x *= rhs);
+        }
+    }
+}
+
+impl<T: Scalar> Div<T> for Mat4<T> {
+    type Output = Self;
+
+    fn div(mut self, rhs: T) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+
+impl<T: Scalar> DivAssign<T> for Mat4<T> {
+    fn div_assign(&mut self, rhs: T) {
+        for c in 0..4 {
+            self.axis_mut(c).iter_mut().for_each(|x| *x /= rhs);
+        }
+    }
+}
+
+impl<T: Scalar> Add<&Self> for Mat4<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: &Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: Scalar> AddAssign<&Self> for Mat4<T> {
+    fn add_assign(&mut self, rhs: &Self) {
+        for c in 0..4 {
+            self.axis_mut(c)
+                .iter_mut()
+                .zip(rhs.axis_ref(c).iter())
+                .for_each(|(a, b)| *a += *b);
+        }
+    }
+}
+
+impl<T: Scalar> Sub<&Self> for Mat4<T> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: &Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T: Scalar> SubAssign<&Self> for Mat4<T> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        for c in 0..4 {
+            self.axis_mut(c)
+                .iter_mut()
+                .zip(rhs.axis_ref(c).iter())
+                .for_each(|(a, b)| *a -= *b);
+        }
+    }
+}
+
+/// `Mat4f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Mat4f)]
+#[derive(Debug, Clone, Default)]
+pub struct Mat4fJs(Mat4f);
+
+#[cfg(feature = "wasm")]
+impl From<Mat4f> for Mat4fJs {
+    fn from(mat: Mat4f) -> Self {
+        Self(mat)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Mat4fJs> for Mat4f {
+    fn from(Mat4fJs(mat): Mat4fJs) -> Self {
+        mat
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Mat4f, Mat4fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Mat4f)]
+impl Mat4fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self(Mat4::new())
+    }
+
+    /// Puts the 3 by 3 matrix in the top left corner, the rest is the identity
+    #[wasm_bindgen(js_name=fromMat3)]
+    pub fn from_mat3(mat: &Mat3f) -> Mat4f {
+        mat.to_mat4()
+    }
+
+    /// Converts a 2D transformation, like the ones created by `Mat3f.translate` or
+    /// `Mat3f.rotation`, to the same transformation of the `z = 0` plane. The `z` coordinate is
+    /// left unchanged.
+    #[wasm_bindgen(js_name=from2dTransform)]
+    pub fn from_2d_transform(mat: &Mat3f) -> Mat4f {
+        Mat4::from_2d_transform(mat)
+    }
+
+    #[wasm_bindgen(js_name=xAxis)]
+    pub fn x_axis(&self) -> Vec4f {
+        self.0.x_axis()
+    }
+
+    #[wasm_bindgen(js_name=yAxis)]
+    pub fn y_axis(&self) -> Vec4f {
+        self.0.y_axis()
+    }
+
+    #[wasm_bindgen(js_name=zAxis)]
+    pub fn z_axis(&self) -> Vec4f {
+        self.0.z_axis()
+    }
+
+    #[wasm_bindgen(js_name=wAxis)]
+    pub fn w_axis(&self) -> Vec4f {
+        self.0.w_axis()
+    }
+
+    /// Returns a flat list in column-major order, as expected by WebGL
+    #[wasm_bindgen(js_name=asList)]
+    pub fn as_list(&self) -> Vec<f32> {
+        self.0.as_list()
+    }
+
+    /// Returns a new matrix which is the inverse of this.
+    ///
+    /// If `this` is not invertible then __null__ is returned.
+    pub fn inverted(&self) -> Option<Mat4f> {
+        self.0.inverted()
+    }
+
+    /// Returns the identity matrix
+    pub fn identity() -> Mat4f {
+        Mat4::identity()
+    }
+
+    /// 3D scale transformation matrix.
+    pub fn scale(a: f32) -> Mat4f {
+        Mat4::scale(a)
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around the
+    /// X axis
+    #[wasm_bindgen(js_name=rotationX)]
+    pub fn rotation_x(rads: f32) -> Mat4f {
+        Mat4::rotation_x(rads)
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around the
+    /// Y axis
+    #[wasm_bindgen(js_name=rotationY)]
+    pub fn rotation_y(rads: f32) -> Mat4f {
+        Mat4::rotation_y(rads)
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around the
+    /// Z axis
+    #[wasm_bindgen(js_name=rotationZ)]
+    pub fn rotation_z(rads: f32) -> Mat4f {
+        Mat4::rotation_z(rads)
+    }
+
+    /// Returns a 3D rotation matrix, rotating with `rads` radians counter-clockwise around
+    /// `axis`. The axis does not have to be normalized.
+    pub fn rotation(axis: &Vec3f, rads: f32) -> Mat4f {
+        Mat4::rotation(axis, rads)
+    }
+
+    /// Creates a matrix for the given translation `t`
+    /// Where `b = M*a` equals `a+t`
+    pub fn translate(t: Vec3f) -> Mat4f {
+        Mat4::translate(t)
+    }
+
+    /// Returns a new matrix that is the transponent of this
+    pub fn transposed(&self) -> Mat4f {
+        self.0.transposed()
+    }
+
+    /// Swaps the matrices in place
+    pub fn swap(&mut self, other: &mut Mat4f) {
+        self.0.swap(other);
+    }
+
+    /// Returns axis 0, 1, 2 or 3. Panics if `col` is greater than 3.
+    pub fn axis(&self, col: usize) -> Vec4f {
+        self.0.axis(col)
+    }
+
+    pub fn at(&self, col: usize, row: usize) -> f32 {
+        self.0.at(col, row)
+    }
+
+    pub fn set(&mut self, col: usize, row: usize, val: f32) {
+        self.0.set(col, row, val);
+    }
+
+    /// `v*M` where `M` is self
+    #[wasm_bindgen(js_name=leftProd)]
+    pub fn left_prod(&self, v: &Vec4f) -> Vec4f {
+        self.0.left_prod(v)
+    }
+
+    /// `M*v` where `M` is self
+    #[wasm_bindgen(js_name=rightProd)]
+    pub fn right_prod(&self, v: &Vec4f) -> Vec4f {
+        self.0.right_prod(v)
+    }
+
+    /// Calculate `A*B=C` where `A` is self
+    #[wasm_bindgen(js_name=matMul)]
+    pub fn mat_mul(&self, b: &Mat4f) -> Mat4f {
+        self.0.mat_mul(b)
+    }
+
+    /// Calculate the determinant
+    pub fn det(&self) -> f32 {
+        self.0.det()
+    }
+
+    /// Check if the two matrices are equal, within `epsilon` range.
+    #[wasm_bindgen(js_name=almostEqual)]
+    pub fn almost_equal(&self, other: &Mat4f, epsilon: f32) -> bool {
+        self.0.almost_equal(other, epsilon)
+    }
+}
@@ -1,3 +1,7 @@
 //! Basic fix sized matrices
+pub mod affine2;
+pub mod decompose;
 pub mod mat2;
 pub mod mat3;
+pub mod mat4;
+pub mod projection;
@@ -0,0 +1,210 @@
+//! Camera and projection matrices
+//!
+//! The camera space is right handed, the camera looks towards negative Z with positive Y up, as
+//! in WebGL.
+use crate::mat::mat4::Mat4;
+#[cfg(feature = "wasm")]
+use crate::mat::mat4::{Mat4f, Mat4fJs};
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+#[cfg(feature = "wasm")]
+use crate::vec::vec2::Vec2f;
+use crate::vec::vec3::Vec3;
+#[cfg(feature = "wasm")]
+use crate::vec::vec3::Vec3f;
+use crate::vec::vec4::Vec4;
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Depth range of the clip space
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub enum ClipDepth {
+    /// Depth goes from -1 at the near plane to 1 at the far plane, as in WebGL and OpenGL
+    WebGl = 0,
+    /// Depth goes from 0 at the near plane to 1 at the far plane, as in WebGPU, Vulkan and
+    /// Direct3D
+    WebGpu = 1,
+}
+
+/// Half-line from `origin` in the unit length `direction`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Ray3<T = f32> {
+    pub origin: Vec3<T>,
+    pub direction: Vec3<T>,
+}
+
+pub type Ray3f = Ray3<f32>;
+
+impl<T: Float> Ray3<T> {
+    /// Returns the point `t` units along the ray
+    pub fn at(&self, t: T) -> Vec3<T> {
+        self.origin + self.direction * t
+    }
+}
+
+impl<T: Float> Mat4<T> {
+    /// Orthographic projection of the box between the `left`, `right`, `bottom`, `top` planes
+    /// and `near`, `far` units in front of the camera
+    pub fn ortho(left: T, right: T, bottom: T, top: T, near: T, far: T, depth: ClipDepth) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        let two = i + i;
+        let lr = i / (left - right);
+        let bt = i / (bottom - top);
+        let nf = i / (near - far);
+        let (z, w) = match depth {
+            ClipDepth::WebGl => (two * nf, (far + near) * nf),
+            ClipDepth::WebGpu => (nf, near * nf),
+        };
+        Self {
+            x_axis: [-two * lr, o, o, o],
+            y_axis: [o, -two * bt, o, o],
+            z_axis: [o, o, z, o],
+            w_axis: [(left + right) * lr, (top + bottom) * bt, w, i],
+        }
+    }
+
+    /// Perspective projection
+    ///
+    /// `fov_y` is the vertical field of view in radians, `aspect` is the width of the viewport
+    /// divided by its height, `near` and `far` are the distances of the clipping planes.
+    pub fn perspective(fov_y: T, aspect: T, near: T, far: T, depth: ClipDepth) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        let two = i + i;
+        let f = i / (fov_y / two).tan();
+        let nf = i / (near - far);
+        let (z, w) = match depth {
+            ClipDepth::WebGl => ((far + near) * nf, two * far * near * nf),
+            ClipDepth::WebGpu => (far * nf, far * near * nf),
+        };
+        Self {
+            x_axis: [f / aspect, o, o, o],
+            y_axis: [o, f, o, o],
+            z_axis: [o, o, z, -i],
+            w_axis: [o, o, w, o],
+        }
+    }
+
+    /// View matrix of a camera at `eye` looking at `target`. `up` must not be parallel to the
+    /// viewing direction.
+    pub fn look_at(eye: &Vec3<T>, target: &Vec3<T>, up: &Vec3<T>) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        let z = (*eye - *target).normalized();
+        let x = up.cross(&z).normalized();
+        let y = z.cross(&x);
+        Self {
+            x_axis: [x.x, y.x, z.x, o],
+            y_axis: [x.y, y.y, z.y, o],
+            z_axis: [x.z, y.z, z.z, o],
+            w_axis: [-x.dot(eye), -y.dot(eye), -z.dot(eye), i],
+        }
+    }
+
+    /// Returns the ray from the near plane through the `screen` point, where `this` is the
+    /// projection matrix multiplied by the view matrix.
+    ///
+    /// `screen` is in pixels from the top left corner of the viewport of `viewport` size.
+    ///
+    /// Returns __null__ if `this` is not invertible.
+    pub fn unproject(
+        &self,
+        screen: Vec2<T>,
+        viewport: Vec2<T>,
+        depth: ClipDepth,
+    ) -> Option<Ray3<T>> {
+        let i = T::ONE;
+        let two = i + i;
+        let inv = self.inverted()?;
+        let x = two * screen.x / viewport.x - i;
+        let y = i - two * screen.y / viewport.y;
+        let near = match depth {
+            ClipDepth::WebGl => -i,
+            ClipDepth::WebGpu => T::ZERO,
+        };
+        let near = Vec3::from_homogeneous(inv.right_prod(&Vec4::new(x, y, near, i)));
+        let far = Vec3::from_homogeneous(inv.right_prod(&Vec4::new(x, y, i, i)));
+        Some(Ray3 {
+            origin: near,
+            direction: (far - near).normalized(),
+        })
+    }
+}
+
+/// `Ray3f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Ray3f)]
+#[derive(Debug, Clone, Copy)]
+pub struct Ray3fJs {
+    pub origin: Vec3f,
+    pub direction: Vec3f,
+}
+
+#[cfg(feature = "wasm")]
+impl From<Ray3f> for Ray3fJs {
+    fn from(Ray3 { origin, direction }: Ray3f) -> Self {
+        Self { origin, direction }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Ray3f)]
+impl Ray3fJs {
+    /// Returns the point `t` units along the ray
+    pub fn at(&self, t: f32) -> Vec3f {
+        Ray3 {
+            origin: self.origin,
+            direction: self.direction,
+        }
+        .at(t)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Mat4f)]
+impl Mat4fJs {
+    /// Orthographic projection of the box between the `left`, `right`, `bottom`, `top` planes
+    /// and `near`, `far` units in front of the camera
+    pub fn ortho(
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        near: f32,
+        far: f32,
+        depth: ClipDepth,
+    ) -> Mat4f {
+        Mat4::ortho(left, right, bottom, top, near, far, depth)
+    }
+
+    /// Perspective projection
+    ///
+    /// `fovY` is the vertical field of view in radians, `aspect` is the width of the viewport
+    /// divided by its height, `near` and `far` are the distances of the clipping planes.
+    pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32, depth: ClipDepth) -> Mat4f {
+        Mat4::perspective(fov_y, aspect, near, far, depth)
+    }
+
+    /// View matrix of a camera at `eye` looking at `target`. `up` must not be parallel to the
+    /// viewing direction.
+    #[wasm_bindgen(js_name=lookAt)]
+    pub fn look_at(eye: &Vec3f, target: &Vec3f, up: &Vec3f) -> Mat4f {
+        Mat4::look_at(eye, target, up)
+    }
+
+    /// Returns the ray from the near plane through the `screen` point, where `this` is the
+    /// projection matrix multiplied by the view matrix.
+    ///
+    /// `screen` is in pixels from the top left corner of the viewport of `viewport` size.
+    ///
+    /// Returns __null__ if `this` is not invertible.
+    pub fn unproject(&self, screen: Vec2f, viewport: Vec2f, depth: ClipDepth) -> Option<Ray3fJs> {
+        Mat4f::from(self.clone())
+            .unproject(screen, viewport, depth)
+            .map(Ray3fJs::from)
+    }
+}
@@ -0,0 +1,448 @@
+//! Quaternions for 3D rotations
+use crate::mat::mat3::Mat3;
+#[cfg(feature = "wasm")]
+use crate::mat::mat3::Mat3f;
+use crate::scalar::{Float, Scalar};
+use crate::vec::vec3::Vec3;
+#[cfg(feature = "wasm")]
+use crate::vec::vec3::Vec3f;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Quaternion `w + xi + yj + zk`
+///
+/// Rotations are represented by unit length quaternions, `q` and `-q` are the same rotation.
+/// `a * b` is the rotation `b` followed by `a`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Quat<T = f32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+pub type Quatf = Quat<f32>;
+pub type Quatd = Quat<f64>;
+
+impl<T: Scalar> Default for Quat<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T: Scalar> Quat<T> {
+    pub fn new(x: T, y: T, z: T, w: T) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// The rotation by 0 radians
+    pub fn identity() -> Self {
+        Self::new(T::ZERO, T::ZERO, T::ZERO, T::ONE)
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Squared length of this quaternion
+    pub fn len_sq(&self) -> T {
+        self.dot(self)
+    }
+
+    /// The `x, y, z` part
+    pub fn vector(&self) -> Vec3<T> {
+        Vec3::new(self.x, self.y, self.z)
+    }
+}
+
+impl<T: Float> Quat<T> {
+    /// Rotation with `rads` radians counter-clockwise around `axis`, looking from the tip of
+    /// `axis` towards the origin. `axis` does not have to be normalized.
+    pub fn from_axis_angle(axis: &Vec3<T>, rads: T) -> Self {
+        let half = rads / (T::ONE + T::ONE);
+        let Vec3 { x, y, z } = axis.normalized() * half.sin();
+        Self::new(x, y, z, half.cos())
+    }
+
+    /// Rotation around the `X` axis by `x` radians, then around `Y` by `y`, then around `Z` by `z`
+    pub fn from_euler(x: T, y: T, z: T) -> Self {
+        let o = T::ZERO;
+        let i = T::ONE;
+        Self::from_axis_angle(&Vec3::new(o, o, i), z)
+            * Self::from_axis_angle(&Vec3::new(o, i, o), y)
+            * Self::from_axis_angle(&Vec3::new(i, o, o), x)
+    }
+
+    /// Rotation of the orthonormal `mat`, with the `x_axis`, `y_axis` and `w_axis` columns as the
+    /// images of the `X`, `Y` and `Z` axes
+    pub fn from_mat3(mat: &Mat3<T>) -> Self {
+        let two = T::ONE + T::ONE;
+        let four = two + two;
+        let m = |row: usize, col: usize| mat.at(col, row);
+        let trace = m(0, 0) + m(1, 1) + m(2, 2);
+        // pick the largest of the 4 components to divide with, for numerical stability
+        if trace > T::ZERO {
+            let s = (trace + T::ONE).sqrt() * two;
+            Self::new(
+                (m(2, 1) - m(1, 2)) / s,
+                (m(0, 2) - m(2, 0)) / s,
+                (m(1, 0) - m(0, 1)) / s,
+                s / four,
+            )
+        } else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+            let s = (T::ONE + m(0, 0) - m(1, 1) - m(2, 2)).sqrt() * two;
+            Self::new(
+                s / four,
+                (m(0, 1) + m(1, 0)) / s,
+                (m(0, 2) + m(2, 0)) / s,
+                (m(2, 1) - m(1, 2)) / s,
+            )
+        } else if m(1, 1) > m(2, 2) {
+            let s = (T::ONE + m(1, 1) - m(0, 0) - m(2, 2)).sqrt() * two;
+            Self::new(
+                (m(0, 1) + m(1, 0)) / s,
+                s / four,
+                (m(1, 2) + m(2, 1)) / s,
+                (m(0, 2) - m(2, 0)) / s,
+            )
+        } else {
+            let s = (T::ONE + m(2, 2) - m(0, 0) - m(1, 1)).sqrt() * two;
+            Self::new(
+                (m(0, 2) + m(2, 0)) / s,
+                (m(1, 2) + m(2, 1)) / s,
+                s / four,
+                (m(1, 0) - m(0, 1)) / s,
+            )
+        }
+    }
+
+    /// Returns the 3 by 3 rotation matrix of this unit quaternion
+    pub fn to_mat3(&self) -> Mat3<T> {
+        let i = T::ONE;
+        let two = i + i;
+        let Self { x, y, z, w } = *self;
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (w * x, w * y, w * z);
+        Mat3 {
+            x_axis: [i - two * (yy + zz), two * (xy + wz), two * (xz - wy)],
+            y_axis: [two * (xy - wz), i - two * (xx + zz), two * (yz + wx)],
+            w_axis: [two * (xz + wy), two * (yz - wx), i - two * (xx + yy)],
+        }
+    }
+
+    /// Length of this quaternion
+    pub fn len(&self) -> T {
+        self.len_sq().sqrt()
+    }
+
+    /// Returns a new quaternion with the same direction but a length of 1
+    pub fn normalized(&self) -> Self {
+        *self * (T::ONE / self.len())
+    }
+
+    /// Negates the vector part. For unit quaternions this is the inverse rotation.
+    pub fn conjugate(&self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Returns the multiplicative inverse.
+    ///
+    /// If `this` is zero then __null__ is returned.
+    pub fn inverse(&self) -> Option<Self> {
+        let len_sq = self.len_sq();
+        if len_sq == T::ZERO {
+            return None;
+        }
+        Some(self.conjugate() * (T::ONE / len_sq))
+    }
+
+    /// Rotates `v` by this unit quaternion
+    pub fn rotate(&self, v: &Vec3<T>) -> Vec3<T> {
+        let u = self.vector();
+        let t = u.cross(v) * (T::ONE + T::ONE);
+        *v + t * self.w + u.cross(&t)
+    }
+
+    /// Normalized linear interpolation along the shorter arc
+    ///
+    /// Cheaper than `slerp` but the angular speed is not constant.
+    pub fn nlerp(&self, other: &Self, t: T) -> Self {
+        let other = if self.dot(other) < T::ZERO {
+            -*other
+        } else {
+            *other
+        };
+        (*self * (T::ONE - t) + other * t).normalized()
+    }
+
+    /// Spherical linear interpolation of unit quaternions along the shorter arc, rotating with
+    /// constant angular speed
+    pub fn slerp(&self, other: &Self, t: T) -> Self {
+        let mut cos = self.dot(other);
+        let mut other = *other;
+        if cos < T::ZERO {
+            cos = -cos;
+            other = -other;
+        }
+        // rounding can push the dot product of unit quaternions above 1
+        if cos > T::ONE {
+            cos = T::ONE;
+        }
+        let angle = cos.acos();
+        let sin = angle.sin();
+        if sin <= T::EPSILON.sqrt() {
+            // the quaternions are too close for the division
+            return self.nlerp(&other, t);
+        }
+        let a = ((T::ONE - t) * angle).sin() / sin;
+        let b = (t * angle).sin() / sin;
+        *self * a + other * b
+    }
+
+    /// Check if the two quaternions are equal, within `epsilon` range.
+    ///
+    /// Compares the components, `q` and `-q` are not considered equal.
+    pub fn almost_equal(&self, other: &Self, epsilon: T) -> bool {
+        (self.x - other.x).abs() < epsilon
+            && (self.y - other.y).abs() < epsilon
+            && (self.z - other.z).abs() < epsilon
+            && (self.w - other.w).abs() < epsilon
+    }
+}
+
+impl<T> From<Quat<T>> for [T; 4] {
+    fn from(q: Quat<T>) -> Self {
+        [q.x, q.y, q.z, q.w]
+    }
+}
+
+impl<T> From<[T; 4]> for Quat<T> {
+    fn from([x, y, z, w]: [T; 4]) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+impl<T: Float> Neg for Quat<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+impl<T: Scalar> AddAssign for Quat<T> {
+    fn add_assign(&mut self, q: Self) {
+        self.x += q.x;
+        self.y += q.y;
+        self.z += q.z;
+        self.w += q.w;
+    }
+}
+
+impl<T: Scalar> Add for Quat<T> {
+    type Output = Self;
+
+    fn add(mut self, q: Self) -> Self {
+        self += q;
+        self
+    }
+}
+
+impl<T: Scalar> SubAssign for Quat<T> {
+    fn sub_assign(&mut self, q: Self) {
+        self.x -= q.x;
+        self.y -= q.y;
+        self.z -= q.z;
+        self.w -= q.w;
+    }
+}
+
+impl<T: Scalar> Sub for Quat<T> {
+    type Output = Self;
+
+    fn sub(mut self, q: Self) -> Self {
+        self -= q;
+        self
+    }
+}
+
+impl<T: Scalar> MulAssign<T> for Quat<T> {
+    fn mul_assign(&mut self, a: T) {
+        self.x *= a;
+        self.y *= a;
+        self.z *= a;
+        self.w *= a;
+    }
+}
+
+impl<T: Scalar> Mul<T> for Quat<T> {
+    type Output = Self;
+
+    fn mul(mut self, a: T) -> Self {
+        self *= a;
+        self
+    }
+}
+
+/// Hamilton product, the rotation `b` followed by `self`
+impl<T: Scalar> Mul<Quat<T>> for Quat<T> {
+    type Output = Self;
+
+    fn mul(self, b: Self) -> Self {
+        let a = self;
+        Self {
+            x: a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+            y: a.w * b.y + a.y * b.w + a.z * b.x - a.x * b.z,
+            z: a.w * b.z + a.z * b.w + a.x * b.y - a.y * b.x,
+            w: a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+        }
+    }
+}
+
+impl<T: Scalar> MulAssign<Quat<T>> for Quat<T> {
+    fn mul_assign(&mut self, b: Self) {
+        *self = *self * b;
+    }
+}
+
+impl<T: Float> Mul<Vec3<T>> for Quat<T> {
+    type Output = Vec3<T>;
+
+    fn mul(self, v: Vec3<T>) -> Vec3<T> {
+        self.rotate(&v)
+    }
+}
+
+/// `Quatf` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Quatf, inspectable)]
+#[derive(Debug, Clone, Copy)]
+pub struct QuatfJs {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+#[cfg(feature = "wasm")]
+impl From<Quatf> for QuatfJs {
+    fn from(Quat { x, y, z, w }: Quatf) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<QuatfJs> for Quatf {
+    fn from(QuatfJs { x, y, z, w }: QuatfJs) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Quatf, QuatfJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Quatf)]
+impl QuatfJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quat::new(x, y, z, w).into()
+    }
+
+    /// The rotation by 0 radians
+    pub fn identity() -> Quatf {
+        Quat::identity()
+    }
+
+    /// Rotation with `rads` radians counter-clockwise around `axis`, looking from the tip of
+    /// `axis` towards the origin. `axis` does not have to be normalized.
+    #[wasm_bindgen(js_name=fromAxisAngle)]
+    pub fn from_axis_angle(axis: &Vec3f, rads: f32) -> Quatf {
+        Quat::from_axis_angle(axis, rads)
+    }
+
+    /// Rotation around the `X` axis by `x` radians, then around `Y` by `y`, then around `Z` by `z`
+    #[wasm_bindgen(js_name=fromEuler)]
+    pub fn from_euler(x: f32, y: f32, z: f32) -> Quatf {
+        Quat::from_euler(x, y, z)
+    }
+
+    /// Rotation of the orthonormal `mat`
+    #[wasm_bindgen(js_name=fromMat3)]
+    pub fn from_mat3(mat: &Mat3f) -> Quatf {
+        Quat::from_mat3(mat)
+    }
+
+    /// Returns the 3 by 3 rotation matrix of this unit quaternion
+    #[wasm_bindgen(js_name=toMat3)]
+    pub fn to_mat3(&self) -> Mat3f {
+        Quatf::from(*self).to_mat3()
+    }
+
+    pub fn dot(&self, other: &Quatf) -> f32 {
+        Quatf::from(*self).dot(other)
+    }
+
+    /// Hamilton product, the rotation `other` followed by `this`
+    pub fn mul(&self, other: &Quatf) -> Quatf {
+        Quatf::from(*self) * *other
+    }
+
+    /// Squared length of this quaternion
+    #[wasm_bindgen(js_name=lenSq)]
+    pub fn len_sq(&self) -> f32 {
+        Quatf::from(*self).len_sq()
+    }
+
+    /// Length of this quaternion
+    pub fn len(&self) -> f32 {
+        Quatf::from(*self).len()
+    }
+
+    /// Returns a new quaternion with the same direction but a length of 1
+    pub fn normalized(&self) -> Quatf {
+        Quatf::from(*self).normalized()
+    }
+
+    /// Negates the vector part. For unit quaternions this is the inverse rotation.
+    pub fn conjugate(&self) -> Quatf {
+        Quatf::from(*self).conjugate()
+    }
+
+    /// Returns the multiplicative inverse.
+    ///
+    /// If `this` is zero then __null__ is returned.
+    pub fn inverse(&self) -> Option<Quatf> {
+        Quatf::from(*self).inverse()
+    }
+
+    /// Rotates `v` by this unit quaternion
+    pub fn rotate(&self, v: &Vec3f) -> Vec3f {
+        Quatf::from(*self).rotate(v)
+    }
+
+    /// Normalized linear interpolation along the shorter arc
+    ///
+    /// Cheaper than `slerp` but the angular speed is not constant.
+    pub fn nlerp(&self, other: &Quatf, t: f32) -> Quatf {
+        Quatf::from(*self).nlerp(other, t)
+    }
+
+    /// Spherical linear interpolation of unit quaternions along the shorter arc, rotating with
+    /// constant angular speed
+    pub fn slerp(&self, other: &Quatf, t: f32) -> Quatf {
+        Quatf::from(*self).slerp(other, t)
+    }
+
+    /// Check if the two quaternions are equal, within `epsilon` range.
+    #[wasm_bindgen(js_name=almostEqual)]
+    pub fn almost_equal(&self, other: &Quatf, epsilon: f32) -> bool {
+        Quatf::from(*self).almost_equal(other, epsilon)
+    }
+}
@@ -0,0 +1,206 @@
+//! Seeded, deterministic random number generation and sampling utilities.
+//!
+//! The generator is [PCG32](https://www.pcg-random.org/) (XSH RR variant). Every sampling method
+//! only uses integer arithmetic, basic IEEE float operations and `sqrt`, so the same seed yields
+//! the same results in native and WASM builds.
+mod poisson;
+pub use poisson::*;
+
+use crate::array::{Array2f, Array3f};
+use crate::hex::Hexagon;
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+/// The stream of the reference implementation's default increment
+const DEFAULT_STREAM: u64 = 721_347_520_444_481_703;
+/// Upper limit of rejection sampling attempts when sampling polygons
+const MAX_POLYGON_ATTEMPTS: u32 = 1000;
+
+/// Seeded pseudo random number generator
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=Rng, inspectable))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Rng {
+    state: u64,
+    inc: u64,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=Rng))]
+impl Rng {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(seed: u64) -> Self {
+        Self::with_stream(seed, DEFAULT_STREAM)
+    }
+
+    /// Generators with the same seed but different streams produce independent sequences.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=withStream))]
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            inc: (stream << 1) | 1,
+        };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    /// Returns the next uniformly distributed 32 bit integer
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=nextU32))]
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(MULTIPLIER).wrapping_add(self.inc);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xorshifted.rotate_right(rot)
+    }
+
+    /// Returns a uniformly distributed number in the `[0, 1)` range
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=nextF32))]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1 << 24) as f32)
+    }
+
+    /// Returns a uniformly distributed integer in the `[min, max)` range.
+    ///
+    /// Returns `min` if the range is empty.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=rangeI32))]
+    pub fn range_i32(&mut self, min: i32, max: i32) -> i32 {
+        if max <= min {
+            return min;
+        }
+        let span = max.wrapping_sub(min) as u32;
+        min.wrapping_add(self.below(span) as i32)
+    }
+
+    /// Returns a uniformly distributed number in the `[min, max)` range
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=rangeF32))]
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Returns a uniformly chosen hex of the `hexagon` in axial coordinates.
+    ///
+    /// Returns __undefined__ if the hexagon has no hexes, because its radius is negative.
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=hexInHexagon))]
+    pub fn hex_in_hexagon(&mut self, hexagon: &Hexagon) -> Option<Vec2> {
+        let index = self.below(hexagon.num_points() as u32) as usize;
+        let [q, r] = hexagon.nth_point(index)?;
+        Some(Vec2::new(q as f32, r as f32))
+    }
+
+    /// Returns a uniformly distributed point inside the circle
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=pointInCircle))]
+    pub fn point_in_circle(&mut self, center: &Vec2, radius: f32) -> Vec2 {
+        *center + self.point_in_unit_circle() * radius
+    }
+
+    /// Returns a uniformly distributed point inside the `polygon`.
+    ///
+    /// The polygon is given by its vertices, in either winding order.
+    ///
+    /// Returns __null__ if the polygon has less than 3 vertices, or no point could be found
+    /// (for example because the polygon has no area).
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=pointInPolygon))]
+    pub fn point_in_polygon(&mut self, polygon: &Array2f) -> Option<Vec2> {
+        let polygon = polygon.data.as_slice();
+        if polygon.len() < 3 {
+            return None;
+        }
+        let [min, max] = bounding_box(polygon)?;
+        (0..MAX_POLYGON_ATTEMPTS)
+            .map(|_| Vec2::new(self.range_f32(min.x, max.x), self.range_f32(min.y, max.y)))
+            .find(|p| polygon_contains(polygon, p))
+    }
+
+    /// Returns a vector of length 1 pointing in a uniformly distributed direction
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=unitVec2))]
+    pub fn unit_vec2(&mut self) -> Vec2 {
+        loop {
+            let v = self.point_in_unit_circle();
+            let len_sq = v.len_sq();
+            if len_sq > f32::EPSILON {
+                return v / len_sq.sqrt();
+            }
+        }
+    }
+
+    /// Shuffles the vectors in place
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=shuffleArray2f))]
+    pub fn shuffle_array2f(&mut self, array: &mut Array2f) {
+        self.shuffle(&mut array.data);
+    }
+
+    /// Shuffles the vectors in place
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=shuffleArray3f))]
+    pub fn shuffle_array3f(&mut self, array: &mut Array3f) {
+        self.shuffle(&mut array.data);
+    }
+}
+
+impl Rng {
+    /// Returns a uniformly distributed integer in the `[0, n)` range, without modulo bias.
+    ///
+    /// Returns 0 if `n` is 0.
+    pub fn below(&mut self, n: u32) -> u32 {
+        if n == 0 {
+            return 0;
+        }
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let r = self.next_u32();
+            if r >= threshold {
+                return r % n;
+            }
+        }
+    }
+
+    /// Fisher-Yates shuffle of the slice
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u32 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    fn point_in_unit_circle(&mut self) -> Vec2 {
+        loop {
+            let v = Vec2::new(self.range_f32(-1., 1.), self.range_f32(-1., 1.));
+            if v.len_sq() <= 1. {
+                return v;
+            }
+        }
+    }
+}
+
+/// Returns the `[min, max]` corners of the axis aligned bounding box of the points.
+pub(crate) fn bounding_box(points: &[Vec2]) -> Option<[Vec2; 2]> {
+    let first = *points.first()?;
+    let bounds = points.iter().fold([first, first], |[min, max], p| {
+        [
+            Vec2::new(min.x.min(p.x), min.y.min(p.y)),
+            Vec2::new(max.x.max(p.x), max.y.max(p.y)),
+        ]
+    });
+    Some(bounds)
+}
+
+/// Even-odd rule point in polygon test
+pub(crate) fn polygon_contains(polygon: &[Vec2], point: &Vec2) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for (i, a) in polygon.iter().enumerate() {
+        let b = &polygon[j];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
@@ -0,0 +1,180 @@
+//! [Poisson-disk](https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph07-poissondisk.pdf)
+//! sampling in pixel space and on the hex grid
+use super::{bounding_box, polygon_contains, Rng};
+use crate::array::Array2f;
+use crate::hex::Hexagon;
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Number of candidates tried around an active sample before it is retired, if not specified
+const DEFAULT_ATTEMPTS: u32 = 30;
+
+/// Bridson Poisson-disk sampling in the rectangle spanned by `min` and `max`.
+///
+/// No two returned points are closer than `radius` to each other.
+///
+/// @param attempts number of candidates tried around each sample, defaults to 30
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=poissonDiskRect))]
+pub fn poisson_disk_rect(
+    rng: &mut Rng,
+    min: &Vec2,
+    max: &Vec2,
+    radius: f32,
+    attempts: Option<u32>,
+) -> Array2f {
+    let (min, max) = (*min, *max);
+    if !(min.x < max.x && min.y < max.y) {
+        return Array2f::new();
+    }
+    let first = Vec2::new(rng.range_f32(min.x, max.x), rng.range_f32(min.y, max.y));
+    let contains = |p: &Vec2| min.x <= p.x && p.x < max.x && min.y <= p.y && p.y < max.y;
+    bridson(rng, [min, max], radius, attempts, first, contains)
+}
+
+/// Bridson Poisson-disk sampling inside the `polygon`, given by its vertices.
+///
+/// No two returned points are closer than `radius` to each other.
+/// Returns an empty array if the polygon has less than 3 vertices or no area.
+///
+/// @param attempts number of candidates tried around each sample, defaults to 30
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=poissonDiskPolygon))]
+pub fn poisson_disk_polygon(
+    rng: &mut Rng,
+    polygon: &Array2f,
+    radius: f32,
+    attempts: Option<u32>,
+) -> Array2f {
+    let first = match rng.point_in_polygon(polygon) {
+        Some(p) => p,
+        None => return Array2f::new(),
+    };
+    let vertices = polygon.data.as_slice();
+    let bounds = bounding_box(vertices).expect("polygon has vertices");
+    let contains = |p: &Vec2| polygon_contains(vertices, p);
+    bridson(rng, bounds, radius, attempts, first, contains)
+}
+
+/// Picks hexes of the `hexagon` such that no two of them are closer than `min_distance`
+/// (measured by `cubeDistance`), and no more hexes can be added.
+///
+/// @return list of the picked axial coordinates
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=poissonDiskHexagon))]
+pub fn poisson_disk_hexagon(rng: &mut Rng, hexagon: &Hexagon, min_distance: u32) -> Array2f {
+    let candidates = hexagon.iter_points().collect();
+    poisson_disk_hex(rng, candidates, min_distance)
+}
+
+/// Picks hexes of the `region`, given by its axial coordinates, such that no two of them are
+/// closer than `min_distance` (measured by `cubeDistance`), and no more hexes can be added.
+///
+/// @return list of the picked axial coordinates
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=poissonDiskRegion))]
+pub fn poisson_disk_region(rng: &mut Rng, region: &Array2f, min_distance: u32) -> Array2f {
+    let candidates = region
+        .data
+        .iter()
+        .map(|p| [p.x.round() as i32, p.y.round() as i32])
+        .collect();
+    poisson_disk_hex(rng, candidates, min_distance)
+}
+
+/// Dart throwing in random order over the candidates, which produces a maximal sampling.
+fn poisson_disk_hex(rng: &mut Rng, mut candidates: Vec<[i32; 2]>, min_distance: u32) -> Array2f {
+    rng.shuffle(&mut candidates);
+
+    let mut picked = BTreeSet::new();
+    let mut data = Vec::new();
+    for [q, r] in candidates {
+        let is_free = min_distance == 0
+            || !Hexagon {
+                center: Vec2::new(q as f32, r as f32),
+                radius: (min_distance - 1) as f32,
+            }
+            .iter_points()
+            .any(|p| picked.contains(&p));
+        if is_free && picked.insert([q, r]) {
+            data.push(Vec2::new(q as f32, r as f32));
+        }
+    }
+    Array2f { data }
+}
+
+fn bridson(
+    rng: &mut Rng,
+    [min, max]: [Vec2; 2],
+    radius: f32,
+    attempts: Option<u32>,
+    first: Vec2,
+    contains: impl Fn(&Vec2) -> bool,
+) -> Array2f {
+    let attempts = attempts.unwrap_or(DEFAULT_ATTEMPTS);
+    if radius <= 0. {
+        return Array2f { data: vec![first] };
+    }
+
+    // every cell of the background grid may hold at most one sample
+    let cell_size = radius / core::f32::consts::SQRT_2;
+    let width = (((max.x - min.x) / cell_size).ceil() as usize).max(1);
+    let height = (((max.y - min.y) / cell_size).ceil() as usize).max(1);
+    let cell_of = |p: &Vec2| {
+        let x = (((p.x - min.x) / cell_size) as usize).min(width - 1);
+        let y = (((p.y - min.y) / cell_size) as usize).min(height - 1);
+        [x, y]
+    };
+    let mut grid: Vec<Option<usize>> = vec![None; width * height];
+
+    let radius_sq = radius * radius;
+    let is_far_enough = |grid: &[Option<usize>], samples: &[Vec2], p: &Vec2| {
+        let [cx, cy] = cell_of(p);
+        let xs = cx.saturating_sub(2)..(cx + 3).min(width);
+        xs.flat_map(|x| (cy.saturating_sub(2)..(cy + 3).min(height)).map(move |y| [x, y]))
+            .filter_map(|[x, y]| grid[y * width + x])
+            .all(|i| (samples[i] - *p).len_sq() >= radius_sq)
+    };
+
+    let mut samples = vec![first];
+    let [x, y] = cell_of(&first);
+    grid[y * width + x] = Some(0);
+    let mut active = vec![0];
+
+    while !active.is_empty() {
+        let i = rng.below(active.len() as u32) as usize;
+        let center = samples[active[i]];
+        let candidate = (0..attempts)
+            .map(|_| center + point_in_annulus(rng, radius))
+            .find(|p| contains(p) && is_far_enough(&grid, &samples, p));
+        match candidate {
+            Some(p) => {
+                let [x, y] = cell_of(&p);
+                grid[y * width + x] = Some(samples.len());
+                active.push(samples.len());
+                samples.push(p);
+            }
+            None => {
+                active.swap_remove(i);
+            }
+        }
+    }
+
+    Array2f { data: samples }
+}
+
+/// Uniform point in the annulus between `radius` and `2*radius` around the origin.
+///
+/// Uses rejection sampling instead of trigonometry to stay deterministic across platforms.
+fn point_in_annulus(rng: &mut Rng, radius: f32) -> Vec2 {
+    let outer = 2. * radius;
+    loop {
+        let v = Vec2::new(rng.range_f32(-outer, outer), rng.range_f32(-outer, outer));
+        let len_sq = v.len_sq();
+        if radius * radius <= len_sq && len_sq <= outer * outer {
+            return v;
+        }
+    }
+}
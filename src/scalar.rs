@@ -0,0 +1,126 @@
+//! Number types the vectors and matrices are generic over
+//!
+//! Without the `std` feature the `Float` methods are implemented with `libm`. Import `Float` with
+//! `#[cfg(not(feature = "std"))]` where they are used on `f32` or `f64`, with `std` the inherent
+//! methods are used instead.
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Components of vectors and matrices
+pub trait Scalar:
+    Copy
+    + Debug
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + MulAssign
+    + Div<Output = Self>
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+/// Floating point scalars, required by the methods that need roots, trigonometry or exact
+/// division
+pub trait Float: Scalar + Neg<Output = Self> {
+    /// Difference between 1 and the next representable number
+    const EPSILON: Self;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn exp(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty: $zero:expr, $one:expr);*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = $zero;
+                const ONE: Self = $one;
+            }
+        )*
+    };
+}
+
+impl_scalar!(
+    f32: 0., 1.;
+    f64: 0., 1.;
+    i32: 0, 1;
+    i64: 0, 1;
+    u32: 0, 1;
+    u64: 0, 1
+);
+
+macro_rules! impl_float {
+    ($t:ty, $($name:ident: $libm:ident),*; atan2: $atan2:ident) => {
+        impl Float for $t {
+            const EPSILON: Self = <$t>::EPSILON;
+
+            $(
+                #[cfg(feature = "std")]
+                fn $name(self) -> Self {
+                    <$t>::$name(self)
+                }
+
+                #[cfg(not(feature = "std"))]
+                fn $name(self) -> Self {
+                    libm::$libm(self)
+                }
+            )*
+
+            #[cfg(feature = "std")]
+            fn atan2(self, x: Self) -> Self {
+                <$t>::atan2(self, x)
+            }
+
+            #[cfg(not(feature = "std"))]
+            fn atan2(self, x: Self) -> Self {
+                libm::$atan2(self, x)
+            }
+        }
+    };
+}
+
+impl_float!(
+    f32,
+    abs: fabsf,
+    sqrt: sqrtf,
+    sin: sinf,
+    cos: cosf,
+    tan: tanf,
+    acos: acosf,
+    exp: expf,
+    floor: floorf,
+    ceil: ceilf,
+    round: roundf;
+    atan2: atan2f
+);
+
+impl_float!(
+    f64,
+    abs: fabs,
+    sqrt: sqrt,
+    sin: sin,
+    cos: cos,
+    tan: tan,
+    acos: acos,
+    exp: exp,
+    floor: floor,
+    ceil: ceil,
+    round: round;
+    atan2: atan2
+);
@@ -0,0 +1,231 @@
+//! SVG export of hex grids and geometry, for debugging
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::Write;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::array::Array2f;
+use crate::hex::{Hexagon, Orientation};
+#[cfg(not(feature = "std"))]
+use crate::scalar::Float;
+use crate::vec::vec2::Vec2;
+
+const DEFAULT_FILL: &str = "none";
+const GRID_STROKE: &str = "#888";
+
+/// Collects shapes and writes them into an SVG document.
+///
+/// Shapes are drawn in the order they were added. The view box fits all shapes.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name=SvgWriter))]
+#[derive(Debug, Clone)]
+pub struct SvgWriter {
+    orientation: Orientation,
+    size: f32,
+    elements: Vec<String>,
+    min: Vec2,
+    max: Vec2,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_class=SvgWriter))]
+impl SvgWriter {
+    /// @param size distance between the center and the vertices of a hex, in pixels
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(orientation: Orientation, size: f32) -> Self {
+        Self {
+            orientation,
+            size,
+            elements: vec![],
+            min: Vec2::new(f32::INFINITY, f32::INFINITY),
+            max: Vec2::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+        }
+    }
+
+    /// Draws the outline of every hex of the hexagon
+    ///
+    /// @param fill fill color of the hexes, defaults to none
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn hexagon(&mut self, hexagon: &Hexagon, fill: Option<String>) {
+        for [q, r] in hexagon.iter_points() {
+            self.hex(q, r, fill.clone(), None);
+        }
+    }
+
+    /// Draws the hex at the axial point `(q, r)`, with an optional label at its center
+    ///
+    /// @param fill fill color of the hex, defaults to none
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn hex(&mut self, q: i32, r: i32, fill: Option<String>, label: Option<String>) {
+        let center = self.center(Vec2::new(q as f32, r as f32));
+        let corners = self.corners(center);
+        let fill = fill.as_deref().unwrap_or(DEFAULT_FILL);
+        let element = format!(
+            r#"<polygon points="{}" fill="{}" stroke="{}" stroke-width="{}"/>"#,
+            self.points_attr(&corners),
+            escape(fill),
+            GRID_STROKE,
+            num(self.size / 20.)
+        );
+        self.push(element, &corners);
+        if let Some(label) = label {
+            self.label(&center, &label);
+        }
+    }
+
+    /// Writes `text` centered at the pixel position
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn label(&mut self, position: &Vec2, text: &str) {
+        let element = format!(
+            r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            num(position.x),
+            num(position.y),
+            num(self.size / 2.),
+            escape(text)
+        );
+        self.push(element, &[*position]);
+    }
+
+    /// Draws a line through the pixel space points
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn polyline(&mut self, points: &Array2f, stroke: &str, width: f32) {
+        let element = format!(
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            self.points_attr(&points.data),
+            escape(stroke),
+            num(width)
+        );
+        self.push(element, &points.data);
+    }
+
+    /// Draws a line through the centers of the axial points
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=hexPath))]
+    pub fn hex_path(&mut self, axial: &Array2f, stroke: &str, width: f32) {
+        let data = axial.data.iter().map(|p| self.center(*p)).collect();
+        self.polyline(&Array2f { data }, stroke, width);
+    }
+
+    /// Draws a closed polygon of the pixel space points
+    ///
+    /// @param stroke color of the outline, defaults to no outline
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn polygon(&mut self, points: &Array2f, fill: &str, stroke: Option<String>) {
+        let element = format!(
+            r#"<polygon points="{}" fill="{}" stroke="{}"/>"#,
+            self.points_attr(&points.data),
+            escape(fill),
+            escape(stroke.as_deref().unwrap_or("none"))
+        );
+        self.push(element, &points.data);
+    }
+
+    /// Draws a dot at each of the pixel space points
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn points(&mut self, points: &Array2f, radius: f32, fill: &str) {
+        for p in points.data.iter() {
+            let element = format!(
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                num(p.x),
+                num(p.y),
+                num(radius),
+                escape(fill)
+            );
+            let extent = Vec2::new(radius, radius);
+            self.push(element, &[*p - extent, *p + extent]);
+        }
+    }
+
+    /// Returns the SVG document
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name=toString))]
+    pub fn to_svg(&self) -> String {
+        let (min, max) = if self.elements.is_empty() {
+            (Vec2::new(0., 0.), Vec2::new(0., 0.))
+        } else {
+            (self.min, self.max)
+        };
+        let margin = self.size / 2.;
+        let mut svg = String::new();
+        write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            num(min.x - margin),
+            num(min.y - margin),
+            num(max.x - min.x + 2. * margin),
+            num(max.y - min.y + 2. * margin)
+        )
+        .unwrap();
+        for element in self.elements.iter() {
+            svg.push('\n');
+            svg.push_str(element);
+        }
+        svg.push_str("\n</svg>\n");
+        svg
+    }
+}
+
+impl SvgWriter {
+    /// Draws every hex of the hexagon, `style` returns the fill color and label of each hex
+    pub fn hexagon_with(
+        &mut self,
+        hexagon: &Hexagon,
+        mut style: impl FnMut([i32; 2]) -> (Option<String>, Option<String>),
+    ) {
+        for [q, r] in hexagon.iter_points() {
+            let (fill, label) = style([q, r]);
+            self.hex(q, r, fill, label);
+        }
+    }
+
+    fn center(&self, axial: Vec2) -> Vec2 {
+        self.orientation.hex_center(axial, self.size)
+    }
+
+    fn corners(&self, center: Vec2) -> Vec<Vec2> {
+        let offset = match self.orientation {
+            Orientation::Pointy => 30f32,
+            Orientation::Flat => 0.,
+        };
+        (0..6)
+            .map(|k| {
+                let angle = (offset + 60. * k as f32).to_radians();
+                center + Vec2::new(angle.cos(), angle.sin()) * self.size
+            })
+            .collect()
+    }
+
+    fn points_attr(&self, points: &[Vec2]) -> String {
+        points
+            .iter()
+            .map(|p| format!("{},{}", num(p.x), num(p.y)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn push(&mut self, element: String, bounds: &[Vec2]) {
+        for p in bounds {
+            self.min = Vec2::new(self.min.x.min(p.x), self.min.y.min(p.y));
+            self.max = Vec2::new(self.max.x.max(p.x), self.max.y.max(p.y));
+        }
+        self.elements.push(element);
+    }
+}
+
+/// Formats the number with at most 3 decimals
+fn num(x: f32) -> String {
+    let x = (x * 1000.).round() / 1000.;
+    // avoid writing `-0`
+    format!("{}", x + 0.)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,3 +1,4 @@
 //! Basic 2d vectors
 pub mod vec2;
 pub mod vec3;
+pub mod vec4;
@@ -1,29 +1,35 @@
-use super::vec3;
+use super::vec3::Vec3;
+#[cfg(feature = "wasm")]
+use super::vec3::Vec3f;
+use crate::scalar::{Float, Scalar};
+use core::mem::swap;
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "alloc")]
 use serde_derive::{Deserialize, Serialize};
-use std::mem::swap;
-use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen(js_name=Vec2f, inspectable)]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
-pub struct Vec2 {
-    pub x: f32,
-    pub y: f32,
+/// 2d vector of any `Scalar`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Vec2<T = f32> {
+    pub x: T,
+    pub y: T,
 }
 
-#[wasm_bindgen(js_class=Vec2f)]
-impl Vec2 {
-    #[wasm_bindgen(constructor)]
-    pub fn new(x: f32, y: f32) -> Self {
+pub type Vec2f = Vec2<f32>;
+pub type Vec2d = Vec2<f64>;
+pub type Vec2i = Vec2<i32>;
+
+impl<T: Scalar> Vec2<T> {
+    pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 
-    #[wasm_bindgen]
-    pub fn swap(&mut self, other: &mut Vec2) {
+    pub fn swap(&mut self, other: &mut Self) {
         swap(self, other);
     }
 
-    #[wasm_bindgen]
     pub fn transponent(&self) -> Self {
         Self {
             x: self.y,
@@ -31,51 +37,76 @@ impl Vec2 {
         }
     }
 
-    #[wasm_bindgen]
-    pub fn dot(&self, other: &Vec2) -> f32 {
+    pub fn dot(&self, other: &Self) -> T {
         self.x * other.x + self.y * other.y
     }
 
-    #[wasm_bindgen]
-    pub fn add(&self, other: &Vec2) -> Self {
+    pub fn add(&self, other: &Self) -> Self {
         *self + *other
     }
 
-    #[wasm_bindgen]
-    pub fn sub(&self, other: &Vec2) -> Self {
+    pub fn sub(&self, other: &Self) -> Self {
         *self - *other
     }
 
-    #[wasm_bindgen]
-    pub fn mul(&self, other: f32) -> Self {
+    pub fn mul(&self, other: T) -> Self {
         *self * other
     }
 
+    /// Squared length of this vector
+    pub fn len_sq(&self) -> T {
+        self.dot(self)
+    }
+
+    /// Returns the orientation of point __C__ in respect to the directed line __AB__.
+    ///
+    /// If the __orient2d(a, b, c)__ > 0, then __C__ lies to the left.
+    ///
+    /// If the __orient2d(a, b, c)__ < 0, then __C__ lies to the right.
+    ///
+    /// If the __orient2d(a, b, c)__ == 0, then __C__ lies to on the line.
+    ///
+    /// The absolute value of the returning number is double the area of the triangle __ABC__
+    pub fn orient2d(a: &Self, b: &Self, c: &Self) -> T {
+        ((a.x - c.x) * (b.y - c.y)) - ((a.y - c.y) * (b.x - c.x))
+    }
+
+    /// Casts `this` to a 3d displacement/vector.
+    pub fn to_3d_vector(&self) -> Vec3<T> {
+        Vec3 {
+            x: self.x,
+            y: self.y,
+            z: T::ZERO,
+        }
+    }
+
+    /// Casts `this` to a 3D coordinate representation
+    pub fn extend(&self, w: Option<T>) -> Vec3<T> {
+        Vec3 {
+            x: self.x,
+            y: self.y,
+            z: w.unwrap_or(T::ZERO),
+        }
+    }
+}
+
+impl<T: Float> Vec2<T> {
     /// Returns a new vector with the same direction but a length of 1
-    #[wasm_bindgen]
     pub fn normalized(&self) -> Self {
         let lensq = self.dot(self);
         *self / lensq.sqrt()
     }
 
-    /// Squared length of this vector
-    #[wasm_bindgen(js_name=lenSq)]
-    pub fn len_sq(&self) -> f32 {
-        self.dot(self)
-    }
-
     /// Length of this vector.
     ///
     /// Calculated as `sqrt lenSq()`. If you only need to compare lengths of vectors (for example
     /// when sorting) prefer using `lenSq`.
-    #[wasm_bindgen(js_name=len)]
-    pub fn len(&self) -> f32 {
+    pub fn len(&self) -> T {
         self.dot(self).sqrt()
     }
 
     /// Rotate the vector around the origin counter-clockwise by `rad` radians.
-    #[wasm_bindgen]
-    pub fn rotate(&mut self, rad: f32) {
+    pub fn rotate(&mut self, rad: T) {
         let c = rad.cos();
         let s = rad.sin();
         self.x = self.x * c - self.y * s;
@@ -83,54 +114,55 @@ impl Vec2 {
     }
 
     /// Returns the angle between the two vectors in radians
-    #[wasm_bindgen(js_name=angleBetween)]
-    pub fn angle_between(&self, other: &Vec2) -> f32 {
+    pub fn angle_between(&self, other: &Self) -> T {
         let cos = self.dot(other) / (self.len_sq().sqrt() * other.len_sq().sqrt());
         cos.acos()
     }
 
-    /// Returns the orientation of point __C__ in respect to the directed line __AB__.
-    ///
-    /// If the __orient2d(a, b, c)__ > 0, then __C__ lies to the left.
-    ///
-    /// If the __orient2d(a, b, c)__ < 0, then __C__ lies to the right.
-    ///
-    /// If the __orient2d(a, b, c)__ == 0, then __C__ lies to on the line.
-    ///
-    /// The absolute value of the returning number is double the area of the triangle __ABC__
-    #[wasm_bindgen]
-    pub fn orient2d(a: &Vec2, b: &Vec2, c: &Vec2) -> f32 {
-        ((a.x - c.x) * (b.y - c.y)) - ((a.y - c.y) * (b.x - c.x))
+    /// Cast the Homogenous representation back to (x, y) representation
+    pub fn from_homogeneous(point: Vec3<T>) -> Self {
+        if point.z.abs() < T::EPSILON {
+            // if z is about 0
+            Self {
+                x: point.x,
+                y: point.y,
+            }
+        } else {
+            Self {
+                x: point.x / point.z,
+                y: point.y / point.z,
+            }
+        }
     }
 }
 
-impl Into<[f32; 2]> for Vec2 {
-    fn into(self) -> [f32; 2] {
+impl<T> Into<[T; 2]> for Vec2<T> {
+    fn into(self) -> [T; 2] {
         [self.x, self.y]
     }
 }
 
-impl Into<[f32; 2]> for &Vec2 {
-    fn into(self) -> [f32; 2] {
+impl<T: Copy> Into<[T; 2]> for &Vec2<T> {
+    fn into(self) -> [T; 2] {
         [self.x, self.y]
     }
 }
 
-impl<'a> Into<[&'a mut f32; 2]> for &'a mut Vec2 {
-    fn into(self) -> [&'a mut f32; 2] {
+impl<'a, T> Into<[&'a mut T; 2]> for &'a mut Vec2<T> {
+    fn into(self) -> [&'a mut T; 2] {
         [&mut self.x, &mut self.y]
     }
 }
 
-impl From<[f32; 2]> for Vec2 {
-    fn from([x, y]: [f32; 2]) -> Self {
+impl<T> From<[T; 2]> for Vec2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
         Self { x, y }
     }
 }
 
-impl Index<usize> for Vec2 {
-    type Output = f32;
-    fn index(&self, index: usize) -> &f32 {
+impl<T> Index<usize> for Vec2<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
         match index {
             0 => &self.x,
             1 => &self.y,
@@ -139,8 +171,8 @@ impl Index<usize> for Vec2 {
     }
 }
 
-impl IndexMut<usize> for Vec2 {
-    fn index_mut(&mut self, index: usize) -> &mut f32 {
+impl<T> IndexMut<usize> for Vec2<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
         match index {
             0 => &mut self.x,
             1 => &mut self.y,
@@ -149,14 +181,14 @@ impl IndexMut<usize> for Vec2 {
     }
 }
 
-impl AddAssign for Vec2 {
+impl<T: Scalar> AddAssign for Vec2<T> {
     fn add_assign(&mut self, p: Self) {
         self.x += p.x;
         self.y += p.y;
     }
 }
 
-impl Add for Vec2 {
+impl<T: Scalar> Add for Vec2<T> {
     type Output = Self;
 
     fn add(mut self, p: Self) -> Self {
@@ -165,14 +197,14 @@ impl Add for Vec2 {
     }
 }
 
-impl SubAssign for Vec2 {
+impl<T: Scalar> SubAssign for Vec2<T> {
     fn sub_assign(&mut self, p: Self) {
         self.x -= p.x;
         self.y -= p.y;
     }
 }
 
-impl Sub for Vec2 {
+impl<T: Scalar> Sub for Vec2<T> {
     type Output = Self;
 
     fn sub(mut self, p: Self) -> Self {
@@ -181,84 +213,166 @@ impl Sub for Vec2 {
     }
 }
 
-impl Mul<Vec2> for Vec2 {
-    type Output = f32;
+impl<T: Scalar> Mul<Vec2<T>> for Vec2<T> {
+    type Output = T;
 
-    fn mul(self, a: Vec2) -> Self::Output {
+    fn mul(self, a: Vec2<T>) -> Self::Output {
         self.dot(&a)
     }
 }
 
-impl MulAssign<f32> for Vec2 {
-    fn mul_assign(&mut self, a: f32) {
+impl<T: Scalar> MulAssign<T> for Vec2<T> {
+    fn mul_assign(&mut self, a: T) {
         self.x *= a;
         self.y *= a;
     }
 }
 
-impl Mul<f32> for Vec2 {
+impl<T: Scalar> Mul<T> for Vec2<T> {
     type Output = Self;
 
-    fn mul(mut self, a: f32) -> Self {
+    fn mul(mut self, a: T) -> Self {
         self *= a;
         self
     }
 }
 
-impl DivAssign<f32> for Vec2 {
-    fn div_assign(&mut self, a: f32) {
+impl<T: Scalar> DivAssign<T> for Vec2<T> {
+    fn div_assign(&mut self, a: T) {
         self.x /= a;
         self.y /= a;
     }
 }
 
-impl Div<f32> for Vec2 {
+impl<T: Scalar> Div<T> for Vec2<T> {
     type Output = Self;
 
-    fn div(mut self, a: f32) -> Self {
+    fn div(mut self, a: T) -> Self {
         self /= a;
         self
     }
 }
 
-// impl interoperability
-//
-#[wasm_bindgen(js_class = Vec2f)]
-impl Vec2 {
-    #[wasm_bindgen(js_name=to3dVector)]
+/// `Vec2f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Vec2f, inspectable)]
+#[derive(Debug, Clone, Copy)]
+pub struct Vec2fJs {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[cfg(feature = "wasm")]
+impl From<Vec2f> for Vec2fJs {
+    fn from(Vec2 { x, y }: Vec2f) -> Self {
+        Self { x, y }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Vec2fJs> for Vec2f {
+    fn from(Vec2fJs { x, y }: Vec2fJs) -> Self {
+        Self { x, y }
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Vec2f, Vec2fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Vec2f)]
+impl Vec2fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f32, y: f32) -> Self {
+        Vec2::new(x, y).into()
+    }
+
+    pub fn swap(&mut self, other: &mut Vec2f) {
+        let mut v = Vec2f::from(*self);
+        v.swap(other);
+        *self = v.into();
+    }
+
+    pub fn transponent(&self) -> Vec2f {
+        Vec2f::from(*self).transponent()
+    }
+
+    pub fn dot(&self, other: &Vec2f) -> f32 {
+        Vec2f::from(*self).dot(other)
+    }
+
+    pub fn add(&self, other: &Vec2f) -> Vec2f {
+        Vec2f::from(*self) + *other
+    }
+
+    pub fn sub(&self, other: &Vec2f) -> Vec2f {
+        Vec2f::from(*self) - *other
+    }
+
+    pub fn mul(&self, other: f32) -> Vec2f {
+        Vec2f::from(*self).mul(other)
+    }
+
+    /// Returns a new vector with the same direction but a length of 1
+    pub fn normalized(&self) -> Vec2f {
+        Vec2f::from(*self).normalized()
+    }
+
+    /// Squared length of this vector
+    #[wasm_bindgen(js_name=lenSq)]
+    pub fn len_sq(&self) -> f32 {
+        Vec2f::from(*self).len_sq()
+    }
+
+    /// Length of this vector.
+    ///
+    /// Calculated as `sqrt lenSq()`. If you only need to compare lengths of vectors (for example
+    /// when sorting) prefer using `lenSq`.
+    pub fn len(&self) -> f32 {
+        Vec2f::from(*self).len()
+    }
+
+    /// Rotate the vector around the origin counter-clockwise by `rad` radians.
+    pub fn rotate(&mut self, rad: f32) {
+        let mut v = Vec2f::from(*self);
+        v.rotate(rad);
+        *self = v.into();
+    }
+
+    /// Returns the angle between the two vectors in radians
+    #[wasm_bindgen(js_name=angleBetween)]
+    pub fn angle_between(&self, other: &Vec2f) -> f32 {
+        Vec2f::from(*self).angle_between(other)
+    }
+
+    /// Returns the orientation of point __C__ in respect to the directed line __AB__.
+    ///
+    /// If the __orient2d(a, b, c)__ > 0, then __C__ lies to the left.
+    ///
+    /// If the __orient2d(a, b, c)__ < 0, then __C__ lies to the right.
+    ///
+    /// If the __orient2d(a, b, c)__ == 0, then __C__ lies to on the line.
+    ///
+    /// The absolute value of the returning number is double the area of the triangle __ABC__
+    pub fn orient2d(a: &Vec2f, b: &Vec2f, c: &Vec2f) -> f32 {
+        Vec2::orient2d(a, b, c)
+    }
+
     /// Casts `this` to a 3d displacement/vector.
-    pub fn to_3d_vector(&self) -> vec3::Vec3 {
-        vec3::Vec3 {
-            x: self.x,
-            y: self.y,
-            z: Default::default(),
-        }
+    #[wasm_bindgen(js_name=to3dVector)]
+    pub fn to_3d_vector(&self) -> Vec3f {
+        Vec2f::from(*self).to_3d_vector()
     }
 
-    #[wasm_bindgen(js_name=extend)]
     /// Casts `this` to a 3D coordinate representation
-    pub fn extend(&self, w: Option<f32>) -> vec3::Vec3 {
-        vec3::Vec3 {
-            x: self.x,
-            y: self.y,
-            z: w.unwrap_or_default(),
-        }
+    pub fn extend(&self, w: Option<f32>) -> Vec3f {
+        Vec2f::from(*self).extend(w)
     }
 
-    #[wasm_bindgen(js_name=fromHomogeneous)]
     /// Cast the Homogenous representation back to (x, y) representation
-    pub fn from_homogeneous(point: vec3::Vec3) -> Self {
-        if point.z.abs() < std::f32::EPSILON {
-            // if z is about 0
-            Self {
-                x: point.x,
-                y: point.y,
-            }
-        } else {
-            Self {
-                x: point.x / point.z,
-                y: point.y / point.z,
-            }
-        }
+    #[wasm_bindgen(js_name=fromHomogeneous)]
+    pub fn from_homogeneous(point: Vec3f) -> Vec2f {
+        Vec2::from_homogeneous(point)
     }
 }
@@ -1,20 +1,29 @@
+use super::vec4::Vec4;
+#[cfg(feature = "wasm")]
+use super::vec4::Vec4f;
+use crate::scalar::{Float, Scalar};
+use core::mem::swap;
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "alloc")]
 use serde_derive::{Deserialize, Serialize};
-use std::mem::swap;
-use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen(js_name=Vec3f, inspectable)]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
-pub struct Vec3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+/// 3d vector of any `Scalar`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Vec3<T = f32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-#[wasm_bindgen(js_class=Vec3f)]
-impl Vec3 {
-    #[wasm_bindgen(constructor)]
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
+pub type Vec3f = Vec3<f32>;
+pub type Vec3d = Vec3<f64>;
+pub type Vec3i = Vec3<i32>;
+
+impl<T: Scalar> Vec3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
         Self { x, y, z }
     }
 
@@ -23,23 +32,19 @@ impl Vec3 {
     ///
     /// This is equivalent to __det([u v w])__ or
     /// __(u cross v) dot w__
-    #[wasm_bindgen(js_name=scalarTriple)]
-    pub fn scalar_triple(u: &Vec3, v: &Vec3, w: &Vec3) -> f32 {
+    pub fn scalar_triple(u: &Self, v: &Self, w: &Self) -> T {
         u.cross(v).dot(w)
     }
 
-    #[wasm_bindgen]
-    pub fn swap(&mut self, other: &mut Vec3) {
+    pub fn swap(&mut self, other: &mut Self) {
         swap(self, other);
     }
 
-    #[wasm_bindgen]
-    pub fn dot(&self, other: &Vec3) -> f32 {
+    pub fn dot(&self, other: &Self) -> T {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
 
-    #[wasm_bindgen]
-    pub fn cross(&self, other: &Vec3) -> Self {
+    pub fn cross(&self, other: &Self) -> Self {
         Self {
             x: self.y * other.z - self.z * other.y,
             y: self.z * other.x - self.x * other.z,
@@ -47,71 +52,95 @@ impl Vec3 {
         }
     }
 
-    #[wasm_bindgen]
-    pub fn add(&self, other: &Vec3) -> Self {
+    pub fn add(&self, other: &Self) -> Self {
         *self + *other
     }
 
-    #[wasm_bindgen]
-    pub fn sub(&self, other: &Vec3) -> Self {
+    pub fn sub(&self, other: &Self) -> Self {
         *self - *other
     }
 
-    #[wasm_bindgen]
-    pub fn mul(&self, other: f32) -> Self {
+    pub fn mul(&self, other: T) -> Self {
         *self * other
     }
 
+    /// Squared length of this vector
+    pub fn len_sq(&self) -> T {
+        self.dot(self)
+    }
+
+    /// Casts `this` to a 4D homogeneous representation. `w` defaults to 0, a direction.
+    pub fn extend(&self, w: Option<T>) -> Vec4<T> {
+        Vec4 {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            w: w.unwrap_or(T::ZERO),
+        }
+    }
+}
+
+impl<T: Float> Vec3<T> {
     /// Returns a new vector with the same direction but a length of 1
-    #[wasm_bindgen]
     pub fn normalized(&self) -> Self {
         let len_sq = self.dot(self);
         *self / len_sq.sqrt()
     }
 
-    /// Squared length of this vector
-    #[wasm_bindgen(js_name=lenSq)]
-    pub fn len_sq(&self) -> f32 {
-        self.dot(self)
-    }
-
     /// Length of this vector
     ///
     /// Calculated as `sqrt lenSq()`. If you only need to compare lengths of vectors (for example
     /// when sorting) prefer using `lenSq`.
-    #[wasm_bindgen(js_name=len)]
-    pub fn len(&self) -> f32 {
+    pub fn len(&self) -> T {
         self.dot(self).sqrt()
     }
+
+    /// Cast the Homogenous representation back to (x, y, z) representation
+    pub fn from_homogeneous(point: Vec4<T>) -> Self {
+        if point.w.abs() < T::EPSILON {
+            // if w is about 0
+            Self {
+                x: point.x,
+                y: point.y,
+                z: point.z,
+            }
+        } else {
+            Self {
+                x: point.x / point.w,
+                y: point.y / point.w,
+                z: point.z / point.w,
+            }
+        }
+    }
 }
 
-impl Into<[f32; 3]> for Vec3 {
-    fn into(self) -> [f32; 3] {
+impl<T> Into<[T; 3]> for Vec3<T> {
+    fn into(self) -> [T; 3] {
         [self.x, self.y, self.z]
     }
 }
 
-impl Into<[f32; 3]> for &Vec3 {
-    fn into(self) -> [f32; 3] {
+impl<T: Copy> Into<[T; 3]> for &Vec3<T> {
+    fn into(self) -> [T; 3] {
         [self.x, self.y, self.z]
     }
 }
 
-impl<'a> Into<[&'a mut f32; 3]> for &'a mut Vec3 {
-    fn into(self) -> [&'a mut f32; 3] {
+impl<'a, T> Into<[&'a mut T; 3]> for &'a mut Vec3<T> {
+    fn into(self) -> [&'a mut T; 3] {
         [&mut self.x, &mut self.y, &mut self.z]
     }
 }
 
-impl From<[f32; 3]> for Vec3 {
-    fn from([x, y, z]: [f32; 3]) -> Self {
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
         Self { x, y, z }
     }
 }
 
-impl Index<usize> for Vec3 {
-    type Output = f32;
-    fn index(&self, index: usize) -> &f32 {
+impl<T> Index<usize> for Vec3<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
         match index {
             0 => &self.x,
             1 => &self.y,
@@ -121,8 +150,8 @@ impl Index<usize> for Vec3 {
     }
 }
 
-impl IndexMut<usize> for Vec3 {
-    fn index_mut(&mut self, index: usize) -> &mut f32 {
+impl<T> IndexMut<usize> for Vec3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
         match index {
             0 => &mut self.x,
             1 => &mut self.y,
@@ -132,7 +161,7 @@ impl IndexMut<usize> for Vec3 {
     }
 }
 
-impl AddAssign for Vec3 {
+impl<T: Scalar> AddAssign for Vec3<T> {
     fn add_assign(&mut self, p: Self) {
         self.x += p.x;
         self.y += p.y;
@@ -140,7 +169,7 @@ impl AddAssign for Vec3 {
     }
 }
 
-impl Add for Vec3 {
+impl<T: Scalar> Add for Vec3<T> {
     type Output = Self;
 
     fn add(mut self, p: Self) -> Self {
@@ -149,7 +178,7 @@ impl Add for Vec3 {
     }
 }
 
-impl SubAssign for Vec3 {
+impl<T: Scalar> SubAssign for Vec3<T> {
     fn sub_assign(&mut self, p: Self) {
         self.x -= p.x;
         self.y -= p.y;
@@ -157,7 +186,7 @@ impl SubAssign for Vec3 {
     }
 }
 
-impl Sub for Vec3 {
+impl<T: Scalar> Sub for Vec3<T> {
     type Output = Self;
 
     fn sub(mut self, p: Self) -> Self {
@@ -166,44 +195,147 @@ impl Sub for Vec3 {
     }
 }
 
-impl MulAssign<f32> for Vec3 {
-    fn mul_assign(&mut self, a: f32) {
+impl<T: Scalar> MulAssign<T> for Vec3<T> {
+    fn mul_assign(&mut self, a: T) {
         self.x *= a;
         self.y *= a;
         self.z *= a;
     }
 }
 
-impl Mul<f32> for Vec3 {
+impl<T: Scalar> Mul<T> for Vec3<T> {
     type Output = Self;
 
-    fn mul(mut self, a: f32) -> Self {
+    fn mul(mut self, a: T) -> Self {
         self *= a;
         self
     }
 }
 
-impl Mul<Vec3> for Vec3 {
-    type Output = f32;
+impl<T: Scalar> Mul<Vec3<T>> for Vec3<T> {
+    type Output = T;
 
-    fn mul(self, a: Vec3) -> f32 {
+    fn mul(self, a: Vec3<T>) -> T {
         self.dot(&a)
     }
 }
 
-impl DivAssign<f32> for Vec3 {
-    fn div_assign(&mut self, a: f32) {
+impl<T: Scalar> DivAssign<T> for Vec3<T> {
+    fn div_assign(&mut self, a: T) {
         self.x /= a;
         self.y /= a;
         self.z /= a;
     }
 }
 
-impl Div<f32> for Vec3 {
+impl<T: Scalar> Div<T> for Vec3<T> {
     type Output = Self;
 
-    fn div(mut self, a: f32) -> Self {
+    fn div(mut self, a: T) -> Self {
         self /= a;
         self
     }
 }
+
+/// `Vec3f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Vec3f, inspectable)]
+#[derive(Debug, Clone, Copy)]
+pub struct Vec3fJs {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[cfg(feature = "wasm")]
+impl From<Vec3f> for Vec3fJs {
+    fn from(Vec3 { x, y, z }: Vec3f) -> Self {
+        Self { x, y, z }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Vec3fJs> for Vec3f {
+    fn from(Vec3fJs { x, y, z }: Vec3fJs) -> Self {
+        Self { x, y, z }
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Vec3f, Vec3fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Vec3f)]
+impl Vec3fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Vec3::new(x, y, z).into()
+    }
+
+    /// Calculates the 'scalar triple' product
+    /// (sometimes referred to as __[uvw]__) of the 3 given vectors.
+    ///
+    /// This is equivalent to __det([u v w])__ or
+    /// __(u cross v) dot w__
+    #[wasm_bindgen(js_name=scalarTriple)]
+    pub fn scalar_triple(u: &Vec3f, v: &Vec3f, w: &Vec3f) -> f32 {
+        Vec3::scalar_triple(u, v, w)
+    }
+
+    pub fn swap(&mut self, other: &mut Vec3f) {
+        let mut v = Vec3f::from(*self);
+        v.swap(other);
+        *self = v.into();
+    }
+
+    pub fn dot(&self, other: &Vec3f) -> f32 {
+        Vec3f::from(*self).dot(other)
+    }
+
+    pub fn cross(&self, other: &Vec3f) -> Vec3f {
+        Vec3f::from(*self).cross(other)
+    }
+
+    pub fn add(&self, other: &Vec3f) -> Vec3f {
+        Vec3f::from(*self) + *other
+    }
+
+    pub fn sub(&self, other: &Vec3f) -> Vec3f {
+        Vec3f::from(*self) - *other
+    }
+
+    pub fn mul(&self, other: f32) -> Vec3f {
+        Vec3f::from(*self).mul(other)
+    }
+
+    /// Returns a new vector with the same direction but a length of 1
+    pub fn normalized(&self) -> Vec3f {
+        Vec3f::from(*self).normalized()
+    }
+
+    /// Squared length of this vector
+    #[wasm_bindgen(js_name=lenSq)]
+    pub fn len_sq(&self) -> f32 {
+        Vec3f::from(*self).len_sq()
+    }
+
+    /// Length of this vector
+    ///
+    /// Calculated as `sqrt lenSq()`. If you only need to compare lengths of vectors (for example
+    /// when sorting) prefer using `lenSq`.
+    pub fn len(&self) -> f32 {
+        Vec3f::from(*self).len()
+    }
+
+    /// Casts `this` to a 4D homogeneous representation. `w` defaults to 0, a direction.
+    pub fn extend(&self, w: Option<f32>) -> Vec4f {
+        Vec3f::from(*self).extend(w)
+    }
+
+    /// Cast the Homogenous representation back to (x, y, z) representation
+    #[wasm_bindgen(js_name=fromHomogeneous)]
+    pub fn from_homogeneous(point: Vec4f) -> Vec3f {
+        Vec3::from_homogeneous(point)
+    }
+}
@@ -0,0 +1,297 @@
+use super::vec3::Vec3;
+#[cfg(feature = "wasm")]
+use super::vec3::Vec3f;
+use crate::scalar::{Float, Scalar};
+use core::mem::swap;
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "alloc")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// 4d vector of any `Scalar`, usually a homogeneous 3d point or direction
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "alloc", derive(Serialize, Deserialize))]
+pub struct Vec4<T = f32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+pub type Vec4f = Vec4<f32>;
+pub type Vec4d = Vec4<f64>;
+pub type Vec4i = Vec4<i32>;
+
+impl<T: Scalar> Vec4<T> {
+    pub fn new(x: T, y: T, z: T, w: T) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn swap(&mut self, other: &mut Self) {
+        swap(self, other);
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        *self + *other
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        *self - *other
+    }
+
+    pub fn mul(&self, other: T) -> Self {
+        *self * other
+    }
+
+    /// Squared length of this vector
+    pub fn len_sq(&self) -> T {
+        self.dot(self)
+    }
+
+    /// Drops the `w` coordinate
+    pub fn truncate(&self) -> Vec3<T> {
+        Vec3 {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl<T: Float> Vec4<T> {
+    /// Returns a new vector with the same direction but a length of 1
+    pub fn normalized(&self) -> Self {
+        let len_sq = self.dot(self);
+        *self / len_sq.sqrt()
+    }
+
+    /// Length of this vector
+    ///
+    /// Calculated as `sqrt lenSq()`. If you only need to compare lengths of vectors (for example
+    /// when sorting) prefer using `lenSq`.
+    pub fn len(&self) -> T {
+        self.dot(self).sqrt()
+    }
+}
+
+// `From` can not be implemented for arrays of a type parameter
+#[allow(clippy::from_over_into)]
+impl<T> Into<[T; 4]> for Vec4<T> {
+    fn into(self) -> [T; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl<T: Copy> Into<[T; 4]> for &Vec4<T> {
+    fn into(self) -> [T; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl<'a, T> Into<[&'a mut T; 4]> for &'a mut Vec4<T> {
+    fn into(self) -> [&'a mut T; 4] {
+        [&mut self.x, &mut self.y, &mut self.z, &mut self.w]
+    }
+}
+
+impl<T> From<[T; 4]> for Vec4<T> {
+    fn from([x, y, z, w]: [T; 4]) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+impl<T> Index<usize> for Vec4<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Vec4 index {} is out of range", index),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vec4<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Vec4 index {} is out of range", index),
+        }
+    }
+}
+
+impl<T: Scalar> AddAssign for Vec4<T> {
+    fn add_assign(&mut self, p: Self) {
+        self.x += p.x;
+        self.y += p.y;
+        self.z += p.z;
+        self.w += p.w;
+    }
+}
+
+impl<T: Scalar> Add for Vec4<T> {
+    type Output = Self;
+
+    fn add(mut self, p: Self) -> Self {
+        self += p;
+        self
+    }
+}
+
+impl<T: Scalar> SubAssign for Vec4<T> {
+    fn sub_assign(&mut self, p: Self) {
+        self.x -= p.x;
+        self.y -= p.y;
+        self.z -= p.z;
+        self.w -= p.w;
+    }
+}
+
+impl<T: Scalar> Sub for Vec4<T> {
+    type Output = Self;
+
+    fn sub(mut self, p: Self) -> Self {
+        self -= p;
+        self
+    }
+}
+
+impl<T: Scalar> MulAssign<T> for Vec4<T> {
+    fn mul_assign(&mut self, a: T) {
+        self.x *= a;
+        self.y *= a;
+        self.z *= a;
+        self.w *= a;
+    }
+}
+
+impl<T: Scalar> Mul<T> for Vec4<T> {
+    type Output = Self;
+
+    fn mul(mut self, a: T) -> Self {
+        self *= a;
+        self
+    }
+}
+
+impl<T: Scalar> Mul<Vec4<T>> for Vec4<T> {
+    type Output = T;
+
+    fn mul(self, a: Vec4<T>) -> T {
+        self.dot(&a)
+    }
+}
+
+impl<T: Scalar> DivAssign<T> for Vec4<T> {
+    fn div_assign(&mut self, a: T) {
+        self.x /= a;
+        self.y /= a;
+        self.z /= a;
+        self.w /= a;
+    }
+}
+
+impl<T: Scalar> Div<T> for Vec4<T> {
+    type Output = Self;
+
+    fn div(mut self, a: T) -> Self {
+        self /= a;
+        self
+    }
+}
+
+/// `Vec4f` as exported to JS
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+#[wasm_bindgen(js_name=Vec4f, inspectable)]
+#[derive(Debug, Clone, Copy)]
+pub struct Vec4fJs {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+#[cfg(feature = "wasm")]
+impl From<Vec4f> for Vec4fJs {
+    fn from(Vec4 { x, y, z, w }: Vec4f) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<Vec4fJs> for Vec4f {
+    fn from(Vec4fJs { x, y, z, w }: Vec4fJs) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+#[cfg(feature = "wasm")]
+wasm_abi_via!(Vec4f, Vec4fJs);
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class=Vec4f)]
+impl Vec4fJs {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Vec4::new(x, y, z, w).into()
+    }
+
+    pub fn swap(&mut self, other: &mut Vec4f) {
+        let mut v = Vec4f::from(*self);
+        v.swap(other);
+        *self = v.into();
+    }
+
+    pub fn dot(&self, other: &Vec4f) -> f32 {
+        Vec4f::from(*self).dot(other)
+    }
+
+    pub fn add(&self, other: &Vec4f) -> Vec4f {
+        Vec4f::from(*self) + *other
+    }
+
+    pub fn sub(&self, other: &Vec4f) -> Vec4f {
+        Vec4f::from(*self) - *other
+    }
+
+    pub fn mul(&self, other: f32) -> Vec4f {
+        Vec4f::from(*self).mul(other)
+    }
+
+    /// Returns a new vector with the same direction but a length of 1
+    pub fn normalized(&self) -> Vec4f {
+        Vec4f::from(*self).normalized()
+    }
+
+    /// Squared length of this vector
+    #[wasm_bindgen(js_name=lenSq)]
+    pub fn len_sq(&self) -> f32 {
+        Vec4f::from(*self).len_sq()
+    }
+
+    /// Length of this vector
+    ///
+    /// Calculated as `sqrt lenSq()`. If you only need to compare lengths of vectors (for example
+    /// when sorting) prefer using `lenSq`.
+    pub fn len(&self) -> f32 {
+        Vec4f::from(*self).len()
+    }
+
+    /// Drops the `w` coordinate
+    pub fn truncate(&self) -> Vec3f {
+        Vec4f::from(*self).truncate()
+    }
+}
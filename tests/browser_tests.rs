@@ -1,3 +1,5 @@
+#![cfg(target_arch = "wasm32")]
+
 pub mod implementations;
 
 use wasm_bindgen_test::*;
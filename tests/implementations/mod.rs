@@ -1,5 +1,34 @@
 //! Using this pattern will compile 1 single test binary instead of compiling one for each file, resulting in faster test compilation
+pub mod test_affine2;
+pub mod test_array_hex;
+pub mod test_bindings;
+pub mod test_decompose;
+pub mod test_fixed;
 pub mod test_hex;
+pub mod test_hex_ascii;
+pub mod test_hex_codec;
+pub mod test_hex_cone;
+pub mod test_hex_diff;
+pub mod test_hex_edge;
+pub mod test_hex_map;
+pub mod test_hex_moves;
+pub mod test_hex_ray;
+pub mod test_hex_smooth;
+pub mod test_hex_spatial;
+pub mod test_hex_tiled;
 pub mod test_mat2;
 pub mod test_mat3;
+pub mod test_mat4;
+pub mod test_poisson;
+pub mod test_projection;
+pub mod test_quat;
+pub mod test_rand;
+pub mod test_svg;
 pub mod test_vec2;
+
+/// Whether the vectors `a` and `b` are closer to each other than `epsilon`
+pub fn close<const N: usize>(a: impl Into<[f32; N]>, b: impl Into<[f32; N]>, epsilon: f32) -> bool {
+    let [a, b] = [a.into(), b.into()];
+    let d2: f32 = a.iter().zip(b.iter()).map(|(a, b)| (a - b) * (a - b)).sum();
+    d2.sqrt() < epsilon
+}
@@ -0,0 +1,97 @@
+use super::close;
+use cao_math::array::Array2f;
+use cao_math::mat::affine2::{Affine2, Affine2f, Affine2i};
+use cao_math::mat::mat2::Mat2f;
+use cao_math::mat::mat3::Mat3f;
+use cao_math::vec::vec2::Vec2;
+use std::f32::consts::FRAC_PI_2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn sample() -> Affine2f {
+    Affine2::new([[1., 2.], [-0.5, 3.]].into(), Vec2::new(4., -1.))
+}
+
+#[test]
+fn test_transform_point_and_vector() {
+    let a = sample();
+    let p = Vec2::new(2., 1.);
+    assert_eq!(a.transform_point(p), Vec2::new(5.5, 6.));
+    assert_eq!(a.transform_vector(p), Vec2::new(1.5, 7.));
+    assert_eq!(&a * p, a.transform_point(p));
+
+    let rot = Affine2f::rotation(FRAC_PI_2);
+    let p = rot.transform_point(Vec2::new(1., 0.));
+    assert!(close(p, Vec2::new(0., 1.), 1e-5), "{:?}", p);
+}
+
+#[test]
+fn test_matches_mat3() {
+    let a = sample();
+    let mat = a.to_mat3();
+    for &(x, y) in &[(0., 0.), (1., -2.), (3.5, 0.25)] {
+        let expected = mat.right_prod(&[x, y, 1.].into());
+        let p = a.transform_point(Vec2::new(x, y));
+        assert!(close(p, Vec2::new(expected.x, expected.y), 1e-5), "{:?}", p);
+    }
+
+    let b = Affine2f::rotation(0.7).compose(&Affine2f::translate(Vec2::new(-2., 5.)));
+    let composed = a.compose(&b).to_mat3();
+    assert!(composed.almost_equal(&mat.mat_mul(&b.to_mat3()), 1e-5));
+    assert_eq!(a.det(), mat.det());
+}
+
+#[test]
+fn test_conversions() {
+    let a = sample();
+    let mat: Mat3f = a.clone().into();
+    assert_eq!(Affine2::from_mat3(&mat), Some(a.clone()));
+    assert_eq!(
+        Affine2::from_mat3(&Mat3f::translate(Vec2::new(1., 2.))),
+        Some(Affine2::translate(Vec2::new(1., 2.)))
+    );
+
+    let mut projective = mat;
+    projective.set(0, 2, 1.);
+    assert_eq!(Affine2::from_mat3(&projective), None);
+
+    let linear: Mat2f = [[1., 2.], [3., 4.]].into();
+    let a = Affine2f::from(linear.clone());
+    assert_eq!(a.to_mat2(), Some(linear));
+    assert_eq!(sample().to_mat2(), None);
+}
+
+#[test]
+fn test_inverse() {
+    let a = sample();
+    let inv = a.inverted().expect("invertible");
+    assert!(a.compose(&inv).almost_equal(&Affine2::identity(), 1e-5));
+    assert!(inv.compose(&a).almost_equal(&Affine2::identity(), 1e-5));
+
+    let p = Vec2::new(-3., 7.);
+    let back = inv.transform_point(a.transform_point(p));
+    assert!(close(back, p, 1e-5), "{:?}", back);
+
+    assert!(Affine2f::scale(0.).inverted().is_none());
+}
+
+#[test]
+fn test_integer_affine() {
+    let a = Affine2i::translate(Vec2::new(1, 2)) * Affine2i::scale(3);
+    assert_eq!(a.transform_point(Vec2::new(1, -1)), Vec2::new(4, -1));
+}
+
+#[test]
+fn test_array_transform() {
+    let a = sample();
+    let mut points = Array2f::new();
+    points.push(Vec2::new(2., 1.));
+    points.push(Vec2::new(0., 0.));
+    let mut vectors = points.clone();
+
+    points.transform_points(&a);
+    assert_eq!(points.data, vec![Vec2::new(5.5, 6.), Vec2::new(4., -1.)]);
+
+    vectors.transform_vectors(&a);
+    assert_eq!(vectors.data, vec![Vec2::new(1.5, 7.), Vec2::new(0., 0.)]);
+}
@@ -0,0 +1,141 @@
+use cao_math::array::{Array2f, Array3f};
+use cao_math::hex::{self, Orientation as HexOrientation};
+use cao_math::vec::vec2::Vec2;
+use cao_math::vec::vec3::Vec3;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn axial_points() -> Array2f {
+    let mut points = Array2f::new();
+    for p in [
+        [3., 0.],
+        [0., 1.],
+        [-2., 2.],
+        [1., 0.],
+        [0., -1.],
+        [5., -5.],
+        [0., 0.],
+    ]
+    .iter()
+    {
+        points.push((*p).into());
+    }
+    points
+}
+
+#[test]
+fn test_sort_by_cube_distance_is_stable() {
+    let mut points = axial_points();
+
+    points.sort_by_cube_distance(&Vec2::new(0., 0.));
+
+    let expected: Vec<Vec2> = vec![
+        [0., 0.].into(),
+        [0., 1.].into(),
+        [1., 0.].into(),
+        [0., -1.].into(),
+        [-2., 2.].into(),
+        [3., 0.].into(),
+        [5., -5.].into(),
+    ];
+    assert_eq!(points.data, expected);
+}
+
+#[test]
+fn test_within_cube_distance() {
+    let points = axial_points();
+
+    let within = points.within_cube_distance(&Vec2::new(1., 0.), 2);
+
+    let expected: Vec<Vec2> = vec![
+        [3., 0.].into(),
+        [0., 1.].into(),
+        [1., 0.].into(),
+        [0., -1.].into(),
+        [0., 0.].into(),
+    ];
+    assert_eq!(within.data, expected);
+}
+
+#[test]
+fn test_nearest_k() {
+    let points = axial_points();
+    let center = Vec2::new(0., 0.);
+
+    let nearest = points.nearest(&center, 3);
+
+    let expected: Vec<Vec2> = vec![[0., 0.].into(), [0., 1.].into(), [1., 0.].into()];
+    assert_eq!(nearest.data, expected);
+    assert_eq!(points.nearest(&center, 100).data.len(), points.data.len());
+    assert!(points.nearest(&center, 0).data.is_empty());
+}
+
+#[test]
+fn test_nearest_k_cube_matches_sorting() {
+    let mut points = Array3f::new();
+    for p in axial_points().data.iter() {
+        points.push(hex::axial_to_cube(p));
+    }
+    let center = Vec3::new(1., -2., 1.);
+
+    let nearest = points.nearest(&center, 4);
+    points.sort_by_cube_distance(&center);
+
+    assert_eq!(nearest.data, points.data[..4].to_vec());
+}
+
+#[test]
+fn test_ring_buckets() {
+    let points = axial_points();
+
+    let buckets = points.ring_buckets(&Vec2::new(0., 0.), 2);
+
+    assert_eq!(buckets.len(), 3);
+    assert_eq!(buckets[0].data, vec![Vec2::new(0., 0.)]);
+    assert_eq!(buckets[1].data.len(), 3);
+    assert_eq!(buckets[2].data, vec![Vec2::new(-2., 2.)]);
+}
+
+#[test]
+fn test_cube_axial_round_trip() {
+    let points = axial_points();
+
+    let cubes = points.to_cube();
+
+    assert_eq!(cubes.data[0], Vec3::new(3., -3., 0.));
+    assert_eq!(cubes.to_axial().data, points.data);
+}
+
+#[test]
+fn test_bulk_pixel_axial_round_trip() {
+    for orientation in [HexOrientation::Pointy, HexOrientation::Flat].iter() {
+        let points = axial_points();
+
+        let mut pixels = points.clone();
+        pixels.axial_to_pixel(*orientation, 12.);
+        for (pixel, axial) in pixels.data.iter().zip(points.data.iter()) {
+            let m = orientation.axial_to_pixel();
+            assert_eq!(*pixel, m.right_prod(*axial) * 12.);
+        }
+
+        // move the points off the hex centers, but stay inside the hexes
+        pixels
+            .data
+            .iter_mut()
+            .for_each(|p| *p += Vec2::new(3., -2.));
+        pixels.pixel_to_axial(*orientation, 12.);
+
+        assert_eq!(pixels.data, points.data);
+    }
+}
+
+#[test]
+fn test_bulk_pixel_to_axial_pointy() {
+    let mut points = Array2f::new();
+    points.push(Vec2::new(3.2 * 12., -2.3 * 12.));
+
+    let mut rounded = points.clone();
+    rounded.pixel_to_axial(HexOrientation::Pointy, 12.);
+
+    assert_eq!(rounded.data, vec![Vec2::new(3., -2.)]);
+}
@@ -0,0 +1,91 @@
+//! Calls the exported classes through JS, to test the values passed via `wasm_abi_via!`
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+use cao_math::mat::mat2::Mat2f;
+use cao_math::mat::mat3::Mat3f;
+use cao_math::mat::mat4::Mat4f;
+use cao_math::vec::vec2::Vec2;
+use cao_math::vec::vec3::Vec3;
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn call(this: &JsValue, method: &str, args: &[&JsValue]) -> JsValue {
+    let method: Function = Reflect::get(this, &method.into())
+        .expect("method")
+        .dyn_into()
+        .expect("function");
+    let args: Array = args.iter().collect();
+    method.apply(this, &args).expect("call")
+}
+
+/// Calls a static method of the class of `instance`
+fn call_static(instance: &JsValue, method: &str, args: &[&JsValue]) -> JsValue {
+    let class = Reflect::get(instance, &"constructor".into()).expect("constructor");
+    call(&class, method, args)
+}
+
+fn get(this: &JsValue, field: &str) -> f64 {
+    Reflect::get(this, &field.into())
+        .expect("field")
+        .as_f64()
+        .expect("number")
+}
+
+fn at(mat: &JsValue, col: u32, row: u32) -> f64 {
+    call(mat, "at", &[&col.into(), &row.into()])
+        .as_f64()
+        .expect("number")
+}
+
+#[test]
+fn test_mut_ref_is_written_back() {
+    let a = JsValue::from(Vec2::new(1f32, 2.));
+    let b = JsValue::from(Vec2::new(3f32, 4.));
+    call(&a, "swap", &[&b]);
+    assert_eq!((get(&a, "x"), get(&a, "y")), (3., 4.));
+    assert_eq!((get(&b, "x"), get(&b, "y")), (1., 2.));
+
+    // the borrowed object is still usable afterwards
+    call(&b, "swap", &[&a]);
+    assert_eq!((get(&a, "x"), get(&a, "y")), (1., 2.));
+    assert_eq!((get(&b, "x"), get(&b, "y")), (3., 4.));
+}
+
+#[test]
+fn test_ref_is_not_consumed() {
+    let a = JsValue::from(Mat3f::translate(Vec2::new(1., 2.)));
+    let b = JsValue::from(Mat3f::scale(3.));
+    let ab = call(&a, "matMul", &[&b]);
+    assert_eq!(at(&ab, 0, 0), 3.);
+    assert_eq!(at(&ab, 2, 0), 1.);
+
+    // `b` and `a` were only borrowed
+    let ba = call(&b, "matMul", &[&a]);
+    assert_eq!(at(&ba, 2, 0), 3.);
+    assert_eq!(at(&ba, 2, 1), 6.);
+    assert_eq!(call(&a, "almostEqual", &[&a, &1e-6.into()]), JsValue::TRUE);
+
+    let m4 = call_static(&Mat4f::default().into(), "fromMat3", &[&b]);
+    assert_eq!(at(&m4, 1, 1), 3.);
+    assert_eq!(at(&b, 1, 1), 3.);
+}
+
+#[test]
+fn test_optional_values() {
+    let mat = JsValue::from(Mat2f::scale(2.));
+    let m3 = call(&mat, "toMat3", &[&JsValue::UNDEFINED, &JsValue::UNDEFINED]);
+    assert_eq!(at(&m3, 0, 0), 2.);
+    assert_eq!(at(&m3, 2, 2), 1.);
+    assert_eq!(at(&m3, 0, 2), 0.);
+
+    let column = JsValue::from(Vec3::new(5f32, 6., 7.));
+    let m3 = call(&mat, "toMat3", &[&column, &JsValue::UNDEFINED]);
+    assert_eq!(at(&m3, 2, 0), 5.);
+    assert_eq!(at(&m3, 2, 1), 6.);
+    assert_eq!(at(&m3, 2, 2), 7.);
+
+    let singular = JsValue::from(Mat3f::scale(0.));
+    assert!(call(&singular, "inverted", &[]).is_undefined());
+    let inv = call(&m3, "inverted", &[]);
+    assert_eq!(at(&inv, 0, 0), 0.5);
+}
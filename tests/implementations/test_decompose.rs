@@ -0,0 +1,110 @@
+use cao_math::mat::decompose::{Transform2, Transform2f};
+use cao_math::mat::mat3::{Mat3, Mat3d, Mat3f};
+use cao_math::vec::vec2::Vec2;
+use std::f32::consts::{FRAC_PI_2, PI};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn almost_equal(a: &Transform2f, b: &Transform2f) -> bool {
+    (a.translation - b.translation).len() < 1e-5
+        && (a.rotation - b.rotation).abs() < 1e-5
+        && (a.scale - b.scale).len() < 1e-5
+        && (a.shear - b.shear).abs() < 1e-5
+}
+
+#[test]
+fn test_decompose_trs() {
+    let mat = Mat3f::translate(Vec2::new(3., -4.))
+        .mat_mul(&Mat3f::rotation(FRAC_PI_2))
+        .mat_mul(&Mat3f::scale(2.));
+    let t = mat.decompose().expect("decomposable");
+    let expected = Transform2 {
+        translation: Vec2::new(3., -4.),
+        rotation: FRAC_PI_2,
+        scale: Vec2::new(2., 2.),
+        shear: 0.,
+    };
+    assert!(almost_equal(&t, &expected), "{:?}", t);
+}
+
+#[test]
+fn test_decompose_shear_and_reflection() {
+    // x' = x + 0.5 y, then y is mirrored
+    let mat = Mat3f::from([[1., 0., 0.], [0.5, -1., 0.], [0., 0., 1.]]);
+    let t = mat.decompose().expect("decomposable");
+    let expected = Transform2 {
+        translation: Vec2::new(0., 0.),
+        rotation: 0.,
+        scale: Vec2::new(1., -1.),
+        shear: -0.5,
+    };
+    assert!(almost_equal(&t, &expected), "{:?}", t);
+    assert!(Mat3::compose(&t).almost_equal(&mat, 1e-6));
+}
+
+#[test]
+fn test_compose_round_trip() {
+    let t = Transform2 {
+        translation: Vec2::new(-1.5, 7.),
+        rotation: -2.5,
+        scale: Vec2::new(0.5, 3.),
+        shear: 0.75,
+    };
+    let mat = Mat3d::compose(&t);
+    let back = mat.decompose().expect("decomposable");
+    assert!((back.translation - t.translation).len() < 1e-12);
+    assert!((back.rotation - t.rotation).abs() < 1e-12);
+    assert!((back.scale - t.scale).len() < 1e-12);
+    assert!((back.shear - t.shear).abs() < 1e-12);
+    assert!(Mat3::compose(&back).almost_equal(&mat, 1e-12));
+}
+
+#[test]
+fn test_decompose_invalid() {
+    assert!(Mat3f::scale(0.).decompose().is_none());
+    let mut projective = Mat3f::identity();
+    projective.set(0, 2, 0.5);
+    assert!(projective.decompose().is_none());
+    assert!(Mat3f::interpolate(&Mat3f::identity(), &projective, 0.5).is_none());
+}
+
+#[test]
+fn test_interpolate() {
+    let a = Mat3f::translate(Vec2::new(0., 2.));
+    let b = Mat3f::translate(Vec2::new(4., 0.))
+        .mat_mul(&Mat3f::rotation(FRAC_PI_2))
+        .mat_mul(&Mat3f::scale(3.));
+
+    assert!(Mat3f::interpolate(&a, &b, 0.)
+        .unwrap()
+        .almost_equal(&a, 1e-5));
+    assert!(Mat3f::interpolate(&a, &b, 1.)
+        .unwrap()
+        .almost_equal(&b, 1e-5));
+
+    let mid = Mat3f::interpolate(&a, &b, 0.5).unwrap();
+    let expected = Mat3f::translate(Vec2::new(2., 1.))
+        .mat_mul(&Mat3f::rotation(FRAC_PI_2 / 2.))
+        .mat_mul(&Mat3f::scale(2.));
+    assert!(mid.almost_equal(&expected, 1e-5), "{:?}", mid);
+}
+
+#[test]
+fn test_interpolate_shortest_arc() {
+    let a = Transform2f {
+        rotation: PI - 0.25,
+        ..Transform2::identity()
+    };
+    let b = Transform2f {
+        rotation: -PI + 0.25,
+        ..Transform2::identity()
+    };
+    // turns 0.5 radians through PI instead of 2 PI - 0.5 through 0
+    let mid = Transform2::interpolate(&a, &b, 0.5);
+    assert!((mid.rotation - PI).abs() < 1e-5, "{:?}", mid);
+    let back = Transform2::interpolate(&b, &a, 0.5);
+    assert!((back.rotation + PI).abs() < 1e-5, "{:?}", back);
+
+    let mat = Mat3f::interpolate(&Mat3f::compose(&a), &Mat3f::compose(&b), 0.5).unwrap();
+    assert!(mat.almost_equal(&Mat3f::rotation(PI), 1e-5), "{:?}", mat);
+}
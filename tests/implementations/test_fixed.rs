@@ -0,0 +1,155 @@
+use cao_math::fixed::{Fixed32, Fixed64};
+use cao_math::hex;
+use cao_math::mat::mat2::Mat2;
+use cao_math::scalar::{Float, Scalar};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn fx(x: f64) -> Fixed32 {
+    Fixed32::from_f64(x)
+}
+
+fn assert_close(a: Fixed32, b: f64, tolerance: f64) {
+    assert!((a.to_f64() - b).abs() <= tolerance, "{} != {}", a, b);
+}
+
+#[test]
+fn test_arithmetic() {
+    let a = fx(2.5);
+    let b = fx(-1.25);
+
+    assert_eq!(a + b, fx(1.25));
+    assert_eq!(a - b, fx(3.75));
+    assert_eq!(a * b, fx(-3.125));
+    assert_eq!(a / b, fx(-2.));
+    assert_eq!(Fixed32::from_int(3).to_bits(), 3 << 16);
+    assert_eq!(fx(-0.5).to_int(), -1);
+    assert_eq!(Fixed32::ONE, 1.into());
+}
+
+#[test]
+fn test_rounding() {
+    assert_eq!(fx(1.5).floor(), fx(1.));
+    assert_eq!(fx(-1.5).floor(), fx(-2.));
+    assert_eq!(fx(1.25).ceil(), fx(2.));
+    assert_eq!(fx(-1.25).ceil(), fx(-1.));
+    assert_eq!(fx(2.5).round(), fx(3.));
+    assert_eq!(fx(-2.5).round(), fx(-3.));
+    assert_eq!(fx(-2.25).round(), fx(-2.));
+}
+
+#[test]
+fn test_sqrt() {
+    assert_eq!(fx(16.).sqrt(), fx(4.));
+    assert_eq!(fx(0.25).sqrt(), fx(0.5));
+    assert_eq!(fx(-4.).sqrt(), Fixed32::ZERO);
+    assert_close(fx(2.).sqrt(), 2f64.sqrt(), 2e-5);
+    assert_eq!(
+        Fixed64::from_int(1 << 20).sqrt(),
+        Fixed64::from_int(1 << 10)
+    );
+}
+
+#[test]
+fn test_trigonometry() {
+    for i in -40..40 {
+        let x = i as f64 * 0.37;
+        assert_close(fx(x).sin(), x.sin(), 1e-4);
+        assert_close(fx(x).cos(), x.cos(), 1e-4);
+        let y = Fixed64::from_f64(x);
+        assert!((y.sin().to_f64() - x.sin()).abs() < 1e-8, "{}", x);
+        assert!((y.cos().to_f64() - x.cos()).abs() < 1e-8, "{}", x);
+    }
+    assert_close(fx(0.5).tan(), 0.5f64.tan(), 1e-4);
+    assert_close(fx(0.3).acos(), 0.3f64.acos(), 1e-4);
+    assert_close(fx(2.).acos(), 0., 1e-4);
+}
+
+#[test]
+fn test_atan2() {
+    for &(y, x) in [
+        (1., 1.),
+        (1., -1.),
+        (-1., -1.),
+        (-1., 1.),
+        (3., 0.5),
+        (-0.2, 7.),
+        (0., -2.),
+        (2., 0.),
+    ]
+    .iter()
+    {
+        let expected = f64::atan2(y, x);
+        assert_close(fx(y).atan2(fx(x)), expected, 1e-4);
+        let a = Fixed64::from_f64(y).atan2(Fixed64::from_f64(x));
+        assert!((a.to_f64() - expected).abs() < 1e-8, "{} {}", y, x);
+    }
+    assert_eq!(Fixed32::ZERO.atan2(Fixed32::ZERO), Fixed32::ZERO);
+}
+
+#[test]
+fn test_exp() {
+    for i in -20..20 {
+        let x = i as f64 * 0.4;
+        let expected = x.exp();
+        assert_close(fx(x).exp(), expected, 1e-4 * expected.max(1.));
+    }
+    assert_eq!(fx(20.).exp(), Fixed32::MAX);
+    assert_eq!(fx(-20.).exp(), Fixed32::ZERO);
+}
+
+#[test]
+fn test_bit_exact_results() {
+    // the results only depend on integer arithmetic, so they are the same on every target
+    let x = fx(1.);
+    assert_eq!(x.sin().to_bits(), 55_147);
+    assert_eq!(x.exp().to_bits(), 178_146);
+    assert_eq!(fx(0.5).atan2(fx(-2.)).to_bits(), 189_831);
+
+    let x = Fixed64::ONE;
+    assert_eq!(x.sin().to_bits(), 3_614_090_361);
+    assert_eq!(x.exp().to_bits(), 11_674_931_554);
+    let y = Fixed64::HALF.atan2(Fixed64::from_int(-2));
+    assert_eq!(y.to_bits(), 12_440_862_359);
+}
+
+#[test]
+fn test_vectors_and_matrices() {
+    let v = Vec2::new(fx(3.), fx(4.));
+
+    assert_eq!(v.len(), fx(5.));
+    assert_eq!(v.normalized(), Vec2::new(fx(0.6), fx(0.8)));
+    assert_eq!(
+        v.angle_between(&Vec2::new(fx(4.), fx(-3.))),
+        Fixed32::FRAC_PI_2
+    );
+
+    let mat = Mat2::from([[fx(4.), fx(2.)], [fx(7.), fx(6.)]]);
+    let inv = mat.inverted().unwrap();
+    assert!(mat.mat_mul(&inv).almost_equal(&Mat2::identity(), fx(0.001)));
+}
+
+#[test]
+fn test_axial_round() {
+    let p = hex::axial_round(Vec2::new(fx(1.4), fx(-0.3)));
+    assert_eq!(p, Vec2::new(fx(1.), fx(0.)));
+
+    let p = hex::axial_round(Vec2::new(fx(0.45), fx(0.45)));
+    let expected = hex::round_to_nearest_axial(0.45, 0.45);
+    assert_eq!(p, Vec2::new(fx(expected.x as f64), fx(expected.y as f64)));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "attempt to multiply with overflow")]
+fn test_mul_overflow() {
+    let _ = Fixed32::MAX * Fixed32::from_int(2);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "attempt to divide with overflow")]
+fn test_div_overflow() {
+    let _ = Fixed64::MAX / Fixed64::HALF;
+}
@@ -1,16 +1,15 @@
-#![cfg(target_arch = "wasm32")]
-
-use cao_math::hex::{self, Hexagon};
+use cao_math::hex;
 use cao_math::vec::vec2::Vec2;
-use wasm_bindgen_test::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
 
 const SIZE: f32 = 12.0;
 
-#[wasm_bindgen_test]
+#[test]
 fn test_pixel_to_axial_pointy() {
     let mat = hex::pixel_to_axial_pointy();
 
-    let pix_coord = Vec2::new(3.14 * SIZE, -2.3 * SIZE);
+    let pix_coord = Vec2::new(3.2 * SIZE, -2.3 * SIZE);
 
     let Vec2 { x: hx, y: hy } = mat.right_prod(pix_coord.into());
     let hex_coord = hex::round_to_nearest_axial(hx / SIZE, hy / SIZE);
@@ -18,11 +17,12 @@ fn test_pixel_to_axial_pointy() {
     assert_eq!(hex_coord, Vec2::new(3., -2.));
 }
 
-#[wasm_bindgen_test]
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+#[test]
 fn test_list_points_r_eq_3() {
     const R: f32 = 3.0;
 
-    let hex = Hexagon::from_radius(R);
+    let hex = hex::Hexagon::from_radius(R);
 
     let pts = hex.list_points();
     let pts = pts
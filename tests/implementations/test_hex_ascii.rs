@@ -0,0 +1,121 @@
+use cao_math::hex::{self, AsciiError, HexMap, Hexagon, Orientation};
+use cao_math::rand::Rng;
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn distance(a: [i32; 2], b: [i32; 2]) -> u32 {
+    let [a, b] = [a, b].map(|[q, r]| hex::axial_to_cube(&Vec2::new(q as f32, r as f32)));
+    hex::cube_distance(&a, &b)
+}
+
+fn rings(orientation: Orientation) -> String {
+    let hexagon = Hexagon {
+        center: Vec2::new(5., -3.),
+        radius: 2.,
+    };
+    let map = HexMap::from_fn(hexagon, |p| distance(p, [5, -3]));
+    map.to_ascii(orientation, |d| match d {
+        0 => 'A',
+        1 => '#',
+        _ => '.',
+    })
+}
+
+#[test]
+fn test_render_pointy() {
+    let expected = "  . . .\n . # # .\n. # A # .\n . # # .\n  . . .\n";
+
+    assert_eq!(rings(Orientation::Pointy), expected);
+}
+
+#[test]
+fn test_render_flat() {
+    let expected =
+        "    .\n  .   .\n.   #   .\n  #   #\n.   A   .\n  #   #\n.   #   .\n  .   .\n    .\n";
+
+    assert_eq!(rings(Orientation::Flat), expected);
+}
+
+#[test]
+fn test_roundtrip() {
+    let mut rng = Rng::new(39);
+    let hexagon = Hexagon {
+        center: Vec2::new(-2., 4.),
+        radius: 4.,
+    };
+    let symbols = ['.', '#', 'A', 'b', '~'];
+    let map = HexMap::from_fn(hexagon, |_| symbols[rng.range_i32(0, 5) as usize]);
+
+    for orientation in [Orientation::Pointy, Orientation::Flat].iter() {
+        let text = map.to_ascii(*orientation, |c| *c);
+
+        // the topmost hex is the first character of both drawings
+        let parsed = HexMap::from_ascii(&text, *orientation, [-2, 0], hexagon, ' ');
+
+        assert_eq!(parsed, Ok(map.clone()));
+    }
+}
+
+#[test]
+fn test_parse_indented_fixture() {
+    let text = r"
+        . #
+       . @ .
+        # .
+    ";
+
+    let tiles = hex::parse_ascii(text, Orientation::Pointy, [0, 0]).unwrap();
+
+    assert_eq!(
+        tiles,
+        vec![
+            ([0, 0], '.'),
+            ([1, 0], '#'),
+            ([-1, 1], '.'),
+            ([0, 1], '@'),
+            ([1, 1], '.'),
+            ([-1, 2], '#'),
+            ([0, 2], '.'),
+        ]
+    );
+    // '@' is the neighbour of every other tile
+    assert!(tiles.iter().all(|(p, _)| distance(*p, [0, 1]) <= 1));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        hex::parse_ascii(". .\n. .", Orientation::Pointy, [0, 0]),
+        Err(AsciiError::Misaligned { line: 1, column: 0 })
+    );
+    assert_eq!(
+        hex::parse_ascii(". .", Orientation::Flat, [0, 0]),
+        Err(AsciiError::Misaligned { line: 0, column: 2 })
+    );
+    assert_eq!(
+        HexMap::from_ascii(
+            ". . . .",
+            Orientation::Pointy,
+            [0, 0],
+            Hexagon::from_radius(1.),
+            ' '
+        ),
+        Err(AsciiError::OutsideMap([0, 0]))
+    );
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+#[test]
+fn test_render_hex_ascii() {
+    use cao_math::array::Array2f;
+
+    let mut points = Array2f::new();
+    points.push(Vec2::new(0., 0.));
+    points.push(Vec2::new(1., 0.));
+    assert_eq!(
+        hex::render_hex_ascii(&points, "ab", Orientation::Pointy).unwrap(),
+        "a b\n"
+    );
+    assert!(hex::render_hex_ascii(&points, "a", Orientation::Pointy).is_err());
+}
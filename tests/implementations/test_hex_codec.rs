@@ -0,0 +1,190 @@
+use cao_math::hex::{self, CodecError, Encoding, HexMap, Hexagon};
+use cao_math::rand::Rng;
+use cao_math::vec::vec2::Vec2;
+use std::collections::HashSet;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn hexagon() -> Hexagon {
+    Hexagon {
+        center: Vec2::new(4., -2.),
+        radius: 3.,
+    }
+}
+
+#[test]
+fn test_iter_spiral() {
+    let hexagon = hexagon();
+
+    let spiral = hexagon.iter_spiral().collect::<Vec<_>>();
+
+    assert_eq!(spiral.len(), hexagon.num_points());
+    assert_eq!(spiral[0], [4, -2]);
+    assert_eq!(spiral[1], [5, -2]);
+    let unique = spiral.iter().collect::<HashSet<_>>();
+    let expected = hexagon.iter_points().collect::<Vec<_>>();
+    assert!(expected.iter().all(|p| unique.contains(p)));
+}
+
+#[test]
+fn test_roundtrip() {
+    let mut rng = Rng::new(35);
+    let floats = HexMap::from_fn(hexagon(), |_| rng.range_f32(-10., 10.));
+    let tiles = HexMap::from_fn(hexagon(), |_| rng.range_i32(0, 4) as u8);
+    let mask = HexMap::from_fn(hexagon(), |_| rng.next_f32() < 0.3);
+
+    for encoding in [Encoding::Raw, Encoding::RunLength].iter() {
+        let bytes = floats.encode(*encoding, None).unwrap();
+        assert_eq!(HexMap::decode(&bytes, None), Ok(floats.clone()));
+
+        let bytes = tiles.encode(*encoding, None).unwrap();
+        assert_eq!(HexMap::decode(&bytes, None), Ok(tiles.clone()));
+
+        let bytes = mask.encode(*encoding, None).unwrap();
+        assert_eq!(HexMap::decode(&bytes, None), Ok(mask.clone()));
+        assert_eq!(hex::decode_hexagon(&bytes), Ok(hexagon()));
+    }
+}
+
+#[test]
+fn test_sizes() {
+    let mask = HexMap::filled(hexagon(), true);
+    let header = 18;
+
+    // 37 hexes, one bit each
+    let raw = mask.encode(Encoding::Raw, None).unwrap();
+    assert_eq!(raw.len(), header + 5);
+    // a single run of 37 trues
+    let runs = mask.encode(Encoding::RunLength, None).unwrap();
+    assert_eq!(runs.len(), header + 2);
+
+    let tiles = HexMap::filled(hexagon(), 7u8);
+    let raw = tiles.encode(Encoding::Raw, None).unwrap();
+    assert_eq!(raw.len(), header + 37);
+}
+
+#[test]
+fn test_delta() {
+    let previous = HexMap::from_fn(hexagon(), |[q, r]| (q * 7 + r) as f32);
+    let mut current = previous.clone();
+    *current.get_mut([4, -2]).unwrap() = -1.;
+
+    let delta = current
+        .encode(Encoding::RunLength, Some(&previous))
+        .unwrap();
+    let full = current.encode(Encoding::RunLength, None).unwrap();
+
+    assert!(delta.len() < full.len() / 4);
+    assert_eq!(HexMap::decode(&delta, Some(&previous)), Ok(current.clone()));
+    assert_eq!(
+        HexMap::<f32>::decode(&delta, None),
+        Err(CodecError::MissingSnapshot)
+    );
+    let other = HexMap::filled(Hexagon::from_radius(3.), 0.);
+    assert_eq!(
+        HexMap::decode(&delta, Some(&other)),
+        Err(CodecError::SnapshotMismatch)
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    let tiles = HexMap::filled(hexagon(), 3u8);
+    let bytes = tiles.encode(Encoding::Raw, None).unwrap();
+
+    assert_eq!(
+        HexMap::<u8>::decode(&bytes[..bytes.len() - 1], None),
+        Err(CodecError::UnexpectedEnd)
+    );
+    let mut longer = bytes.clone();
+    longer.push(0);
+    assert_eq!(
+        HexMap::<u8>::decode(&longer, None),
+        Err(CodecError::TrailingBytes)
+    );
+    assert_eq!(
+        HexMap::<f32>::decode(&bytes, None),
+        Err(CodecError::KindMismatch {
+            expected: 2,
+            found: 1
+        })
+    );
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert_eq!(
+        HexMap::<u8>::decode(&wrong_magic, None),
+        Err(CodecError::InvalidMagic)
+    );
+    let mut wrong_version = bytes.clone();
+    wrong_version[3] = 200;
+    assert_eq!(
+        HexMap::<u8>::decode(&wrong_version, None),
+        Err(CodecError::UnsupportedVersion(200))
+    );
+
+    let runs = tiles.encode(Encoding::RunLength, None).unwrap();
+    let mut too_long = runs.clone();
+    too_long[18] = 38;
+    assert_eq!(
+        HexMap::<u8>::decode(&too_long, None),
+        Err(CodecError::InvalidRunLength)
+    );
+}
+
+#[test]
+fn test_untrusted_header() {
+    let single = Hexagon {
+        center: Vec2::new(0., 0.),
+        radius: 0.,
+    };
+    let runs = HexMap::filled(single, 7u8)
+        .encode(Encoding::RunLength, None)
+        .unwrap();
+    assert_eq!(&runs[18..], &[1, 7]);
+
+    // the count 1 with extra bits above the 32nd
+    let mut overflow = runs[..18].to_vec();
+    overflow.extend_from_slice(&[0x81, 0x80, 0x80, 0x80, 0x10, 7]);
+    assert_eq!(
+        HexMap::<u8>::decode(&overflow, None),
+        Err(CodecError::InvalidRunLength)
+    );
+
+    // claims the largest hexagon without the data for it
+    let mut huge = runs[..18].to_vec();
+    huge[14..18].copy_from_slice(&hex::MAX_ENCODED_RADIUS.to_le_bytes());
+    assert_eq!(
+        HexMap::<u8>::decode(&huge, None),
+        Err(CodecError::UnexpectedEnd)
+    );
+}
+
+#[test]
+fn test_invalid_hexagon() {
+    // the hexagon is checked before the number of tiles
+    let encode = |center: Vec2, radius: f32| {
+        hex::encode_tiles::<u8>(&Hexagon { center, radius }, &[], Encoding::RunLength, None)
+    };
+    assert_eq!(
+        encode(Vec2::new(0.5, 0.), 2.),
+        Err(CodecError::InvalidHexagon)
+    );
+    assert_eq!(
+        encode(Vec2::new(0., 0.), 1.5),
+        Err(CodecError::InvalidHexagon)
+    );
+    assert_eq!(
+        encode(Vec2::new(0., 0.), -1.),
+        Err(CodecError::InvalidHexagon)
+    );
+    assert_eq!(
+        encode(Vec2::new(3e9, 0.), 1.),
+        Err(CodecError::InvalidHexagon)
+    );
+    let radius = hex::MAX_ENCODED_RADIUS + 1;
+    assert_eq!(
+        encode(Vec2::new(0., 0.), radius as f32),
+        Err(CodecError::RadiusTooLarge(radius))
+    );
+}
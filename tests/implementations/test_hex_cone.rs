@@ -0,0 +1,114 @@
+use cao_math::array::Array2f;
+use cao_math::hex::{self, HexDirection, HexRegion, Hexagon, Orientation};
+use cao_math::vec::vec2::Vec2;
+use std::f32::consts::PI;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn distance(a: &Vec2, b: &Vec2) -> u32 {
+    hex::cube_distance(&hex::axial_to_cube(a), &hex::axial_to_cube(b))
+}
+
+#[test]
+fn test_iter_ring() {
+    let hexagon = Hexagon {
+        center: Vec2::new(2., -1.),
+        radius: 3.,
+    };
+
+    let ring = hexagon.iter_ring().collect::<Vec<_>>();
+
+    assert_eq!(ring.len(), 18);
+    for (k, d) in HexDirection::ALL.iter().enumerate() {
+        let [dq, dr] = d.axial();
+        assert_eq!(ring[k * 3], [2 + 3 * dq, -1 + 3 * dr]);
+    }
+    for [q, r] in ring.iter() {
+        let p = Vec2::new(*q as f32, *r as f32);
+        assert_eq!(distance(&p, &hexagon.center), 3);
+    }
+    let single = Hexagon::from_radius(0.).iter_ring().collect::<Vec<_>>();
+    assert_eq!(single, vec![[0, 0]]);
+}
+
+#[test]
+fn test_wedge_sizes() {
+    let origin = Vec2::new(1., 1.);
+
+    let narrow = hex::hex_wedge(&origin, HexDirection::West, 0.5, 3);
+    let wide = hex::hex_wedge(&origin, HexDirection::West, 1., 3);
+    let full = hex::hex_wedge(&origin, HexDirection::West, 3., 3);
+
+    assert_eq!(narrow.data.len(), 1 + 3 + 3);
+    assert_eq!(wide.data.len(), 3 + 5 + 7);
+    assert_eq!(full.data.len(), 36);
+    assert_eq!(narrow.data[0], Vec2::new(0., 1.));
+    for p in wide.data.iter() {
+        assert!(p.x <= origin.x, "{:?}", p);
+        assert!(distance(p, &origin) <= 3);
+    }
+}
+
+#[test]
+fn test_sixty_degree_wedges_cover_the_disk() {
+    let origin = Vec2::new(0., 0.);
+
+    let mut region = HexRegion::new();
+    for d in HexDirection::ALL.iter() {
+        region = region.union(&HexRegion::wedge(&origin, *d, 0.5, 4));
+    }
+
+    assert_eq!(region.len(), 60);
+    assert!(!region.contains(&origin));
+}
+
+#[test]
+fn test_cone_along_hex_direction() {
+    for orientation in [Orientation::Pointy, Orientation::Flat].iter() {
+        let origin = Vec2::new(-2., 3.);
+        let east = orientation.hex_center(Vec2::new(1., 0.), 1.);
+        let angle = east.y.atan2(east.x);
+
+        let cone = hex::hex_cone(&origin, angle, 0.01, 4, *orientation);
+        let all = hex::hex_cone(&origin, angle, PI, 4, *orientation);
+
+        let expected = (1..=4)
+            .map(|n| Vec2::new(origin.x + n as f32, origin.y))
+            .collect::<Vec<_>>();
+        assert_eq!(cone.data, expected);
+        assert_eq!(all.data.len(), 60);
+    }
+}
+
+#[test]
+fn test_cone_region() {
+    let origin = Vec2::new(0., 0.);
+
+    let region = HexRegion::cone(&origin, PI / 2., PI / 3., 2, Orientation::Pointy);
+
+    // pointing down on the screen, the hexes with positive `r`
+    assert!(!region.is_empty());
+    for p in region.to_array().data.iter() {
+        assert!(p.y > 0., "{:?}", p);
+    }
+}
+
+#[test]
+fn test_region_set_operations() {
+    let mut points = Array2f::new();
+    for p in [[0., 0.], [1., 0.], [0., 0.], [2., -1.]].iter() {
+        points.push((*p).into());
+    }
+    let a = HexRegion::from_points(&points);
+    let mut b = HexRegion::new();
+    assert!(b.add(&Vec2::new(1., 0.)));
+    assert!(b.add(&Vec2::new(5., 5.)));
+    assert!(!b.add(&Vec2::new(5., 5.)));
+
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.union(&b).len(), 4);
+    assert_eq!(a.intersection(&b).to_array().data, vec![Vec2::new(1., 0.)]);
+    assert_eq!(a.difference(&b).len(), 2);
+    assert!(b.remove(&Vec2::new(5., 5.)));
+    assert!(!b.contains(&Vec2::new(5., 5.)));
+}
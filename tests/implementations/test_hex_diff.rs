@@ -0,0 +1,114 @@
+use cao_math::hex::{CodecError, HexMap, HexPatch, Hexagon, PatchError};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn hexagon() -> Hexagon {
+    Hexagon {
+        center: Vec2::new(-3., 5.),
+        radius: 4.,
+    }
+}
+
+fn snapshots() -> (HexMap<u8>, HexMap<u8>) {
+    let previous = HexMap::from_fn(hexagon(), |[q, r]| (q + 2 * r) as u8);
+    let mut current = previous.clone();
+    *current.get_mut([-3, 5]).unwrap() = 100;
+    *current.get_mut([1, 3]).unwrap() = 200;
+    (previous, current)
+}
+
+#[test]
+fn test_diff_and_apply() {
+    let (previous, current) = snapshots();
+
+    let patch = previous.diff(&current).unwrap();
+
+    assert_eq!(patch.len(), 2);
+    let mut changes = patch.iter().collect::<Vec<_>>();
+    changes.sort();
+    assert_eq!(changes, vec![([-3, 5], 100), ([1, 3], 200)]);
+    assert_eq!(patch.checksum_before(), previous.checksum());
+    assert_eq!(patch.checksum_after(), current.checksum());
+
+    let mut map = previous.clone();
+    patch.apply(&mut map).unwrap();
+    assert_eq!(map, current);
+}
+
+#[test]
+fn test_empty_diff() {
+    let (previous, _) = snapshots();
+
+    let patch = previous.diff(&previous).unwrap();
+
+    assert!(patch.is_empty());
+    assert_eq!(patch.checksum_before(), patch.checksum_after());
+}
+
+#[test]
+fn test_checksum_detects_changes() {
+    let (previous, current) = snapshots();
+    assert_ne!(previous.checksum(), current.checksum());
+
+    let moved = HexMap::from_values(Hexagon::from_radius(4.), previous.values().to_vec()).unwrap();
+    assert_ne!(previous.checksum(), moved.checksum());
+}
+
+#[test]
+fn test_apply_to_wrong_map() {
+    let (previous, current) = snapshots();
+    let patch = previous.diff(&current).unwrap();
+
+    // applying twice: the map is no longer the one the patch was made for
+    let mut map = current.clone();
+    assert_eq!(
+        patch.apply(&mut map),
+        Err(PatchError::ChecksumMismatch {
+            expected: previous.checksum(),
+            found: current.checksum(),
+        })
+    );
+    assert_eq!(map, current);
+
+    let mut other = HexMap::filled(Hexagon::from_radius(4.), 0);
+    assert_eq!(patch.apply(&mut other), Err(PatchError::ShapeMismatch));
+    assert_eq!(
+        previous.diff(&other).unwrap_err(),
+        PatchError::ShapeMismatch
+    );
+}
+
+#[test]
+fn test_bytes_roundtrip() {
+    let (previous, current) = snapshots();
+    let patch = previous.diff(&current).unwrap();
+
+    let bytes = patch.to_bytes().unwrap();
+
+    assert_eq!(HexPatch::from_bytes(&bytes), Ok(patch.clone()));
+    assert_eq!(
+        HexPatch::<u8>::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(CodecError::UnexpectedEnd)
+    );
+    // claims more changes than the input can hold
+    let mut count_too_large = bytes[..25].to_vec();
+    count_too_large.extend_from_slice(&[30, 0, 1]);
+    assert_eq!(
+        HexPatch::<u8>::from_bytes(&count_too_large),
+        Err(CodecError::UnexpectedEnd)
+    );
+    assert_eq!(
+        HexPatch::<f32>::from_bytes(&bytes),
+        Err(CodecError::KindMismatch {
+            expected: 2,
+            found: 1
+        })
+    );
+
+    let floats = HexMap::filled(hexagon(), 1.5f32);
+    let mut changed = floats.clone();
+    *changed.get_mut([-3, 1]).unwrap() = -2.;
+    let patch = floats.diff(&changed).unwrap();
+    assert_eq!(HexPatch::from_bytes(&patch.to_bytes().unwrap()), Ok(patch));
+}
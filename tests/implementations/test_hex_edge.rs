@@ -0,0 +1,131 @@
+use cao_math::hex::{HexDirection, HexEdge, HexVertex, HexWalls, Orientation};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+const SIZE: f32 = 10.;
+
+fn hex_center(orientation: Orientation, [q, r]: [i32; 2]) -> Vec2 {
+    orientation.hex_center(Vec2::new(q as f32, r as f32), SIZE)
+}
+
+#[test]
+fn test_edges_are_canonical() {
+    let hex = [2, -3];
+    for d in HexDirection::ALL.iter() {
+        let from_neighbour = HexEdge::from_hex(d.neighbour(hex), d.opposite());
+
+        assert_eq!(HexEdge::from_hex(hex, *d), from_neighbour);
+        assert!(from_neighbour.direction().index() < 3);
+    }
+    assert_eq!(
+        HexEdge::new(0, 0, HexDirection::West),
+        HexEdge::new(-1, 0, HexDirection::East)
+    );
+}
+
+#[test]
+fn test_vertices_are_canonical() {
+    let hex = [-1, 4];
+    for k in 0..6 {
+        let a = HexVertex::from_hex(hex, k);
+        let b = HexVertex::from_hex(HexDirection::from_index(k).neighbour(hex), k + 2);
+        let c = HexVertex::from_hex(HexDirection::from_index(k + 1).neighbour(hex), k + 4);
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert!(a.corner() < 2);
+        assert!(a.hex_triple().contains(&hex));
+    }
+}
+
+#[test]
+fn test_neighbours_share_one_edge_and_two_vertices() {
+    let hex = [0, 0];
+    for d in HexDirection::ALL.iter() {
+        let neighbour = d.neighbour(hex);
+        let edges = HexEdge::of_hex(neighbour);
+        let vertices = HexVertex::of_hex(neighbour);
+
+        let shared_edges = HexEdge::of_hex(hex)
+            .iter()
+            .filter(|e| edges.contains(e))
+            .count();
+        let shared_vertices = HexVertex::of_hex(hex)
+            .iter()
+            .filter(|v| vertices.contains(v))
+            .count();
+
+        assert_eq!(shared_edges, 1);
+        assert_eq!(shared_vertices, 2);
+    }
+}
+
+#[test]
+fn test_adjacency() {
+    let edge = HexEdge::new(3, 1, HexDirection::SouthWest);
+    let vertex = HexVertex::new(3, 1, 4);
+
+    let mut edges = edge.adjacent_edges();
+    edges.sort();
+    edges.dedup();
+    assert_eq!(edges.len(), 4);
+    assert!(!edges.contains(&edge));
+
+    let mut vertices = vertex.adjacent_vertices();
+    vertices.sort();
+    vertices.dedup();
+    assert_eq!(vertices.len(), 3);
+    for e in vertex.edges() {
+        assert!(e.vertices().contains(&vertex));
+    }
+    for v in edge.vertices() {
+        assert!(v.edges().contains(&edge));
+    }
+}
+
+#[test]
+fn test_pixel_positions() {
+    for orientation in [Orientation::Pointy, Orientation::Flat].iter() {
+        let hex = [1, -2];
+        let center = hex_center(*orientation, hex);
+
+        for v in HexVertex::of_hex(hex).iter() {
+            let p = v.position(*orientation, SIZE);
+            for h in v.hex_triple().iter() {
+                let d = (p - hex_center(*orientation, *h)).len();
+                assert!((d - SIZE).abs() < 1e-3, "{:?} {}", orientation, d);
+            }
+        }
+        for e in HexEdge::of_hex(hex).iter() {
+            let ends = e.endpoints(*orientation, SIZE);
+            let len = (ends.data[0] - ends.data[1]).len();
+            assert!((len - SIZE).abs() < 1e-3);
+            let d = (e.center(*orientation, SIZE) - center).len();
+            assert!((d - SIZE * 3f32.sqrt() / 2.).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn test_walls() {
+    let mut walls = HexWalls::new();
+    let a = Vec2::new(0., 0.);
+    let b = Vec2::new(0., 1.);
+
+    assert!(walls.is_passable(&a, &b));
+    assert!(walls.add_between(&a, &b));
+    assert!(!walls.add_between(&b, &a));
+
+    assert!(!walls.is_passable(&a, &b));
+    assert!(!walls.is_passable(&b, &a));
+    assert!(!walls.is_passable(&a, &Vec2::new(2., 0.)));
+    assert!(walls.is_passable(&a, &Vec2::new(1., 0.)));
+
+    let neighbours = walls.passable_neighbours(&a);
+    assert_eq!(neighbours.data.len(), 5);
+    assert!(!neighbours.data.contains(&b));
+
+    assert!(walls.remove(&HexEdge::new(0, 1, HexDirection::NorthWest)));
+    assert!(walls.list_edges().is_empty());
+}
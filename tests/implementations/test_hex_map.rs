@@ -0,0 +1,119 @@
+use cao_math::hex::{self, Boundary, HexMap, HexMapf, Hexagon};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn hexagon(radius: f32) -> Hexagon {
+    Hexagon {
+        center: Vec2::new(0., 0.),
+        radius,
+    }
+}
+
+#[test]
+fn test_index_of_matches_iter_points() {
+    for hexagon in [
+        Hexagon::from_radius(0.),
+        Hexagon::from_radius(4.),
+        hexagon(3.),
+    ]
+    .iter()
+    {
+        for (i, p) in hexagon.iter_points().enumerate() {
+            assert_eq!(hexagon.index_of(p), Some(i), "{:?}", p);
+            assert_eq!(hexagon.nth_point(i), Some(p));
+        }
+        assert_eq!(hexagon.iter_points().count(), hexagon.num_points());
+    }
+
+    let hexagon = hexagon(3.);
+    assert_eq!(hexagon.index_of([4, 0]), None);
+    assert_eq!(hexagon.index_of([2, 2]), None);
+    assert_eq!(hexagon.index_of([-2, -2]), None);
+}
+
+#[test]
+fn test_get_set() {
+    let mut map = HexMapf::new(&Hexagon::from_radius(2.), None);
+
+    map.set(2, 2, 4.);
+
+    assert_eq!(map.get(2, 2), Some(4.));
+    assert_eq!(map.get(1, 2), Some(0.));
+    assert_eq!(map.get(0, 0), None);
+}
+
+#[test]
+fn test_identity_kernel() {
+    let map = HexMap::from_fn(hexagon(3.), |[q, r]| (q * 10 + r) as f32);
+    let kernel = HexMap::from_fn(hexagon(1.), |p| if p == [0, 0] { 1. } else { 0. });
+    let mut out = map.clone();
+
+    map.convolve_into(&kernel, Boundary::Constant, 0., &mut out);
+
+    assert_eq!(out, map);
+}
+
+#[test]
+fn test_blur_of_constant_map_is_constant() {
+    let map: HexMapf = HexMap::filled(hexagon(4.), 2.).into();
+
+    for boundary in [Boundary::Clamp, Boundary::Ignore].iter() {
+        let blurred = map.gaussian_blur(2, 1., *boundary, 0.);
+        for v in blurred.values() {
+            assert!((v - 2.).abs() < 1e-5, "{:?} {}", boundary, v);
+        }
+    }
+
+    let blurred = map.gaussian_blur(2, 1., Boundary::Constant, 0.);
+    assert!(blurred.get(0, 0).unwrap() > blurred.get(4, 0).unwrap());
+}
+
+#[test]
+fn test_gaussian_kernel_is_normalized() {
+    let kernel = hex::gaussian_kernel(3, 1.5);
+
+    let sum: f32 = kernel.values().iter().sum();
+
+    assert!((sum - 1.).abs() < 1e-5);
+    let center = kernel.get([0, 0]).unwrap();
+    assert!(kernel.values().iter().all(|w| w <= center));
+}
+
+#[test]
+fn test_gaussian_kernel_without_spread_is_identity() {
+    let identity = HexMap::from_fn(hexagon(2.), |p| if p == [0, 0] { 1. } else { 0. });
+
+    for sigma in [0., -1., 1e-30, f32::NAN].iter() {
+        assert_eq!(hex::gaussian_kernel(2, *sigma), identity, "{}", sigma);
+    }
+}
+
+#[test]
+fn test_diffusion_spreads_and_conserves() {
+    let mut map = HexMapf::new(&hexagon(3.), None);
+    map.set(3, 0, 6.);
+
+    map.diffuse(0.5, 0., Boundary::Ignore, 0.);
+
+    assert_eq!(map.get(3, 0), Some(6. - 3. * 0.5));
+    assert_eq!(map.get(2, 0), Some(0.5));
+    assert_eq!(map.get(1, 0), Some(0.));
+    let total: f32 = map.values().iter().sum();
+    assert!((total - 6.).abs() < 1e-5);
+
+    map.diffuse(0.5, 0.5, Boundary::Ignore, 0.);
+    let total: f32 = map.values().iter().sum();
+    assert!((total - 3.).abs() < 1e-5);
+}
+
+#[test]
+fn test_diffusion_with_constant_boundary() {
+    let mut map = HexMapf::new(&hexagon(1.), None);
+
+    map.diffuse(0.6, 0., Boundary::Constant, 1.);
+
+    // the edge hexes have 3 neighbours outside of the map
+    assert_eq!(map.get(1, 0), Some(0.3));
+    assert_eq!(map.get(0, 0), Some(0.));
+}
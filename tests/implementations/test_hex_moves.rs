@@ -0,0 +1,165 @@
+use cao_math::hex::{CodecError, HexDirection, HexMoves, PathError, MAX_PATH_STEPS};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn path() -> Vec<[i32; 2]> {
+    vec![
+        [2, -1],
+        [3, -1],
+        [4, -1],
+        [5, -1],
+        [5, 0],
+        [4, 1],
+        [3, 2],
+        [3, 1],
+    ]
+}
+
+#[test]
+fn test_runs() {
+    let moves = HexMoves::from_axial_path(&path()).unwrap();
+
+    assert_eq!(moves.start_axial(), [2, -1]);
+    assert_eq!(moves.end_axial(), [3, 1]);
+    assert_eq!(
+        moves.runs(),
+        &[
+            (HexDirection::East, 3),
+            (HexDirection::SouthEast, 1),
+            (HexDirection::SouthWest, 2),
+            (HexDirection::NorthWest, 1),
+        ]
+    );
+    assert_eq!(moves.len(), 7);
+    assert_eq!(moves.iter().collect::<Vec<_>>(), path());
+}
+
+#[test]
+fn test_single_hex() {
+    let moves = HexMoves::from_axial_path(&[[1, 1]]).unwrap();
+
+    assert!(moves.is_empty());
+    assert_eq!(moves.end_axial(), [1, 1]);
+    assert_eq!(moves.iter().collect::<Vec<_>>(), vec![[1, 1]]);
+}
+
+#[test]
+fn test_invalid_paths() {
+    assert_eq!(HexMoves::from_axial_path(&[]), Err(PathError::Empty));
+    assert_eq!(
+        HexMoves::from_axial_path(&[[0, 0], [1, 0], [2, 1]]),
+        Err(PathError::NotAdjacent { index: 2 })
+    );
+    // standing still is not a move
+    assert_eq!(
+        HexMoves::from_axial_path(&[[0, 0], [0, 0]]),
+        Err(PathError::NotAdjacent { index: 1 })
+    );
+}
+
+#[test]
+fn test_bytes_roundtrip() {
+    let moves = HexMoves::from_axial_path(&path()).unwrap();
+
+    let bytes = moves.to_bytes();
+
+    // header, start, run count and one byte per run
+    assert_eq!(bytes.len(), 4 + 8 + 1 + 4);
+    assert_eq!(HexMoves::from_bytes(&bytes), Ok(moves));
+}
+
+#[test]
+fn test_long_runs() {
+    let mut moves = HexMoves::new([0, 0]);
+    moves.push(HexDirection::West, 16);
+    moves.push(HexDirection::West, 1);
+    moves.push(HexDirection::NorthEast, 1000);
+
+    assert_eq!(moves.runs().len(), 2);
+    let bytes = moves.to_bytes();
+    assert_eq!(bytes.len(), 4 + 8 + 1 + 2 + 2);
+    let decoded = HexMoves::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.end_axial(), [983, -1000]);
+    assert_eq!(decoded, moves);
+}
+
+#[test]
+fn test_invalid_bytes() {
+    let bytes = HexMoves::from_axial_path(&path()).unwrap().to_bytes();
+
+    assert_eq!(
+        HexMoves::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(CodecError::UnexpectedEnd)
+    );
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        HexMoves::from_bytes(&trailing),
+        Err(CodecError::TrailingBytes)
+    );
+    let mut direction = bytes.clone();
+    direction[13] = 6;
+    assert_eq!(
+        HexMoves::from_bytes(&direction),
+        Err(CodecError::InvalidDirection(6))
+    );
+    let mut magic = bytes;
+    magic[0] = b'X';
+    assert_eq!(HexMoves::from_bytes(&magic), Err(CodecError::InvalidMagic));
+}
+
+#[test]
+fn test_path_too_long() {
+    let mut moves = HexMoves::new([0, 0]);
+    moves.push(HexDirection::East, MAX_PATH_STEPS);
+    let bytes = moves.to_bytes();
+    assert_eq!(HexMoves::from_bytes(&bytes), Ok(moves.clone()));
+
+    // a few bytes must not decode to billions of steps
+    moves.push(HexDirection::NorthEast, 1);
+    assert_eq!(
+        HexMoves::from_bytes(&moves.to_bytes()),
+        Err(CodecError::InvalidRunLength)
+    );
+    let mut moves = HexMoves::new([0, 0]);
+    for i in 0..8 {
+        moves.push(HexDirection::from_index(i % 2), 1 << 29);
+    }
+    let bytes = moves.to_bytes();
+    assert!(bytes.len() < 64);
+    assert_eq!(
+        HexMoves::from_bytes(&bytes),
+        Err(CodecError::InvalidRunLength)
+    );
+}
+
+#[test]
+fn test_end_of_long_run() {
+    let mut moves = HexMoves::new([-2_000_000_000, 0]);
+    moves.push(HexDirection::East, 3_000_000_000);
+    assert_eq!(moves.end_axial(), [1_000_000_000, 0]);
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn test_js_path() {
+    use cao_math::array::Array2f;
+    use cao_math::hex;
+    use cao_math::vec::vec2::Vec2;
+
+    let points = Array2f {
+        data: path()
+            .into_iter()
+            .map(|[q, r]| Vec2::new(q as f32, r as f32))
+            .collect(),
+    };
+
+    let bytes = hex::encode_hex_path(&points).unwrap();
+    let decoded = hex::decode_hex_path(&bytes).unwrap();
+
+    assert_eq!(decoded.data, points.data);
+    let moves = HexMoves::from_path(&points).unwrap();
+    assert_eq!(moves.directions(), vec![0, 5, 4, 2]);
+    assert_eq!(moves.counts(), vec![3, 1, 2, 1]);
+    assert_eq!(moves.end(), Vec2::new(3., 1.));
+}
@@ -0,0 +1,107 @@
+use cao_math::hex::{self, HexDirection, HexEdge, Orientation, RayHit};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+const SIZE: f32 = 8.;
+
+fn pixel_to_hex(orientation: Orientation, p: Vec2) -> [i32; 2] {
+    let Vec2 { x, y } = orientation.pixel_to_axial().right_prod(p / SIZE);
+    let Vec2 { x, y } = hex::round_to_nearest_axial(x, y);
+    [x as i32, y as i32]
+}
+
+fn collect_hits(
+    origin: Vec2,
+    direction: Vec2,
+    max_t: f32,
+    orientation: Orientation,
+) -> Vec<RayHit> {
+    let mut hits = Vec::new();
+    let stopped = hex::cast_ray(&origin, &direction, max_t, orientation, SIZE, |hit| {
+        hits.push(*hit);
+        false
+    });
+    assert!(stopped.is_none());
+    hits
+}
+
+#[test]
+fn test_ray_matches_dense_sampling() {
+    let rays = [
+        (Vec2::new(1.3, 2.1), Vec2::new(93.7, 41.2)),
+        (Vec2::new(-20.4, 7.7), Vec2::new(-3.1, -88.3)),
+        (Vec2::new(0.2, -0.3), Vec2::new(-61.9, 15.3)),
+    ];
+    for orientation in [Orientation::Pointy, Orientation::Flat].iter() {
+        for (origin, direction) in rays.iter() {
+            let hits = collect_hits(*origin, *direction, 1., *orientation);
+
+            let mut sampled: Vec<[i32; 2]> = Vec::new();
+            for i in 0..=10000 {
+                let p = *origin + *direction * (i as f32 / 10000.);
+                let h = pixel_to_hex(*orientation, p);
+                if sampled.last() != Some(&h) {
+                    sampled.push(h);
+                }
+            }
+            let traversed = hits.iter().map(|h| [h.q, h.r]).collect::<Vec<_>>();
+            assert_eq!(traversed, sampled);
+
+            assert_eq!(hits[0].t_enter, 0.);
+            assert_eq!(hits.last().unwrap().t_exit, 1.);
+            for hit in hits.iter() {
+                let mid = *origin + *direction * ((hit.t_enter + hit.t_exit) / 2.);
+                assert_eq!(pixel_to_hex(*orientation, mid), [hit.q, hit.r]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ray_reports_entry_edges() {
+    let hits = collect_hits(
+        Vec2::new(0.5, 0.5),
+        Vec2::new(100., 3.),
+        1.,
+        Orientation::Pointy,
+    );
+
+    assert_eq!(hits[0].direction(), None);
+    assert_eq!(hits[0].edge(), None);
+    for pair in hits.windows(2) {
+        assert_eq!(pair[0].t_exit, pair[1].t_enter);
+        let d = pair[1].direction().unwrap();
+        assert_eq!(d.neighbour([pair[0].q, pair[0].r]), [pair[1].q, pair[1].r]);
+        assert_eq!(
+            pair[1].edge(),
+            HexEdge::between(&pair[0].hex(), &pair[1].hex())
+        );
+    }
+    assert!(hits[1..]
+        .iter()
+        .all(|h| h.direction() == Some(HexDirection::East)
+            || h.direction() == Some(HexDirection::SouthEast)
+            || h.direction() == Some(HexDirection::NorthEast)));
+}
+
+#[test]
+fn test_ray_early_exit() {
+    let mut hits = Vec::new();
+
+    let stopped = hex::cast_ray(
+        &Vec2::new(0., 0.),
+        &Vec2::new(1., 0.3),
+        std::f32::INFINITY,
+        Orientation::Flat,
+        SIZE,
+        |hit| {
+            hits.push(*hit);
+            hits.len() == 4
+        },
+    );
+
+    assert_eq!(hits.len(), 4);
+    assert_eq!(stopped, Some(hits[3]));
+    assert!(hits[3].t_enter > 0.);
+}
@@ -0,0 +1,172 @@
+use super::close;
+use cao_math::array::Array2f;
+use cao_math::hex::{self, Orientation, SmoothPath, Smoothing};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn path() -> Vec<Vec2> {
+    // east, east, then south east twice
+    vec![
+        Vec2::new(0., 0.),
+        Vec2::new(1., 0.),
+        Vec2::new(2., 0.),
+        Vec2::new(2., 1.),
+        Vec2::new(2., 2.),
+    ]
+}
+
+fn centers() -> Vec<Vec2> {
+    path()
+        .into_iter()
+        .map(|p| Orientation::Pointy.hex_center(p, 10.))
+        .collect()
+}
+
+#[test]
+fn test_no_smoothing() {
+    let points = hex::smooth_hex_path(&path(), Orientation::Pointy, 10., Smoothing::None, 4);
+
+    assert_eq!(points, centers());
+}
+
+#[test]
+fn test_chaikin() {
+    let points = hex::smooth_hex_path(&path(), Orientation::Pointy, 10., Smoothing::Chaikin, 2);
+
+    // every iteration doubles the number of points
+    assert_eq!(points.len(), 20);
+    assert_eq!(points[0], centers()[0]);
+    assert_eq!(points[19], centers()[4]);
+}
+
+#[test]
+fn test_chaikin_iterations_are_capped() {
+    let points = hex::smooth_hex_path(
+        &path(),
+        Orientation::Pointy,
+        10.,
+        Smoothing::Chaikin,
+        u32::MAX,
+    );
+
+    assert_eq!(points.len(), 5 << hex::MAX_CHAIKIN_ITERATIONS);
+    assert_eq!(points[points.len() - 1], centers()[4]);
+}
+
+#[test]
+fn test_catmull_rom_passes_through_centers() {
+    let points = hex::smooth_hex_path(&path(), Orientation::Flat, 10., Smoothing::CatmullRom, 5);
+    let centers = path()
+        .into_iter()
+        .map(|p| Orientation::Flat.hex_center(p, 10.))
+        .collect::<Vec<_>>();
+
+    assert_eq!(points.len(), 4 * 5 + 1);
+    for (i, c) in centers.iter().enumerate() {
+        assert!(
+            close(points[i * 5], *c, 1e-3),
+            "{:?} {:?}",
+            points[i * 5],
+            c
+        );
+    }
+}
+
+#[test]
+fn test_rounded_corners() {
+    let centers = centers();
+    let points = hex::smooth_hex_path(
+        &path(),
+        Orientation::Pointy,
+        10.,
+        Smoothing::RoundedCorners,
+        4,
+    );
+
+    // only the turn at the third hex is rounded, from the middle of the edge before it to the
+    // middle of the edge after it
+    assert_eq!(points.len(), 2 + 5);
+    assert_eq!(points[0], centers[0]);
+    assert!(close(points[1], (centers[1] + centers[2]) * 0.5, 1e-3));
+    assert!(close(points[5], (centers[2] + centers[3]) * 0.5, 1e-3));
+    assert_eq!(points[6], centers[4]);
+    // the curve cuts the corner
+    assert!(points[1..6].iter().all(|p| !close(*p, centers[2], 1e-3)));
+
+    let straight = &path()[..3];
+    let points = hex::smooth_hex_path(
+        straight,
+        Orientation::Pointy,
+        10.,
+        Smoothing::RoundedCorners,
+        4,
+    );
+    assert_eq!(points.len(), 2);
+}
+
+#[test]
+fn test_arc_length() {
+    let points = Array2f {
+        data: vec![
+            Vec2::new(0., 0.),
+            Vec2::new(3., 0.),
+            Vec2::new(3., 0.),
+            Vec2::new(3., 4.),
+        ],
+    };
+    let path = SmoothPath::new(&points);
+
+    assert_eq!(path.length(), 7.);
+    assert_eq!(path.points().data.len(), 3);
+    assert_eq!(path.point_at(-1.), Some(Vec2::new(0., 0.)));
+    assert_eq!(path.point_at(1.5), Some(Vec2::new(1.5, 0.)));
+    assert_eq!(path.point_at(3.), Some(Vec2::new(3., 0.)));
+    assert_eq!(path.point_at(5.), Some(Vec2::new(3., 2.)));
+    assert_eq!(path.point_at(100.), Some(Vec2::new(3., 4.)));
+    assert_eq!(path.tangent_at(1.), Some(Vec2::new(1., 0.)));
+    assert_eq!(path.tangent_at(6.), Some(Vec2::new(0., 1.)));
+
+    let samples = path.sample_every(2.).data;
+    assert_eq!(
+        samples,
+        vec![
+            Vec2::new(0., 0.),
+            Vec2::new(2., 0.),
+            Vec2::new(3., 1.),
+            Vec2::new(3., 3.),
+            Vec2::new(3., 4.),
+        ]
+    );
+    let samples = path.sample(8).data;
+    assert_eq!(samples.len(), 8);
+    assert_eq!(samples[7], Vec2::new(3., 4.));
+
+    let empty = SmoothPath::new(&Array2f { data: vec![] });
+    assert_eq!(empty.point_at(0.), None);
+    assert_eq!(empty.tangent_at(0.), None);
+    assert_eq!(empty.sample_every(1.).data, vec![]);
+}
+
+#[test]
+fn test_constant_speed_along_curve() {
+    let path = SmoothPath::from_hex_path(
+        &Array2f { data: path() },
+        Orientation::Pointy,
+        10.,
+        Smoothing::CatmullRom,
+        16,
+    );
+
+    let samples = path.sample_every(1.).data;
+
+    // consecutive samples are 1 apart along the path, so at most 1 apart in a straight line,
+    // except for the last one which is the end of the path
+    let n = samples.len();
+    assert_eq!(n, path.length().ceil() as usize + 1);
+    assert!((samples[n - 1] - samples[n - 2]).len() <= 1.);
+    for w in samples[..n - 1].windows(2) {
+        let d = (w[1] - w[0]).len();
+        assert!(d <= 1. + 1e-3 && d > 0.9, "{}", d);
+    }
+}
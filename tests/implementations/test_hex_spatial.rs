@@ -0,0 +1,127 @@
+use cao_math::hex::{self, HexSpatialIndex, Orientation};
+use cao_math::rand::Rng;
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn distance(a: &Vec2, b: &Vec2) -> u32 {
+    hex::cube_distance(&hex::axial_to_cube(a), &hex::axial_to_cube(b))
+}
+
+fn random_index(rng: &mut Rng, count: u32, spread: i32) -> HexSpatialIndex {
+    let mut index = HexSpatialIndex::new();
+    for id in 0..count {
+        let p = Vec2::new(
+            rng.range_i32(-spread, spread) as f32,
+            rng.range_i32(-spread, spread) as f32,
+        );
+        index.insert(id, &p);
+    }
+    index
+}
+
+/// Ids ordered by distance from `center`, then id
+fn brute_force(index: &HexSpatialIndex, center: &Vec2) -> Vec<(u32, u32)> {
+    let mut keys = index
+        .iter()
+        .map(|(id, [q, r])| (distance(&Vec2::new(q as f32, r as f32), center), id))
+        .collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn test_insert_move_remove() {
+    let mut index = HexSpatialIndex::new();
+
+    assert!(index.insert(1, &Vec2::new(0., 0.)));
+    assert!(index.insert(2, &Vec2::new(0., 0.)));
+    assert!(index.insert(3, &Vec2::new(1., -1.)));
+    assert!(!index.insert(3, &Vec2::new(2., -1.)));
+    assert_eq!(index.len(), 3);
+    assert_eq!(index.at(&Vec2::new(0., 0.)), vec![1, 2]);
+    assert!(index.at(&Vec2::new(1., -1.)).is_empty());
+    assert_eq!(index.at(&Vec2::new(2., -1.)), vec![3]);
+
+    assert!(index.move_to(1, &Vec2::new(2., -1.)));
+    assert!(!index.move_to(4, &Vec2::new(2., -1.)));
+    assert_eq!(index.position(1), Some(Vec2::new(2., -1.)));
+    assert_eq!(index.position(4), None);
+    assert_eq!(index.at(&Vec2::new(0., 0.)), vec![2]);
+    assert_eq!(index.at(&Vec2::new(2., -1.)), vec![1, 3]);
+
+    assert!(index.remove(2));
+    assert!(!index.remove(2));
+    assert!(index.at(&Vec2::new(0., 0.)).is_empty());
+    assert_eq!(index.position(2), None);
+    assert_eq!(index.len(), 2);
+}
+
+#[test]
+fn test_within_matches_brute_force() {
+    let mut rng = Rng::new(34);
+    let index = random_index(&mut rng, 200, 12);
+    let center = Vec2::new(3., -2.);
+
+    // small radii walk the rings, large ones scan the entities
+    for radius in [0, 1, 2, 5, 30, 40_000, u32::MAX].iter() {
+        let expected = brute_force(&index, &center)
+            .into_iter()
+            .filter(|(d, _)| d <= radius)
+            .map(|(_, id)| id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(index.within(&center, *radius), expected);
+    }
+}
+
+#[test]
+fn test_nearest_matches_brute_force() {
+    let mut rng = Rng::new(35);
+    let dense = random_index(&mut rng, 200, 8);
+    // few entities, far apart: too sparse to be worth walking the rings
+    let sparse = random_index(&mut rng, 10, 1000);
+    let center = Vec2::new(-1., 2.);
+
+    for index in [dense, sparse].iter() {
+        let all = brute_force(index, &center);
+        for k in [0, 1, 3, 7, 10, 500].iter() {
+            let nearest = index.nearest(&center, *k);
+
+            assert_eq!(nearest.len(), (*k).min(index.len()));
+            // ties at the furthest distance are broken by id
+            let expected = all.iter().take(*k).map(|(_, id)| *id).collect::<Vec<_>>();
+            assert_eq!(nearest, expected);
+        }
+    }
+}
+
+#[test]
+fn test_in_pixel_rect_matches_brute_force() {
+    let mut rng = Rng::new(36);
+    let index = random_index(&mut rng, 300, 15);
+    let size = 2.;
+
+    for orientation in [Orientation::Pointy, Orientation::Flat].iter() {
+        let to_pixel = orientation.axial_to_pixel();
+        // a small rectangle is searched hex by hex, a large one by scanning the entities
+        for [min, max] in [
+            [Vec2::new(-5., -4.), Vec2::new(6., 3.)],
+            [Vec2::new(-100., -80.), Vec2::new(30., 200.)],
+        ]
+        .iter()
+        {
+            let mut expected = index
+                .iter()
+                .filter(|(_, [q, r])| {
+                    let c = to_pixel.right_prod(Vec2::new(*q as f32, *r as f32)) * size;
+                    min.x <= c.x && c.x <= max.x && min.y <= c.y && c.y <= max.y
+                })
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+            expected.sort();
+
+            assert_eq!(index.in_pixel_rect(min, max, *orientation, size), expected);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use cao_math::hex::{
+    HexDirection, HexMap, Hexagon, Orientation, StaggerIndex, TiledError, TiledMap,
+};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+const POINTY_ODD: &str = r#"{
+    "type": "map",
+    "version": "1.10",
+    "tiledversion": "1.10.2",
+    "orientation": "hexagonal",
+    "renderorder": "right-down",
+    "infinite": false,
+    "width": 3,
+    "height": 2,
+    "tilewidth": 28,
+    "tileheight": 32,
+    "hexsidelength": 16,
+    "staggeraxis": "y",
+    "staggerindex": "odd",
+    "nextlayerid": 3,
+    "nextobjectid": 2,
+    "tilesets": [{ "firstgid": 1, "source": "terrain.tsx" }],
+    "layers": [
+        {
+            "id": 1,
+            "type": "tilelayer",
+            "name": "terrain",
+            "data": [1, 0, 2, 0, 3, 0],
+            "width": 3,
+            "height": 2,
+            "x": 0,
+            "y": 0,
+            "opacity": 1,
+            "visible": true
+        },
+        {
+            "id": 2,
+            "type": "objectgroup",
+            "name": "spawns",
+            "draworder": "topdown",
+            "objects": [
+                { "id": 1, "name": "spawn", "x": 14, "y": 16, "width": 0, "height": 0, "point": true }
+            ],
+            "opacity": 1,
+            "visible": true
+        }
+    ]
+}"#;
+
+fn maps() -> Vec<TiledMap> {
+    let mut maps = vec![];
+    for orientation in [Orientation::Pointy, Orientation::Flat].iter() {
+        for index in [StaggerIndex::Odd, StaggerIndex::Even].iter() {
+            maps.push(TiledMap::new(10, 10, *orientation, *index, 20).unwrap());
+        }
+    }
+    maps
+}
+
+#[test]
+fn test_load() {
+    let map = TiledMap::from_json(POINTY_ODD).unwrap();
+
+    assert_eq!(map.orientation(), Orientation::Pointy);
+    let mut tiles = map.tiles("terrain").unwrap();
+    tiles.sort();
+    // odd rows are shifted right: (1, 1) is the south east neighbour of (1, 0)
+    assert_eq!(tiles, vec![([0, 0], 1), ([1, 1], 3), ([2, 0], 2)]);
+    assert!(map.tiles("spawns").is_none());
+    assert!(map.tiles("missing").is_none());
+
+    let positions = map.object_positions("spawns", 10.).unwrap();
+    assert_eq!(positions.len(), 1);
+    assert!(positions[0].len() < 1e-4, "{:?}", positions);
+}
+
+#[test]
+fn test_json_roundtrip() {
+    let map = TiledMap::from_json(POINTY_ODD).unwrap();
+
+    let json = map.to_json();
+
+    assert_eq!(TiledMap::from_json(&json), Ok(map));
+    assert!(json.contains("\"tiledversion\":\"1.10.2\""));
+    assert!(json.contains("\"draworder\":\"topdown\""));
+}
+
+#[test]
+fn test_offset_conversion() {
+    for map in maps() {
+        for col in -4..4 {
+            for row in -4..4 {
+                let axial = map.offset_to_axial([col, row]);
+                assert_eq!(map.axial_to_offset(axial), [col, row]);
+            }
+        }
+        // offset neighbours along the row or column are axial neighbours
+        let a = map.offset_to_axial([2, 2]);
+        let b = map.offset_to_axial([2, 3]);
+        let c = map.offset_to_axial([3, 2]);
+        assert!(HexDirection::from_axial([b[0] - a[0], b[1] - a[1]]).is_some());
+        assert!(HexDirection::from_axial([c[0] - a[0], c[1] - a[1]]).is_some());
+    }
+}
+
+#[test]
+fn test_hex_centers() {
+    for map in maps() {
+        let w = map.tilewidth as f32;
+        let h = map.tileheight as f32;
+        let s = map.hexsidelength as f32;
+        let odd = map.staggerindex == StaggerIndex::Odd;
+        let to_pixel = map.orientation().axial_to_pixel();
+        for col in 0..4 {
+            for row in 0..4 {
+                // center of the tile as drawn by Tiled
+                let tiled = match map.orientation() {
+                    Orientation::Pointy => {
+                        let shifted = (row % 2 == 1) == odd;
+                        let x = col as f32 * w + if shifted { w / 2. } else { 0. };
+                        Vec2::new(x + w / 2., row as f32 * (h + s) / 2. + h / 2.)
+                    }
+                    Orientation::Flat => {
+                        let shifted = (col % 2 == 1) == odd;
+                        let y = row as f32 * h + if shifted { h / 2. } else { 0. };
+                        Vec2::new(col as f32 * (w + s) / 2. + w / 2., y + h / 2.)
+                    }
+                };
+                let [q, r] = map.offset_to_axial([col, row]);
+                let expected = to_pixel.right_prod(Vec2::new(q as f32, r as f32)) * 3.;
+
+                let pixel = map.tiled_to_pixel(&tiled, 3.);
+
+                assert!(
+                    (pixel - expected).len() < 1e-3,
+                    "{:?} {:?}",
+                    pixel,
+                    expected
+                );
+                assert!((map.pixel_to_tiled(&pixel, 3.) - tiled).len() < 1e-3);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_write_layer() {
+    let hexagon = Hexagon {
+        center: Vec2::new(3., 3.),
+        radius: 2.,
+    };
+    let tiles = HexMap::from_fn(hexagon, |[q, r]| (q * 10 + r + 100) as u32);
+
+    for mut map in maps() {
+        map.set_layer_from_hex_map("ground", &tiles).unwrap();
+        let map = TiledMap::from_json(&map.to_json()).unwrap();
+
+        let read = map.layer_to_hex_map("ground", hexagon).unwrap();
+
+        // the whole hexagon fits into the 10 by 10 map
+        assert_eq!(read, tiles);
+        assert_eq!(map.layers.len(), 1);
+    }
+}
+
+#[test]
+fn test_invalid_maps() {
+    let orthogonal = POINTY_ODD.replace("\"hexagonal\"", "\"orthogonal\"");
+    assert_eq!(
+        TiledMap::from_json(&orthogonal),
+        Err(TiledError::NotHexagonal)
+    );
+
+    let infinite = POINTY_ODD.replace("\"infinite\": false", "\"infinite\": true");
+    assert_eq!(TiledMap::from_json(&infinite), Err(TiledError::Infinite));
+
+    let short = POINTY_ODD.replace("[1, 0, 2, 0, 3, 0]", "[1, 0, 2]");
+    assert_eq!(
+        TiledMap::from_json(&short),
+        Err(TiledError::LayerSize("terrain".to_owned()))
+    );
+
+    let huge = POINTY_ODD
+        .replacen("\"width\": 3", "\"width\": 65536", 1)
+        .replacen("\"height\": 2", "\"height\": 65536", 1);
+    assert_eq!(TiledMap::from_json(&huge), Err(TiledError::TooLarge));
+
+    let new = |width, height, size| {
+        TiledMap::new(width, height, Orientation::Flat, StaggerIndex::Odd, size)
+    };
+    assert_eq!(new(10, 10, 0), Err(TiledError::InvalidTileSize));
+    assert_eq!(new(10, 10, 1 << 31), Err(TiledError::InvalidTileSize));
+    assert_eq!(new(65536, 65536, 20), Err(TiledError::TooLarge));
+
+    let flat_tiles = POINTY_ODD.replace("\"tilewidth\": 28", "\"tilewidth\": 0");
+    assert_eq!(
+        TiledMap::from_json(&flat_tiles),
+        Err(TiledError::InvalidTileSize)
+    );
+
+    let base64 = POINTY_ODD.replace("[1, 0, 2, 0, 3, 0]", "\"AQAAAA==\"");
+    match TiledMap::from_json(&base64) {
+        Err(TiledError::Json(_)) => {}
+        other => panic!("Expected a Json error, got {:?}", other),
+    }
+}
@@ -1,13 +1,12 @@
 //! Test suite for the Web and headless browsers.
 //
-#![cfg(target_arch = "wasm32")]
-
-use cao_math::mat::mat2::Mat2f;
 use cao_math::array::Array2f;
+use cao_math::mat::mat2::{Mat2, Mat2d, Mat2f};
 use cao_math::vec::vec2::Vec2;
-use wasm_bindgen_test::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_right_prod_2by2() {
     const SQRT3APROX: f32 = 1.73205080757;
 
@@ -23,7 +22,7 @@ fn basic_right_prod_2by2() {
     assert_eq!(res, [SQRT3APROX * 2., 3.].into());
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_left_prod_2by2() {
     const SQRT3APROX: f32 = 1.73205080757;
 
@@ -39,7 +38,7 @@ fn basic_left_prod_2by2() {
     assert_eq!(res, [SQRT3APROX, 3.8660254].into());
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_right_prod_2by2_array() {
     let mut arr = Array2f::new();
     for _ in 0..512 {
@@ -68,7 +67,7 @@ fn basic_right_prod_2by2_array() {
     }
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn inverse_returns_correct_result() {
     let mat = Mat2f {
         x_axis: [4., 7.],
@@ -94,7 +93,7 @@ fn inverse_returns_correct_result() {
     );
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn inverse_returns_none_is_non_invertible() {
     let mat = Mat2f {
         x_axis: [0., 4.],
@@ -116,3 +115,17 @@ fn inverse_returns_none_is_non_invertible() {
         "matrix with 0 column is not invertible"
     );
 }
+
+#[test]
+fn test_generic_scalars() {
+    let mat: Mat2d = [[4., 2.], [7., 6.]].into();
+
+    let inv = mat.inverted().unwrap();
+
+    assert!(mat.mat_mul(&inv).almost_equal(&Mat2::identity(), 1e-12));
+
+    let mat = Mat2::from([[1, 2], [3, 4]]);
+    assert_eq!(mat.det(), -2);
+    assert_eq!(&mat * &Mat2::identity(), mat);
+    assert_eq!(mat.right_prod([1, 1].into()), [4, 6].into());
+}
@@ -1,10 +1,9 @@
-#![cfg(target_arch = "wasm32")]
-
 use cao_math::mat::mat3::Mat3f;
 use std::f32::EPSILON;
-use wasm_bindgen_test::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_left_prod_3by3() {
     let mut mat = Mat3f::scale(1.);
     mat.set(1, 0, 2.);
@@ -15,7 +14,7 @@ fn basic_left_prod_3by3() {
     assert_eq!(res, [1., 4., 3.].into());
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_right_prod_3by3() {
     let mut mat = Mat3f::scale(1.);
     mat.set(1, 0, 2.);
@@ -26,7 +25,7 @@ fn basic_right_prod_3by3() {
     assert_eq!(res, [5., 2., 3.].into());
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_mat_mat_3_ab() {
     let a = Mat3f::from([[1., 2., 3.], [1., 2., 3.], [1., 2., 3.]]);
     let b = Mat3f::from([[5., 6., 7.], [5., 6., 7.], [5., 6., 7.]]);
@@ -38,7 +37,7 @@ fn basic_mat_mat_3_ab() {
     assert_eq!(c.w_axis, [18., 36., 54.]);
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_mat_mat_3_ba() {
     let a = Mat3f::from([[5., 6., 7.], [5., 6., 7.], [5., 6., 7.]]);
     let b = Mat3f::from([[1., 2., 3.], [1., 2., 3.], [1., 2., 3.]]);
@@ -50,7 +49,7 @@ fn basic_mat_mat_3_ba() {
     assert_eq!(c.w_axis, [30.0, 36.0, 42.0]);
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn test_translation() {
     let a = Mat3f::translate([1., 2.].into());
 
@@ -61,7 +60,7 @@ fn test_translation() {
     assert_eq!(res, [1., 2., 1.].into());
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn basic_mat_multiplication() {
     let a = Mat3f::translate([5.0, 6.0].into());
     let b = Mat3f::scale(8.0);
@@ -77,7 +76,7 @@ fn basic_mat_multiplication() {
     assert_eq!(c, control);
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn determinant() {
     let a = Mat3f {
         x_axis: [6., 4., 2.],
@@ -90,7 +89,7 @@ fn determinant() {
     assert!((d - -306.).abs() < EPSILON, "{}", d);
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn inverse_returns_none_is_non_invertible() {
     let mat = Mat3f {
         x_axis: [0., 4., 2.],
@@ -115,7 +114,7 @@ fn inverse_returns_none_is_non_invertible() {
     );
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn inverse_returns_correct_result() {
     let mat = Mat3f {
         x_axis: [0., -3., -2.],
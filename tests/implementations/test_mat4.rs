@@ -0,0 +1,103 @@
+use super::close;
+use cao_math::mat::mat3::Mat3f;
+use cao_math::mat::mat4::Mat4f;
+use cao_math::vec::vec2::Vec2;
+use cao_math::vec::vec3::Vec3;
+use cao_math::vec::vec4::Vec4;
+use std::f32::consts::FRAC_PI_2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn basic_prod_4by4() {
+    let mut mat = Mat4f::identity();
+    mat.set(1, 0, 2.);
+
+    let p = [1., 2., 3., 4.].into();
+
+    assert_eq!(mat.right_prod(&p), [5., 2., 3., 4.].into());
+    assert_eq!(mat.left_prod(&p), [1., 4., 3., 4.].into());
+}
+
+#[test]
+fn test_translate_and_scale() {
+    let mat = Mat4f::translate(Vec3::new(1., -2., 3.)).mat_mul(&Mat4f::scale(2.));
+
+    let p = mat.right_prod(&Vec4::new(1., 1., 1., 1.));
+    assert_eq!(p, Vec4::new(3., 0., 5., 1.));
+    // directions are not translated
+    let d = mat.right_prod(&Vec4::new(1., 1., 1., 0.));
+    assert_eq!(d, Vec4::new(2., 2., 2., 0.));
+    assert_eq!(mat.det(), 8.);
+}
+
+#[test]
+fn test_rotations() {
+    let p = Vec4::new(1., 0., 0., 1.);
+
+    let z = Mat4f::rotation_z(FRAC_PI_2).right_prod(&p);
+    assert!(close(z, Vec4::new(0., 1., 0., 1.), 1e-5), "{:?}", z);
+
+    let y = Mat4f::rotation_y(FRAC_PI_2).right_prod(&p);
+    assert!(close(y, Vec4::new(0., 0., -1., 1.), 1e-5), "{:?}", y);
+
+    let x = Mat4f::rotation_x(FRAC_PI_2).right_prod(&Vec4::new(0., 1., 0., 1.));
+    assert!(close(x, Vec4::new(0., 0., 1., 1.), 1e-5), "{:?}", x);
+
+    // a third of a turn around the diagonal cycles the axes
+    let axis = Vec3::new(1., 1., 1.);
+    let r = Mat4f::rotation(&axis, std::f32::consts::PI * 2. / 3.).right_prod(&p);
+    assert!(close(r, Vec4::new(0., 1., 0., 1.), 1e-5), "{:?}", r);
+
+    let rz = Mat4f::rotation(&Vec3::new(0., 0., 2.), 0.7);
+    assert!(rz.almost_equal(&Mat4f::rotation_z(0.7), 1e-6));
+}
+
+#[test]
+fn test_inverse() {
+    let mat = Mat4f::translate(Vec3::new(4., 5., -6.))
+        .mat_mul(&Mat4f::rotation(&Vec3::new(1., 2., 3.), 0.5))
+        .mat_mul(&Mat4f::scale(3.));
+
+    let inv = mat.inverted().expect("invertible");
+
+    assert!(mat.mat_mul(&inv).almost_equal(&Mat4f::identity(), 1e-5));
+    assert!(inv.mat_mul(&mat).almost_equal(&Mat4f::identity(), 1e-5));
+    assert!((mat.det() - 27.).abs() < 1e-3);
+
+    let mut singular = Mat4f::identity();
+    singular.z_axis = [0.; 4];
+    assert!(singular.inverted().is_none());
+}
+
+#[test]
+fn test_transposed_and_list() {
+    let mat = Mat4f::from([
+        [1., 2., 3., 4.],
+        [5., 6., 7., 8.],
+        [9., 10., 11., 12.],
+        [13., 14., 15., 16.],
+    ]);
+
+    let t = mat.transposed();
+    assert_eq!(t.x_axis, [1., 5., 9., 13.]);
+    assert_eq!(t.transposed(), mat);
+    assert_eq!(
+        mat.as_list(),
+        (1..=16).map(|x| x as f32).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_from_mat3() {
+    let rot = Mat3f::rotation(0.3);
+
+    let mat = rot.to_mat4();
+    assert!(mat.almost_equal(&Mat4f::rotation_z(0.3), 1e-6));
+
+    let transform = Mat3f::translate(Vec2::new(2., 3.)).mat_mul(&rot);
+    let mat = Mat4f::from_2d_transform(&transform);
+    let p = transform.right_prod(&Vec3::new(1., 1., 1.));
+    let q = mat.right_prod(&Vec4::new(1., 1., 7., 1.));
+    assert_eq!(q, Vec4::new(p.x, p.y, 7., 1.));
+}
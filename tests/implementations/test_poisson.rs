@@ -0,0 +1,103 @@
+use cao_math::array::Array2f;
+use cao_math::hex::{self, Hexagon};
+use cao_math::rand::{self, Rng};
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn assert_min_distance(points: &Array2f, radius: f32) {
+    for (i, a) in points.data.iter().enumerate() {
+        for b in points.data[i + 1..].iter() {
+            assert!((*a - *b).len() >= radius, "{:?} {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn test_poisson_disk_rect() {
+    let mut rng = Rng::new(42);
+    let min = Vec2::new(-10., 5.);
+    let max = Vec2::new(30., 25.);
+
+    let points = rand::poisson_disk_rect(&mut rng, &min, &max, 2., None);
+
+    // a 40x20 box fits about 800/(2*2) / 2 points with this radius
+    assert!(points.data.len() > 60, "{}", points.data.len());
+    for p in points.data.iter() {
+        assert!(min.x <= p.x && p.x < max.x && min.y <= p.y && p.y < max.y);
+    }
+    assert_min_distance(&points, 2.);
+}
+
+#[test]
+fn test_poisson_disk_is_deterministic() {
+    let min = Vec2::new(0., 0.);
+    let max = Vec2::new(10., 10.);
+
+    let a = rand::poisson_disk_rect(&mut Rng::new(7), &min, &max, 1., None);
+    let b = rand::poisson_disk_rect(&mut Rng::new(7), &min, &max, 1., None);
+
+    assert_eq!(a.data, b.data);
+}
+
+#[test]
+fn test_poisson_disk_polygon() {
+    let mut rng = Rng::new(42);
+    let mut polygon = Array2f::new();
+    for p in [[0., 0.], [20., 0.], [0., 20.]].iter() {
+        polygon.push((*p).into());
+    }
+
+    let points = rand::poisson_disk_polygon(&mut rng, &polygon, 1.5, Some(20));
+
+    assert!(points.data.len() > 20, "{}", points.data.len());
+    for p in points.data.iter() {
+        assert!(p.x >= 0. && p.y >= 0. && p.x + p.y <= 20., "{:?}", p);
+    }
+    assert_min_distance(&points, 1.5);
+}
+
+#[test]
+fn test_poisson_disk_hexagon() {
+    const D: u32 = 3;
+    let mut rng = Rng::new(42);
+    let hexagon = Hexagon::from_radius(6.);
+
+    let points = rand::poisson_disk_hexagon(&mut rng, &hexagon, D);
+
+    let cubes = points
+        .data
+        .iter()
+        .map(|p| hex::axial_to_cube(p))
+        .collect::<Vec<_>>();
+    for (i, a) in cubes.iter().enumerate() {
+        for b in cubes[i + 1..].iter() {
+            assert!(hex::cube_distance(a, b) >= D);
+        }
+    }
+    // the sampling is maximal: every hex is closer than D to a picked one
+    for [q, r] in hexagon.iter_points() {
+        let c = hex::axial_to_cube(&Vec2::new(q as f32, r as f32));
+        assert!(cubes.iter().any(|p| hex::cube_distance(p, &c) < D));
+    }
+    for p in points.data.iter() {
+        assert!(hexagon.contains(p));
+    }
+}
+
+#[test]
+fn test_poisson_disk_region() {
+    let mut rng = Rng::new(42);
+    let mut region = Array2f::new();
+    for q in 0..10 {
+        region.push(Vec2::new(q as f32, 0.));
+    }
+
+    let points = rand::poisson_disk_region(&mut rng, &region, 2);
+
+    assert!(points.data.len() >= 4, "{:?}", points.data);
+    for p in points.data.iter() {
+        assert!(region.data.contains(p));
+    }
+    assert_min_distance(&points, 2.);
+}
@@ -0,0 +1,150 @@
+use super::close;
+use cao_math::mat::mat4::{Mat4d, Mat4f};
+use cao_math::mat::projection::ClipDepth;
+use cao_math::vec::vec2::Vec2;
+use cao_math::vec::vec3::Vec3;
+use cao_math::vec::vec4::Vec4;
+use std::f32::consts::FRAC_PI_2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn project(mat: &Mat4f, p: Vec3<f32>) -> Vec3<f32> {
+    Vec3::from_homogeneous(mat.right_prod(&p.extend(Some(1.))))
+}
+
+#[test]
+fn test_perspective_reference() {
+    // reference values from gl-matrix `perspective` and `perspectiveZO`
+    let gl = Mat4f::perspective(FRAC_PI_2, 2., 1., 100., ClipDepth::WebGl);
+    let expected = Mat4f::from([
+        [0.5, 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., -101. / 99., -1.],
+        [0., 0., -200. / 99., 0.],
+    ]);
+    assert!(gl.almost_equal(&expected, 1e-5), "{:?}", gl);
+
+    let gpu = Mat4f::perspective(FRAC_PI_2, 2., 1., 100., ClipDepth::WebGpu);
+    let expected = Mat4f::from([
+        [0.5, 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., -100. / 99., -1.],
+        [0., 0., -100. / 99., 0.],
+    ]);
+    assert!(gpu.almost_equal(&expected, 1e-5), "{:?}", gpu);
+}
+
+#[test]
+fn test_perspective_depth_range() {
+    for &(depth, near_z) in &[(ClipDepth::WebGl, -1.), (ClipDepth::WebGpu, 0.)] {
+        let mat = Mat4f::perspective(1., 1.5, 0.5, 50., depth);
+        let near = project(&mat, Vec3::new(0., 0., -0.5));
+        let far = project(&mat, Vec3::new(0., 0., -50.));
+        assert!(close(near, Vec3::new(0., 0., near_z), 1e-4), "{:?}", near);
+        assert!(close(far, Vec3::new(0., 0., 1.), 1e-4), "{:?}", far);
+    }
+}
+
+#[test]
+fn test_ortho_reference() {
+    let mat = Mat4f::ortho(-1., 1., -1., 1., -1., 1., ClipDepth::WebGl);
+    let mut expected = Mat4f::identity();
+    expected.set(2, 2, -1.);
+    assert!(mat.almost_equal(&expected, 1e-5), "{:?}", mat);
+
+    // reference values from gl-matrix `ortho` and `orthoZO`
+    let gl = Mat4f::ortho(0., 800., 600., 0., 0.1, 10., ClipDepth::WebGl);
+    let expected = Mat4f::from([
+        [2. / 800., 0., 0., 0.],
+        [0., -2. / 600., 0., 0.],
+        [0., 0., -2. / 9.9, 0.],
+        [-1., 1., -10.1 / 9.9, 1.],
+    ]);
+    assert!(gl.almost_equal(&expected, 1e-5), "{:?}", gl);
+
+    let gpu = Mat4f::ortho(0., 800., 600., 0., 0.1, 10., ClipDepth::WebGpu);
+    let expected = Mat4f::from([
+        [2. / 800., 0., 0., 0.],
+        [0., -2. / 600., 0., 0.],
+        [0., 0., -1. / 9.9, 0.],
+        [-1., 1., -0.1 / 9.9, 1.],
+    ]);
+    assert!(gpu.almost_equal(&expected, 1e-5), "{:?}", gpu);
+
+    let near = project(&gpu, Vec3::new(800., 0., -0.1));
+    assert!(close(near, Vec3::new(1., 1., 0.), 1e-4), "{:?}", near);
+}
+
+#[test]
+fn test_look_at() {
+    let view = Mat4f::look_at(
+        &Vec3::new(0., 0., 1.),
+        &Vec3::new(0., 0., 0.),
+        &Vec3::new(0., 1., 0.),
+    );
+    let expected = Mat4f::translate(Vec3::new(0., 0., -1.));
+    assert!(view.almost_equal(&expected, 1e-5), "{:?}", view);
+
+    // camera on the +X axis looking at the origin, world -Z is to its right
+    let view = Mat4f::look_at(
+        &Vec3::new(5., 0., 0.),
+        &Vec3::new(0., 0., 0.),
+        &Vec3::new(0., 1., 0.),
+    );
+    let p = view.right_prod(&Vec4::new(0., 0., 0., 1.));
+    assert!(close(p.truncate(), Vec3::new(0., 0., -5.), 1e-4), "{:?}", p);
+    let p = view.right_prod(&Vec4::new(5., 0., -1., 1.));
+    assert!(close(p.truncate(), Vec3::new(1., 0., 0.), 1e-4), "{:?}", p);
+}
+
+#[test]
+fn test_unproject_center() {
+    let eye = Vec3::new(1., 2., 3.);
+    let target = Vec3::new(1., 2., -7.);
+    let view = Mat4f::look_at(&eye, &target, &Vec3::new(0., 1., 0.));
+    let viewport = Vec2::new(640., 480.);
+
+    for &depth in &[ClipDepth::WebGl, ClipDepth::WebGpu] {
+        let proj = Mat4f::perspective(FRAC_PI_2, 640. / 480., 0.1, 100., depth);
+        let ray = proj
+            .mat_mul(&view)
+            .unproject(Vec2::new(320., 240.), viewport, depth)
+            .expect("invertible");
+        assert!(close(ray.origin, Vec3::new(1., 2., 2.9), 1e-4), "{:?}", ray);
+        assert!(
+            close(ray.direction, Vec3::new(0., 0., -1.), 1e-4),
+            "{:?}",
+            ray
+        );
+    }
+}
+
+#[test]
+fn test_unproject_round_trip() {
+    let view = Mat4d::look_at(
+        &Vec3::new(-3., 4., 2.),
+        &Vec3::new(1., 0., -1.),
+        &Vec3::new(0., 1., 0.),
+    );
+    let viewport = Vec2::new(800., 600.);
+    for &depth in &[ClipDepth::WebGl, ClipDepth::WebGpu] {
+        let view_proj = Mat4d::perspective(1., 800. / 600., 0.5, 200., depth).mat_mul(&view);
+        let world = Vec3::new(2., -1., -3.);
+        let clip = view_proj.right_prod(&world.extend(Some(1.)));
+        let ndc = Vec3::from_homogeneous(clip);
+        let screen = Vec2::new((ndc.x + 1.) * 400., (1. - ndc.y) * 300.);
+
+        let ray = view_proj
+            .unproject(screen, viewport, depth)
+            .expect("invertible");
+        let t = (world - ray.origin).dot(&ray.direction);
+        assert!((ray.at(t) - world).len() < 1e-9, "{:?} {:?}", ray, world);
+    }
+}
+
+#[test]
+fn test_unproject_singular() {
+    let mat = Mat4f::scale(0.);
+    let ray = mat.unproject(Vec2::new(1., 1.), Vec2::new(2., 2.), ClipDepth::WebGl);
+    assert!(ray.is_none());
+}
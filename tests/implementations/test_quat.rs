@@ -0,0 +1,140 @@
+use super::close;
+use cao_math::mat::mat3::Mat3f;
+use cao_math::quat::{Quat, Quatd, Quatf};
+use cao_math::vec::vec3::Vec3;
+use std::f32::consts::{FRAC_PI_2, PI};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn test_axis_angle_rotation() {
+    let q = Quatf::from_axis_angle(&Vec3::new(0., 0., 2.), FRAC_PI_2);
+    let half = FRAC_PI_2 / 2.;
+    assert!(q.almost_equal(&Quat::new(0., 0., half.sin(), half.cos()), 1e-6));
+    assert!((q.len() - 1.).abs() < 1e-6);
+
+    let v = q.rotate(&Vec3::new(1., 0., 0.));
+    assert!(close(v, Vec3::new(0., 1., 0.), 1e-5), "{:?}", v);
+    let v = q * Vec3::new(0., 1., 5.);
+    assert!(close(v, Vec3::new(-1., 0., 5.), 1e-5), "{:?}", v);
+
+    let q = Quatf::from_axis_angle(&Vec3::new(1., 1., 1.), 2. * PI / 3.);
+    let v = q.rotate(&Vec3::new(1., 0., 0.));
+    assert!(close(v, Vec3::new(0., 1., 0.), 1e-5), "{:?}", v);
+}
+
+#[test]
+fn test_composition_and_inverse() {
+    let a = Quatf::from_axis_angle(&Vec3::new(1., 0., 0.), 0.3);
+    let b = Quatf::from_axis_angle(&Vec3::new(0., 1., 0.), -1.2);
+    let v = Vec3::new(1., 2., 3.);
+
+    let ab = (a * b).rotate(&v);
+    assert!(close(ab, a.rotate(&b.rotate(&v)), 1e-5), "{:?}", ab);
+
+    let back = a.conjugate().rotate(&a.rotate(&v));
+    assert!(close(back, v, 1e-5), "{:?}", back);
+
+    let q = a * 3.;
+    let inv = q.inverse().unwrap();
+    assert!((q * inv).almost_equal(&Quat::identity(), 1e-6));
+    assert!(Quatf::new(0., 0., 0., 0.).inverse().is_none());
+}
+
+#[test]
+fn test_euler() {
+    let (x, y, z) = (0.4, -0.7, 1.9);
+    let q = Quatf::from_euler(x, y, z);
+    let expected = Quatf::from_axis_angle(&Vec3::new(0., 0., 1.), z)
+        * Quatf::from_axis_angle(&Vec3::new(0., 1., 0.), y)
+        * Quatf::from_axis_angle(&Vec3::new(1., 0., 0.), x);
+    assert!(q.almost_equal(&expected, 1e-6));
+
+    let q = Quatf::from_euler(FRAC_PI_2, 0., FRAC_PI_2);
+    // Y goes to Z by the X rotation, Z is not moved by the Z rotation
+    let v = q.rotate(&Vec3::new(0., 1., 0.));
+    assert!(close(v, Vec3::new(0., 0., 1.), 1e-5), "{:?}", v);
+    // X is not moved by the X rotation, goes to Y by the Z rotation
+    let v = q.rotate(&Vec3::new(1., 0., 0.));
+    assert!(close(v, Vec3::new(0., 1., 0.), 1e-5), "{:?}", v);
+}
+
+#[test]
+fn test_mat3_conversion() {
+    let q = Quatf::from_axis_angle(&Vec3::new(0., 0., 1.), FRAC_PI_2);
+    let expected = Mat3f::from([[0., 1., 0.], [-1., 0., 0.], [0., 0., 1.]]);
+    assert!(
+        q.to_mat3().almost_equal(&expected, 1e-6),
+        "{:?}",
+        q.to_mat3()
+    );
+
+    let v = Vec3::new(-2., 0.5, 3.);
+    // covers every branch of `from_mat3`
+    for &(axis, angle) in &[
+        (Vec3::new(1., 2., 3.), 0.5),
+        (Vec3::new(1., 0.1, 0.), 3.),
+        (Vec3::new(0.1, 1., 0.), 3.),
+        (Vec3::new(0., 0.1, 1.), 3.),
+    ] {
+        let q = Quatf::from_axis_angle(&axis, angle);
+        let mat = q.to_mat3();
+        let rotated = mat.right_prod(&v);
+        assert!(close(rotated, q.rotate(&v), 1e-5), "{:?}", rotated);
+
+        let back = Quatf::from_mat3(&mat);
+        assert!(
+            back.almost_equal(&q, 1e-5) || back.almost_equal(&-q, 1e-5),
+            "{:?} {:?}",
+            back,
+            q
+        );
+    }
+}
+
+#[test]
+fn test_slerp() {
+    let axis = Vec3::new(0., 1., 0.);
+    let a = Quatd::from_axis_angle(&axis, 0.2);
+    let b = Quatd::from_axis_angle(&axis, 1.4);
+
+    assert!(a.slerp(&b, 0.).almost_equal(&a, 1e-12));
+    assert!(a.slerp(&b, 1.).almost_equal(&b, 1e-12));
+    for &t in &[0.25, 0.5, 0.9] {
+        let expected = Quatd::from_axis_angle(&axis, 0.2 + 1.2 * t);
+        assert!(a.slerp(&b, t).almost_equal(&expected, 1e-12));
+    }
+
+    // takes the shorter arc
+    let s = a.slerp(&-b, 0.5);
+    assert!(s.almost_equal(&Quatd::from_axis_angle(&axis, 0.8), 1e-12));
+
+    // nearly identical rotations do not divide by zero
+    let s = a.slerp(&a, 0.5);
+    assert!(s.almost_equal(&a, 1e-12), "{:?}", s);
+}
+
+#[test]
+fn test_slerp_with_itself() {
+    // the dot product of these with themselves is often slightly above 1 in f32
+    for i in 0..2000 {
+        let i = i as f32;
+        let axis = Vec3::new(i.sin(), (i * 0.7).cos(), 0.5 + (i * 1.3).sin());
+        let q = Quatf::from_axis_angle(&axis, i * 0.01);
+        let s = q.slerp(&q, 0.5);
+        assert!(s.almost_equal(&q, 1e-6), "{:?} {:?}", q, s);
+    }
+}
+
+#[test]
+fn test_nlerp() {
+    let axis = Vec3::new(1., 0., 0.);
+    let a = Quatf::from_axis_angle(&axis, 0.);
+    let b = Quatf::from_axis_angle(&axis, 1.);
+
+    let n = a.nlerp(&b, 0.5);
+    assert!((n.len() - 1.).abs() < 1e-6);
+    // halfway is exact for nlerp too, by symmetry
+    assert!(n.almost_equal(&Quatf::from_axis_angle(&axis, 0.5), 1e-6));
+    assert!(a.nlerp(&-b, 1.).almost_equal(&b, 1e-6));
+}
@@ -0,0 +1,112 @@
+use cao_math::array::Array2f;
+use cao_math::hex::Hexagon;
+use cao_math::rand::Rng;
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn test_pcg32_reference_values() {
+    let mut rng = Rng::with_stream(42, 54);
+
+    let values = (0..6).map(|_| rng.next_u32()).collect::<Vec<_>>();
+
+    assert_eq!(
+        values,
+        vec![0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e]
+    );
+}
+
+#[test]
+fn test_same_seed_same_sequence() {
+    let mut a = Rng::new(0xcafe);
+    let mut b = Rng::new(0xcafe);
+
+    for _ in 0..100 {
+        assert_eq!(a.next_f32().to_bits(), b.next_f32().to_bits());
+    }
+}
+
+#[test]
+fn test_range_i32_is_in_range() {
+    let mut rng = Rng::new(1);
+
+    for _ in 0..1000 {
+        let x = rng.range_i32(-3, 4);
+        assert!(-3 <= x && x < 4, "{}", x);
+    }
+    assert_eq!(rng.range_i32(5, 5), 5);
+}
+
+#[test]
+fn test_hex_in_hexagon_covers_the_hexagon() {
+    let mut rng = Rng::new(2);
+    let hex = Hexagon::from_radius(2.0);
+
+    let mut seen = Vec::new();
+    for _ in 0..1000 {
+        let p = rng.hex_in_hexagon(&hex).unwrap();
+        assert!(hex.contains(&p), "{:?}", p);
+        if !seen.contains(&p) {
+            seen.push(p);
+        }
+    }
+
+    assert_eq!(seen.len(), hex.num_points());
+
+    let empty = Hexagon::from_radius(-1.0);
+    assert_eq!(empty.num_points(), empty.iter_points().count());
+    assert_eq!(rng.hex_in_hexagon(&empty), None);
+}
+
+#[test]
+fn test_point_in_circle() {
+    let mut rng = Rng::new(3);
+    let center = Vec2::new(10., -4.);
+
+    for _ in 0..1000 {
+        let p = rng.point_in_circle(&center, 2.5);
+        assert!((p - center).len() <= 2.5);
+    }
+}
+
+#[test]
+fn test_point_in_polygon() {
+    let mut rng = Rng::new(4);
+    // L shaped polygon
+    let mut polygon = Array2f::new();
+    for p in [[0., 0.], [4., 0.], [4., 1.], [1., 1.], [1., 4.], [0., 4.]].iter() {
+        polygon.push((*p).into());
+    }
+
+    for _ in 0..1000 {
+        let p = rng.point_in_polygon(&polygon).unwrap();
+        assert!(p.x >= 0. && p.y >= 0.);
+        assert!(p.x <= 1. || p.y <= 1., "{:?}", p);
+    }
+
+    polygon.data.truncate(2);
+    assert!(rng.point_in_polygon(&polygon).is_none());
+}
+
+#[test]
+fn test_unit_vec2() {
+    let mut rng = Rng::new(5);
+
+    for _ in 0..1000 {
+        let v = rng.unit_vec2();
+        assert!((v.len() - 1.).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_shuffle_is_a_permutation() {
+    let mut rng = Rng::new(6);
+    let mut items = (0..50).collect::<Vec<_>>();
+
+    rng.shuffle(&mut items);
+
+    assert_ne!(items, (0..50).collect::<Vec<_>>());
+    items.sort();
+    assert_eq!(items, (0..50).collect::<Vec<_>>());
+}
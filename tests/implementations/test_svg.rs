@@ -0,0 +1,75 @@
+use cao_math::array::Array2f;
+use cao_math::hex::{Hexagon, Orientation};
+use cao_math::svg::SvgWriter;
+use cao_math::vec::vec2::Vec2;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn test_empty_document() {
+    let svg = SvgWriter::new(Orientation::Pointy, 10.).to_svg();
+
+    assert_eq!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-5 -5 10 10\">\n</svg>\n"
+    );
+}
+
+#[test]
+fn test_hex_corners() {
+    let mut svg = SvgWriter::new(Orientation::Flat, 10.);
+
+    svg.hex(0, 0, Some("red".to_owned()), None);
+
+    let svg = svg.to_svg();
+    assert!(
+        svg.contains(r#"<polygon points="10,0 5,8.66 -5,8.66 -10,0 -5,-8.66 5,-8.66" fill="red""#)
+    );
+    assert!(svg.contains(r#"viewBox="-15 -13.66 30 27.321""#), "{}", svg);
+}
+
+#[test]
+fn test_grid_with_labels() {
+    let mut svg = SvgWriter::new(Orientation::Pointy, 10.);
+    let hexagon = Hexagon::from_radius(2.);
+
+    svg.hexagon_with(&hexagon, |[q, r]| {
+        let fill = if q == r {
+            Some("#0f0".to_owned())
+        } else {
+            None
+        };
+        (fill, Some(format!("{}<{}", q, r)))
+    });
+
+    let svg = svg.to_svg();
+    assert_eq!(svg.matches("<polygon").count(), 19);
+    assert_eq!(svg.matches("fill=\"#0f0\"").count(), 3);
+    assert_eq!(svg.matches("<text").count(), 19);
+    assert!(svg.contains(">2&lt;2</text>"));
+    assert!(!svg.contains("2<2"));
+}
+
+#[test]
+fn test_geometry() {
+    let mut svg = SvgWriter::new(Orientation::Pointy, 10.);
+    let points = Array2f {
+        data: vec![Vec2::new(0., 0.), Vec2::new(100., 0.), Vec2::new(0., 50.)],
+    };
+    let path = Array2f {
+        data: vec![Vec2::new(0., 0.), Vec2::new(1., 0.)],
+    };
+
+    svg.polyline(&points, "blue", 2.);
+    svg.polygon(&points, "yellow", None);
+    svg.points(&points, 3., "black");
+    svg.hex_path(&path, "green", 1.);
+
+    let svg = svg.to_svg();
+    assert!(svg.contains(r#"<polyline points="0,0 100,0 0,50" fill="none" stroke="blue""#));
+    assert!(svg.contains(r#"<polygon points="0,0 100,0 0,50" fill="yellow" stroke="none"/>"#));
+    assert_eq!(svg.matches("<circle").count(), 3);
+    assert!(svg.contains(r#"<polyline points="0,0 17.321,0""#));
+    // points extend the view box by their radius, the margin is half a hex
+    assert!(svg.contains(r#"viewBox="-8 -8 116 66""#), "{}", svg);
+}
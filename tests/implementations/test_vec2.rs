@@ -1,8 +1,7 @@
-#![cfg(target_arch = "wasm32")]
-
-use cao_math::vec::vec2::Vec2;
+use cao_math::vec::vec2::{Vec2, Vec2d, Vec2i};
 use std::f32::{consts::PI, EPSILON};
-use wasm_bindgen_test::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
 
 fn perform_angle_test(u: [f32; 2], v: [f32; 2], exp: f32) {
     let u: Vec2 = u.into();
@@ -14,7 +13,7 @@ fn perform_angle_test(u: [f32; 2], v: [f32; 2], exp: f32) {
     assert!(diff <= EPSILON);
 }
 
-#[wasm_bindgen_test]
+#[test]
 fn test_angle_basic() {
     perform_angle_test([1.0, 0.0], [0.0, 1.0], PI / 2.0);
     perform_angle_test([0.0, 1.0], [1.0, 0.0], PI / 2.0);
@@ -22,3 +21,24 @@ fn test_angle_basic() {
     perform_angle_test([0.0, 1.0], [0.0, 1.0], 0.0);
     perform_angle_test([0.13, -4.2], [-0.13, 4.2], PI);
 }
+
+#[test]
+fn test_integer_vectors() {
+    let a = Vec2i::new(3, -2);
+    let b = Vec2i::new(1, 4);
+
+    assert_eq!(a + b, Vec2::new(4, 2));
+    assert_eq!(a * 2, Vec2::new(6, -4));
+    assert_eq!(a.dot(&b), -5);
+    assert_eq!(a.len_sq(), 13);
+    assert_eq!(Vec2::orient2d(&a, &b, &Vec2::new(0, 0)), 14);
+}
+
+#[test]
+fn test_double_vectors() {
+    let v = Vec2d::new(3., 4.);
+
+    assert_eq!(v.len(), 5.);
+    assert_eq!(v.normalized(), Vec2::new(0.6, 0.8));
+    assert_eq!(v.extend(Some(1.)).z, 1f64);
+}
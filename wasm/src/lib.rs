@@ -0,0 +1,5 @@
+//! The npm package of cao-math
+//!
+//! The JS bindings are exported by `cao-math` itself, this crate only links them into a `cdylib`.
+//! Keeping the `cdylib` out of `cao-math` lets `no_std` crates depend on it.
+pub use cao_math::*;